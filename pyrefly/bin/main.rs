@@ -27,8 +27,15 @@ use library::run::BuckCheckArgs;
 use library::run::CheckArgs;
 use library::run::CommandExitStatus;
 use library::run::CommonGlobalArgs;
+use library::run::ConfigOverrideArgs;
+use library::run::DepsArgs;
 use library::run::InitArgs;
 use library::run::LspArgs;
+use library::run::MergeShardsArgs;
+#[cfg(unix)]
+use library::run::daemon_project_root;
+#[cfg(unix)]
+use library::run::default_daemon_socket;
 use library::standard_config_finder;
 use path_absolutize::Absolutize;
 use pyrefly::library::library::library::library;
@@ -86,6 +93,62 @@ struct FullCheckArgs {
     /// Watch for file changes and re-check them.
     #[arg(long, env = clap_env("WATCH"), conflicts_with = "check_all")]
     watch: bool,
+    /// Ask a running `pyrefly daemon` to recheck instead of checking from scratch.
+    /// The daemon checks the files and uses the settings it was started with, so only the flags
+    /// about how to output the errors can be given.
+    #[arg(
+        long,
+        env = clap_env("DAEMON"),
+        conflicts_with_all = [
+            "files",
+            "project_excludes",
+            "watch",
+            "check_all",
+            "suppress_errors",
+            "expectations",
+            "remove_unused_ignores",
+            "changed_since",
+            "memory_budget",
+            "shard",
+            "count_errors",
+            "summarize_errors",
+            "debug_info",
+            "report_binding_memory",
+            "report_trace",
+            "report_timings",
+            "report_chrome_trace",
+            "report_glean",
+            "progress",
+            "search_path",
+            "python_version",
+            "python_platform",
+            "site_package_path",
+            "python_interpreter",
+            "conda_environment",
+            "use_untyped_imports",
+            "replace_imports_with_any",
+            "ignore_missing_source",
+            "ignore_errors_in_generated_code",
+            "untyped_def_behavior",
+            "solve_budget_ms",
+            "strict",
+            "explicit_reexport",
+        ]
+    )]
+    daemon: bool,
+    /// With `--daemon`, the files that changed since the last check. If none are given, the
+    /// daemon rereads every file it checks.
+    #[arg(long, requires = "daemon", num_args = 1.., value_name = "FILE")]
+    changed: Vec<PathBuf>,
+    /// With `--daemon`, ask the daemon to stop instead of checking.
+    #[arg(long, requires = "daemon", conflicts_with = "changed")]
+    stop: bool,
+    /// The socket used to talk to `pyrefly daemon`.
+    /// Defaults to a socket specific to the project root, i.e. the directory of the config file,
+    /// or else the root of the git repository, or else the current directory. It lives in
+    /// `$XDG_RUNTIME_DIR` or else in a private directory under the temporary directory.
+    #[arg(long, env = clap_env("DAEMON_SOCKET"), value_name = "FILE")]
+    daemon_socket: Option<PathBuf>,
 
     /// Explicitly set the Pyre configuration to use when type checking or starting a language server.
     /// In "single-file checking mode," this config is applied to all files being checked, ignoring
@@ -101,6 +164,22 @@ struct FullCheckArgs {
     args: CheckArgs,
 }
 
+#[derive(Debug, Clone, Parser)]
+struct FullDepsArgs {
+    /// Files to start the import graph from (glob supported).
+    /// If no file is specified, use the files of the project defined by the closest configuration file.
+    files: Vec<String>,
+    /// Files to exclude.
+    #[arg(long, env = clap_env("PROJECT_EXCLUDES"))]
+    project_excludes: Option<Vec<String>>,
+    /// Explicitly set the Pyrefly configuration to use, as for `check`.
+    #[arg(long, short, env = clap_env("CONFIG"), value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    #[command(flatten)]
+    args: DepsArgs,
+}
+
 #[derive(Debug, Clone, Subcommand)]
 enum Command {
     /// Full type checking on a file or a project
@@ -119,6 +198,16 @@ enum Command {
     /// Start an LSP server
     Lsp(LspArgs),
 
+    /// Start a server that keeps a project checked in the background, so that
+    /// `pyrefly check --daemon` can recheck incrementally. Takes the same arguments as `check`.
+    Daemon(FullCheckArgs),
+
+    /// Print the import graph of a project, as resolved by the checker, and any import cycles in it.
+    Deps(FullDepsArgs),
+
+    /// Combine the outputs of `pyrefly check --shard i/N` into a single report.
+    MergeShards(MergeShardsArgs),
+
     Autotype(FullCheckArgs),
 }
 
@@ -155,7 +244,73 @@ async fn run_check(
     }
 }
 
-fn config_finder(args: library::run::CheckArgs) -> ConfigFinder {
+#[cfg(unix)]
+fn daemon_socket(
+    socket: Option<PathBuf>,
+    config: Option<&Path>,
+    args: &ConfigOverrideArgs,
+) -> anyhow::Result<PathBuf> {
+    match socket {
+        Some(socket) => Ok(socket),
+        None => default_daemon_socket(&daemon_project_root(config, &config_finder(args.clone()))?),
+    }
+}
+
+#[cfg(unix)]
+fn run_daemon_client(
+    args: library::run::CheckArgs,
+    socket: Option<PathBuf>,
+    config: Option<PathBuf>,
+    changed: Vec<PathBuf>,
+    stop: bool,
+) -> anyhow::Result<CommandExitStatus> {
+    let socket = daemon_socket(socket, config.as_deref(), args.config_override())?;
+    let changed = if changed.is_empty() {
+        None
+    } else {
+        Some(changed)
+    };
+    args.run_daemon_client(&socket, changed, stop)
+}
+
+#[cfg(not(unix))]
+fn run_daemon_client(
+    _args: library::run::CheckArgs,
+    _socket: Option<PathBuf>,
+    _config: Option<PathBuf>,
+    _changed: Vec<PathBuf>,
+    _stop: bool,
+) -> anyhow::Result<CommandExitStatus> {
+    Err(anyhow::anyhow!("`--daemon` is only supported on Unix"))
+}
+
+#[cfg(unix)]
+fn run_daemon(
+    args: library::run::CheckArgs,
+    socket: Option<PathBuf>,
+    config: Option<PathBuf>,
+    files_to_check: FilteredGlobs,
+    config_finder: ConfigFinder,
+) -> anyhow::Result<CommandExitStatus> {
+    let socket = daemon_socket(socket, config.as_deref(), args.config_override())?;
+    args.run_daemon(&socket, files_to_check, config_finder)?;
+    Ok(CommandExitStatus::Success)
+}
+
+#[cfg(not(unix))]
+fn run_daemon(
+    _args: library::run::CheckArgs,
+    _socket: Option<PathBuf>,
+    _config: Option<PathBuf>,
+    _files_to_check: FilteredGlobs,
+    _config_finder: ConfigFinder,
+) -> anyhow::Result<CommandExitStatus> {
+    Err(anyhow::anyhow!(
+        "`pyrefly daemon` is only supported on Unix"
+    ))
+}
+
+fn config_finder(args: ConfigOverrideArgs) -> ConfigFinder {
     standard_config_finder(Arc::new(move |_, x| args.override_config(x)))
 }
 
@@ -165,7 +320,7 @@ fn absolutize(globs: Globs) -> anyhow::Result<Globs> {
 
 fn get_explicit_config(
     path: &Path,
-    args: &library::run::CheckArgs,
+    args: &ConfigOverrideArgs,
 ) -> (ArcId<ConfigFile>, Vec<ConfigError>) {
     let (file_config, parse_errors) = ConfigFile::from_file(path);
     let (config, validation_errors) = args.override_config(file_config);
@@ -191,7 +346,7 @@ fn add_config_errors(config_finder: &ConfigFinder, errors: Vec<ConfigError>) ->
 fn get_globs_and_config_for_project(
     config: Option<PathBuf>,
     project_excludes: Option<Globs>,
-    args: &library::run::CheckArgs,
+    args: &ConfigOverrideArgs,
 ) -> anyhow::Result<(FilteredGlobs, ConfigFinder)> {
     let (config, errors) = match config {
         Some(explicit) => get_explicit_config(&explicit, args),
@@ -240,7 +395,7 @@ fn get_globs_and_config_for_files(
     config: Option<PathBuf>,
     files_to_check: Globs,
     project_excludes: Option<Globs>,
-    args: &library::run::CheckArgs,
+    args: &ConfigOverrideArgs,
 ) -> anyhow::Result<(FilteredGlobs, ConfigFinder)> {
     let project_excludes = project_excludes.unwrap_or_else(ConfigFile::default_project_excludes);
     let files_to_check = absolutize(files_to_check)?;
@@ -281,7 +436,7 @@ fn get_globs_and_config(
     files: Vec<String>,
     project_excludes: Option<Vec<String>>,
     config: Option<PathBuf>,
-    args: &mut library::run::CheckArgs,
+    args: &mut ConfigOverrideArgs,
 ) -> anyhow::Result<(FilteredGlobs, ConfigFinder)> {
     args.absolute_search_path();
     args.validate()?;
@@ -303,25 +458,59 @@ async fn run_command(command: Command, allow_forget: bool) -> anyhow::Result<Com
             files,
             project_excludes,
             watch,
+            daemon,
+            changed,
+            stop,
+            daemon_socket,
             config,
             mut args,
         }) => {
+            if daemon {
+                return run_daemon_client(args, daemon_socket, config, changed, stop);
+            }
             let (files_to_check, config_finder) =
-                get_globs_and_config(files, project_excludes, config, &mut args)?;
+                get_globs_and_config(files, project_excludes, config, args.config_override_mut())?;
             run_check(args, watch, files_to_check, config_finder, allow_forget).await
         }
+        Command::Daemon(FullCheckArgs {
+            files,
+            project_excludes,
+            daemon_socket,
+            config,
+            mut args,
+            ..
+        }) => {
+            let (files_to_check, config_finder) = get_globs_and_config(
+                files,
+                project_excludes,
+                config.clone(),
+                args.config_override_mut(),
+            )?;
+            run_daemon(args, daemon_socket, config, files_to_check, config_finder)
+        }
+        Command::Deps(FullDepsArgs {
+            files,
+            project_excludes,
+            config,
+            mut args,
+        }) => {
+            let (files_to_check, config_finder) =
+                get_globs_and_config(files, project_excludes, config, args.config_override_mut())?;
+            args.run(files_to_check, config_finder)
+        }
         Command::BuckCheck(args) => args.run(),
         Command::Lsp(args) => args.run(),
         Command::Init(args) => args.run(),
+        Command::MergeShards(args) => args.run(),
         Command::Autotype(FullCheckArgs {
             files,
             project_excludes,
             config,
-            watch: _,
             mut args,
+            ..
         }) => {
             let (files_to_check, config_finder) =
-                get_globs_and_config(files, project_excludes, config, &mut args)?;
+                get_globs_and_config(files, project_excludes, config, args.config_override_mut())?;
             run_autotype(AutotypeArgs::new(), files_to_check, config_finder).await
         }
         // We intentionally make DumpConfig take the same arguments as Check so that dumping the
@@ -336,7 +525,7 @@ async fn run_command(command: Command, allow_forget: bool) -> anyhow::Result<Com
             let mut configs_to_files: SmallMap<ArcId<ConfigFile>, Vec<ModulePath>> =
                 SmallMap::new();
             let (files_to_check, config_finder) =
-                get_globs_and_config(files, project_excludes, config, &mut args)?;
+                get_globs_and_config(files, project_excludes, config, args.config_override_mut())?;
             let mut handles = args
                 .get_handles(files_to_check, &config_finder)?
                 .into_iter()
@@ -11,6 +11,12 @@ use std::fmt::Debug;
 use std::fmt::Display;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::OnceLock;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
 
 use dupe::Dupe;
 use dupe::OptionDupedExt;
@@ -42,6 +48,7 @@ use crate::binding::bindings::BindingEntry;
 use crate::binding::bindings::BindingTable;
 use crate::binding::bindings::Bindings;
 use crate::binding::table::TableKeyed;
+use crate::config::error::OptInErrors;
 use crate::error::collector::ErrorCollector;
 use crate::error::context::ErrorContext;
 use crate::error::context::TypeCheckContext;
@@ -55,6 +62,7 @@ use crate::module::module_info::ModuleInfo;
 use crate::module::module_info::TextRangeWithModuleInfo;
 use crate::module::module_name::ModuleName;
 use crate::module::module_path::ModulePath;
+use crate::plugin::Plugins;
 use crate::solver::solver::Solver;
 use crate::solver::type_order::TypeOrder;
 use crate::state::ide::IntermediateDefinition;
@@ -111,6 +119,56 @@ pub struct Answers {
     table: AnswerTable,
     index: Option<Arc<Mutex<Index>>>,
     trace: Option<Mutex<Traces>>,
+    budget: Option<SolveBudget>,
+    opt_in_errors: OptInErrors,
+}
+
+/// A limit on how long solving a module may take. Once exceeded, the remaining
+/// bindings are answered with `Any` rather than solved.
+/// Time spent waiting on answers from other modules doesn't count against the budget.
+struct SolveBudget {
+    limit: Duration,
+    /// Set when we start solving the whole module.
+    started: OnceLock<Instant>,
+    /// Nanoseconds since `started` spent waiting on other modules.
+    waiting: AtomicU64,
+    /// Set when we have solved the whole module, after which the budget no longer applies.
+    finished: AtomicBool,
+    exceeded: AtomicBool,
+}
+
+impl SolveBudget {
+    fn new(limit: Duration) -> Self {
+        Self {
+            limit,
+            started: OnceLock::new(),
+            waiting: AtomicU64::new(0),
+            finished: AtomicBool::new(false),
+            exceeded: AtomicBool::new(false),
+        }
+    }
+
+    /// Run `f`, which waits on another module, without charging the time it takes to us.
+    fn wait<R>(&self, f: impl FnOnce() -> R) -> R {
+        if self.started.get().is_none() || self.finished.load(Ordering::Relaxed) {
+            return f();
+        }
+        let start = Instant::now();
+        let res = f();
+        self.waiting
+            .fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+        res
+    }
+
+    /// How long we have spent solving this module, not counting waits on other modules.
+    fn spent(&self) -> Option<Duration> {
+        let started = self.started.get()?;
+        Some(
+            started
+                .elapsed()
+                .saturating_sub(Duration::from_nanos(self.waiting.load(Ordering::Relaxed))),
+        )
+    }
 }
 
 pub type AnswerEntry<K> =
@@ -379,6 +437,7 @@ pub struct AnswersSolver<'a, Ans: LookupAnswer> {
     bindings: &'a Bindings,
     pub exports: &'a dyn LookupExport,
     pub uniques: &'a UniqueFactory,
+    pub plugins: &'a Plugins,
     pub recurser: &'a Recurser<Var>,
     pub stdlib: &'a Stdlib,
 }
@@ -407,6 +466,8 @@ impl Answers {
         solver: Solver,
         enable_index: bool,
         enable_trace: bool,
+        solve_budget: Option<Duration>,
+        opt_in_errors: OptInErrors,
     ) -> Self {
         fn presize<K: SolveRecursive>(items: &mut AnswerEntry<K>, bindings: &Bindings)
         where
@@ -430,12 +491,15 @@ impl Answers {
         } else {
             None
         };
+        let budget = solve_budget.map(SolveBudget::new);
 
         Self {
             solver,
             table,
             index,
             trace,
+            budget,
+            opt_in_errors,
         }
     }
 
@@ -454,6 +518,7 @@ impl Answers {
         errors: &ErrorCollector,
         stdlib: &Stdlib,
         uniques: &UniqueFactory,
+        plugins: &Plugins,
         compute_everything: bool,
     ) -> Solutions {
         let mut res = SolutionsTable::default();
@@ -484,6 +549,9 @@ impl Answers {
                 }
             }
         }
+        if let Some(budget) = &self.budget {
+            budget.started.get_or_init(Instant::now);
+        }
         let answers_solver = AnswersSolver {
             stdlib,
             answers,
@@ -492,6 +560,7 @@ impl Answers {
             base_errors: errors,
             exports,
             uniques,
+            plugins,
             recurser: &Recurser::new(),
             current: self,
         };
@@ -500,6 +569,9 @@ impl Answers {
             &answers_solver,
             compute_everything
         ));
+        if let Some(budget) = &self.budget {
+            budget.finished.store(true, Ordering::Relaxed);
+        }
         if let Some(index) = &self.index {
             let mut index = index.lock();
             // Index bindings with external definitions.
@@ -554,6 +626,7 @@ impl Answers {
         errors: &ErrorCollector,
         stdlib: &Stdlib,
         uniques: &UniqueFactory,
+        plugins: &Plugins,
         key: Hashed<&K>,
         stack: &CalcStack,
     ) -> Arc<K::Answer>
@@ -564,6 +637,7 @@ impl Answers {
         let solver = AnswersSolver {
             stdlib,
             uniques,
+            plugins,
             answers,
             bindings,
             base_errors: errors,
@@ -642,6 +716,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         bindings: &'a Bindings,
         exports: &'a dyn LookupExport,
         uniques: &'a UniqueFactory,
+        plugins: &'a Plugins,
         recurser: &'a Recurser<Var>,
         stdlib: &'a Stdlib,
         stack: &'a CalcStack,
@@ -649,6 +724,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         AnswersSolver {
             stdlib,
             uniques,
+            plugins,
             answers,
             bindings,
             base_errors,
@@ -675,6 +751,11 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         self.stack
     }
 
+    /// Whether to look for errors of the given kind in the current module.
+    pub fn is_enabled(&self, kind: ErrorKind) -> bool {
+        self.current.opt_in_errors.is_enabled(kind)
+    }
+
     pub fn for_display(&self, t: Type) -> Type {
         self.solver().for_display(t)
     }
@@ -694,6 +775,8 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             && path.is_none_or(|path| path == self.module_info().path())
         {
             self.get(k)
+        } else if let Some(budget) = &self.current.budget {
+            budget.wait(|| self.answers.get(module, path, k, self.stack))
         } else {
             self.answers.get(module, path, k, self.stack)
         }
@@ -742,7 +825,11 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         let result = calculation.calculate_with_recursive(
             || {
                 let binding = self.bindings().get(idx);
-                K::solve(self, binding, self.base_errors)
+                if self.over_budget(idx) {
+                    K::time_out(self, binding, self.base_errors)
+                } else {
+                    K::solve(self, binding, self.base_errors)
+                }
             },
             || {
                 let binding = self.bindings().get(idx);
@@ -760,6 +847,48 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
+    /// Whether the current module has run out of solve budget, in which case `idx` should not
+    /// be solved. Reports an error the first time the budget is exceeded.
+    fn over_budget<K: Solve<Ans>>(&self, idx: Idx<K>) -> bool
+    where
+        BindingTable: TableKeyed<K, Value = BindingEntry<K>>,
+    {
+        let Some(budget) = &self.current.budget else {
+            return false;
+        };
+        if budget.exceeded.load(Ordering::Relaxed) {
+            return true;
+        }
+        if budget.finished.load(Ordering::Relaxed) {
+            return false;
+        }
+        if budget.spent().is_none_or(|spent| spent <= budget.limit) {
+            return false;
+        }
+        if !budget.exceeded.swap(true, Ordering::Relaxed) {
+            let range = self.bindings().idx_to_key(idx).range();
+            let code = self.module_info().code_at(range);
+            let mut name = code.lines().next().unwrap_or_default().to_owned();
+            if name.len() > 40 || name.len() < code.len() {
+                name = format!(
+                    "{}...",
+                    name.chars().take(40).collect::<String>().trim_end()
+                );
+            }
+            self.base_errors.add(
+                range,
+                ErrorKind::TooComplex,
+                None,
+                vec1![format!(
+                    "Module `{}` exceeded its solve budget of {}ms while solving `{name}`, so it and the remaining bindings are treated as `Any`",
+                    self.module_info().name(),
+                    budget.limit.as_millis(),
+                )],
+            );
+        }
+        true
+    }
+
     pub fn get<K: Solve<Ans>>(&self, k: &K) -> Arc<K::Answer>
     where
         AnswerTable: TableKeyed<K, Value = AnswerEntry<K>>,
@@ -866,11 +995,18 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         errors: &ErrorCollector,
         tcc: &dyn Fn() -> TypeCheckContext,
     ) -> bool {
-        if got.is_error() || self.is_subset_eq(got, want) {
-            true
-        } else {
-            self.solver().error(want, got, errors, loc, tcc);
-            false
+        if got.is_error() {
+            return true;
+        }
+        match self
+            .solver()
+            .is_subset_eq_explained(got, want, self.type_order())
+        {
+            Ok(()) => true,
+            Err(failures) => {
+                self.solver().error(want, got, errors, loc, tcc, &failures);
+                false
+            }
         }
     }
 
@@ -1006,3 +1142,21 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::thread::sleep;
+
+    use super::*;
+
+    #[test]
+    fn test_solve_budget_excludes_waiting() {
+        let budget = SolveBudget::new(Duration::from_millis(50));
+        assert_eq!(budget.spent(), None);
+        budget.started.get_or_init(Instant::now);
+        budget.wait(|| sleep(Duration::from_millis(100)));
+        assert!(budget.spent().unwrap() < budget.limit);
+        sleep(Duration::from_millis(100));
+        assert!(budget.spent().unwrap() > budget.limit);
+    }
+}
@@ -6,6 +6,7 @@
  */
 
 use dupe::Dupe;
+use dupe::OptionDupedExt;
 use ruff_python_ast::name::Name;
 use ruff_text_size::TextRange;
 use starlark_map::small_set::SmallSet;
@@ -18,11 +19,13 @@ use crate::alt::attr::DescriptorBase;
 use crate::alt::callable::CallArg;
 use crate::alt::callable::CallKeyword;
 use crate::alt::callable::CallWithTypes;
+use crate::alt::expr::TypeOrExpr;
 use crate::dunder;
 use crate::error::collector::ErrorCollector;
 use crate::error::context::ErrorContext;
 use crate::error::kind::ErrorKind;
-use crate::types::callable::BoolKeywords;
+use crate::plugin::Plugin;
+use crate::plugin::PluginKey;
 use crate::types::callable::Callable;
 use crate::types::callable::FuncFlags;
 use crate::types::callable::FuncMetadata;
@@ -94,6 +97,25 @@ struct CalledOverload {
     return_type: Type,
 }
 
+impl Target {
+    /// The name a plugin would be registered under to customize calls to this target.
+    fn plugin_key(&self) -> Option<PluginKey> {
+        match self {
+            Target::Function(Function { metadata, .. })
+            | Target::BoundMethod(_, Function { metadata, .. })
+            | Target::FunctionOverload(_, metadata)
+            | Target::BoundMethodOverload(_, _, metadata) => {
+                Some(PluginKey::of_func_id(&metadata.kind.as_func_id()))
+            }
+            Target::Class(cls) => Some(PluginKey::new(
+                cls.class_object().module_name(),
+                cls.name().clone(),
+            )),
+            Target::Callable(_) | Target::TypedDict(_) | Target::Any(_) => None,
+        }
+    }
+}
+
 impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
     fn error_call_target(
         &self,
@@ -500,7 +522,39 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         context: Option<&dyn Fn() -> ErrorContext>,
         hint: Option<Type>,
     ) -> Type {
-        let is_dataclass = matches!(&call_target.target, Target::FunctionOverload(_, meta) if matches!(meta.kind, FunctionKind::Dataclass(_)));
+        // Calls like `dataclass(frozen=True)` return the decorator; remember the keywords on it.
+        let dataclass_kind = match &call_target.target {
+            Target::FunctionOverload(_, meta)
+                if matches!(
+                    meta.kind,
+                    FunctionKind::Dataclass(_) | FunctionKind::AttrsClass(_)
+                ) =>
+            {
+                Some(meta.kind.clone())
+            }
+            _ => None,
+        };
+        let plugin = if self.plugins.is_empty() {
+            None
+        } else {
+            call_target
+                .target
+                .plugin_key()
+                .and_then(|key| self.plugins.get(&key).duped())
+        };
+        // A plugin sees the argument types, so infer them once up front and share them with the
+        // call, rather than inferring them again afterwards.
+        let call = CallWithTypes::new();
+        let plugin_call = plugin.as_ref().map(|_| {
+            (
+                call.vec_call_arg(args, self, errors),
+                call.vec_call_keyword(keywords, self, errors),
+            )
+        });
+        let (args, keywords) = match &plugin_call {
+            Some((args, keywords)) => (args.as_slice(), keywords.as_slice()),
+            None => (args, keywords),
+        };
         let res = match call_target.target {
             Target::Class(cls) => {
                 if let Some(hint) = hint {
@@ -624,15 +678,22 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             }
         };
         self.solver().finish_quantified(&call_target.qs);
-        if is_dataclass && let Type::Callable(c) = res {
-            let mut kws = BoolKeywords::new();
-            for kw in keywords {
-                kws.set_keyword(kw.arg, kw.value.infer(self, errors));
+        let res = match plugin {
+            Some(plugin) => self.call_plugin_return_type(&*plugin, args, keywords, res),
+            None => res,
+        };
+        if let Some(mut kind) = dataclass_kind
+            && let Type::Callable(c) = res
+        {
+            if let FunctionKind::Dataclass(kws) | FunctionKind::AttrsClass(kws) = &mut kind {
+                for kw in keywords {
+                    kws.set_keyword(kw.arg, kw.value.infer(self, errors));
+                }
             }
             Type::Function(Box::new(Function {
                 signature: *c,
                 metadata: FuncMetadata {
-                    kind: FunctionKind::Dataclass(Box::new(kws)),
+                    kind,
                     flags: FuncFlags::default(),
                 },
             }))
@@ -641,6 +702,34 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
+    /// Let a plugin replace the return type of a call. The arguments have already been inferred
+    /// to types by `call_infer`.
+    fn call_plugin_return_type(
+        &self,
+        plugin: &dyn Plugin,
+        args: &[CallArg],
+        keywords: &[CallKeyword],
+        ret: Type,
+    ) -> Type {
+        let arg_tys = args
+            .iter()
+            .filter_map(|arg| match arg {
+                CallArg::Arg(TypeOrExpr::Type(ty, _)) => Some((*ty).clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let keyword_tys = keywords
+            .iter()
+            .filter_map(|kw| match kw.value {
+                TypeOrExpr::Type(ty, _) => Some((kw.arg?.id.clone(), ty.clone())),
+                TypeOrExpr::Expr(_) => None,
+            })
+            .collect::<Vec<_>>();
+        plugin
+            .call_return_type(self, &arg_tys, &keyword_tys, &ret)
+            .unwrap_or(ret)
+    }
+
     fn call_overloads(
         &self,
         overloads: Vec1<Callable>,
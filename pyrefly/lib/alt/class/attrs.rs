@@ -0,0 +1,244 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use ruff_python_ast::Keyword;
+use ruff_python_ast::name::Name;
+use starlark_map::small_set::SmallSet;
+
+use crate::alt::answers::AnswersSolver;
+use crate::alt::answers::LookupAnswer;
+use crate::alt::class::class_field::ClassField;
+use crate::alt::class::class_field::DataclassFieldKeywords;
+use crate::alt::types::class_metadata::ClassMetadata;
+use crate::alt::types::class_metadata::ClassSynthesizedField;
+use crate::alt::types::class_metadata::DataclassKind;
+use crate::alt::types::class_metadata::DataclassMetadata;
+use crate::dunder;
+use crate::error::collector::ErrorCollector;
+use crate::types::callable::AttrsKeywords;
+use crate::types::callable::BoolKeywords;
+use crate::types::callable::Callable;
+use crate::types::callable::DataclassKeywords;
+use crate::types::callable::FuncMetadata;
+use crate::types::callable::Function;
+use crate::types::callable::Param;
+use crate::types::callable::ParamList;
+use crate::types::callable::Params;
+use crate::types::class::Class;
+use crate::types::class::ClassType;
+use crate::types::literal::Lit;
+use crate::types::types::Forall;
+use crate::types::types::Forallable;
+use crate::types::types::OverloadType;
+use crate::types::types::Type;
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    /// Gets the candidate fields for an `attrs` class. Whether a name in the class body is really
+    /// a field depends on its value and on `auto_attribs`, which we can't look at while computing
+    /// metadata, so this is narrowed down by `iter_fields`.
+    pub fn get_attrs_fields(
+        &self,
+        cls: &Class,
+        bases_with_metadata: &[(ClassType, Arc<ClassMetadata>)],
+    ) -> SmallSet<Name> {
+        let mut all_fields = SmallSet::new();
+        for (_, metadata) in bases_with_metadata.iter().rev() {
+            if let Some(dataclass) = metadata.dataclass_metadata() {
+                all_fields.extend(dataclass.fields.clone());
+            }
+        }
+        for name in cls.fields() {
+            all_fields.insert(name.clone());
+        }
+        all_fields
+    }
+
+    /// Whether annotated attributes without an `attr.ib()`/`attrs.field()` value are fields.
+    /// If the decorator doesn't say, `attrs` uses them only when there are annotated attributes
+    /// and no unannotated field specifiers.
+    pub fn attrs_auto_attribs(&self, cls: &Class, kws: &BoolKeywords) -> bool {
+        if let Some(auto_attribs) = kws.get(&AttrsKeywords::AUTO_ATTRIBS) {
+            return auto_attribs;
+        }
+        let mut has_annotated = false;
+        for name in cls.fields() {
+            if cls.is_field_annotated(name) {
+                has_annotated = true;
+            } else if self
+                .get_non_synthesized_field_from_current_class_only(cls, name, false)
+                .is_some_and(|field| field.dataclass_field_keywords().is_some())
+            {
+                return false;
+            }
+        }
+        has_annotated
+    }
+
+    fn is_attrs_field(field: &ClassField, auto_attribs: bool) -> bool {
+        field.dataclass_field_keywords().is_some()
+            || (auto_attribs && field.has_explicit_annotation())
+    }
+
+    /// Whether `name`, found on `cls`, is an attrs field. This depends on the `auto_attribs` of the
+    /// class that defines it, which for inherited fields may differ from that of `cls`.
+    pub fn is_attrs_field_of(
+        &self,
+        cls: &Class,
+        name: &Name,
+        field: &ClassField,
+        auto_attribs: bool,
+    ) -> bool {
+        let auto_attribs = match self.get_non_synthesized_class_member(cls, name) {
+            Some(member) if member.defining_class != *cls => {
+                let metadata = self.get_metadata_for_class(&member.defining_class);
+                match metadata.dataclass_metadata() {
+                    Some(dataclass) if dataclass.kind == DataclassKind::Attrs => {
+                        self.attrs_auto_attribs(&member.defining_class, &dataclass.kws)
+                    }
+                    _ => auto_attribs,
+                }
+            }
+            _ => auto_attribs,
+        };
+        Self::is_attrs_field(field, auto_attribs)
+    }
+
+    /// Collects the keywords passed to `attr.ib()` or `attrs.field()`.
+    pub fn attrs_field_keywords(
+        &self,
+        keywords: &[Keyword],
+        errors: &ErrorCollector,
+    ) -> DataclassFieldKeywords {
+        let mut flags = BoolKeywords::new();
+        let mut converter_param = None;
+        let mut alias = None;
+        for kw in keywords {
+            let Some(id) = &kw.arg else {
+                continue;
+            };
+            if id.id == DataclassKeywords::DEFAULT.0 || id.id == AttrsKeywords::FACTORY {
+                flags.set(DataclassKeywords::DEFAULT.0, true);
+            } else if id.id == AttrsKeywords::CONVERTER {
+                let converter = self.expr_infer(&kw.value, errors);
+                converter_param = Some(self.attrs_converter_param(converter));
+            } else if id.id == AttrsKeywords::ALIAS {
+                if let Type::Literal(Lit::Str(s)) = self.expr_infer(&kw.value, errors) {
+                    alias = Some(Name::new(&*s));
+                }
+            } else {
+                let val = self.expr_infer(&kw.value, errors);
+                flags.set_keyword(kw.arg.as_ref(), val);
+            }
+        }
+        DataclassFieldKeywords {
+            flags,
+            converter_param,
+            alias,
+        }
+    }
+
+    /// The type that `__init__` accepts for a field with a converter: the type of the converter's
+    /// first parameter. For an overloaded converter, this is the union over its overloads, and for
+    /// a class, the first parameter of its constructor.
+    fn attrs_converter_param(&self, converter: Type) -> Type {
+        match converter {
+            Type::Function(func) => Self::first_param_type(&func.signature),
+            Type::Callable(callable) => Self::first_param_type(&callable),
+            Type::Forall(box Forall {
+                body: Forallable::Function(func),
+                ..
+            }) => Self::first_param_type(&func.signature),
+            Type::Overload(overload) => self.unions(
+                overload
+                    .signatures
+                    .iter()
+                    .map(|signature| match signature {
+                        OverloadType::Callable(callable) => Self::first_param_type(callable),
+                        OverloadType::Forall(forall) => {
+                            Self::first_param_type(&forall.body.signature)
+                        }
+                    })
+                    .collect(),
+            ),
+            Type::BoundMethod(method) => match method.to_callable() {
+                Some(callable) => self.attrs_converter_param(callable),
+                None => Type::any_implicit(),
+            },
+            Type::ClassDef(cls) => {
+                let cls = cls.as_class_type();
+                if let Some(new) = self.get_dunder_new(&cls) {
+                    // `__new__` is unbound, so skip its `cls` parameter.
+                    match new.to_unbound_callable() {
+                        Some(callable) => self.attrs_converter_param(callable),
+                        None => Type::any_implicit(),
+                    }
+                } else if let Some(init) = self.get_dunder_init(&cls, false) {
+                    self.attrs_converter_param(init)
+                } else {
+                    Type::any_implicit()
+                }
+            }
+            _ => Type::any_implicit(),
+        }
+    }
+
+    fn first_param_type(signature: &Callable) -> Type {
+        match &signature.params {
+            Params::List(params) => match params.items().first() {
+                Some(Param::PosOnly(_, ty, _) | Param::Pos(_, ty, _) | Param::VarArg(_, ty)) => {
+                    ty.clone()
+                }
+                _ => Type::any_implicit(),
+            },
+            _ => Type::any_implicit(),
+        }
+    }
+
+    /// Gets `__init__` method for an `attrs` class. Unlike dataclasses, parameters are named after
+    /// the field's alias (by default, the field name with leading underscores removed), and accept
+    /// the input type of the field's converter, if any.
+    pub fn get_attrs_init(
+        &self,
+        cls: &Class,
+        dataclass: &DataclassMetadata,
+    ) -> ClassSynthesizedField {
+        let kw_only = dataclass.kws.is_set(&DataclassKeywords::KW_ONLY);
+        let mut params = vec![self.class_self_param(cls, false)];
+        for (name, field, field_flags) in self.iter_fields(cls, dataclass, true) {
+            if !field_flags.is_set(&DataclassKeywords::INIT) {
+                continue;
+            }
+            let (param_name, converter_param) = match field.dataclass_field_keywords() {
+                Some(field_kws) => (field_kws.alias.clone(), field_kws.converter_param.clone()),
+                None => (None, None),
+            };
+            let param_name =
+                param_name.unwrap_or_else(|| Name::new(name.as_str().trim_start_matches('_')));
+            let param = field.as_param(
+                &param_name,
+                field_flags.is_set(&DataclassKeywords::DEFAULT),
+                kw_only || field_flags.is_set(&DataclassKeywords::KW_ONLY),
+            );
+            params.push(match (param, converter_param) {
+                (Param::Pos(name, _, required), Some(ty)) => Param::Pos(name, ty, required),
+                (Param::KwOnly(name, _, required), Some(ty)) => Param::KwOnly(name, ty, required),
+                (param, _) => param,
+            });
+        }
+        let ty = Type::Function(Box::new(Function {
+            signature: Callable::list(ParamList::new(params), Type::None),
+            metadata: FuncMetadata::def(
+                self.module_info().name(),
+                cls.name().clone(),
+                dunder::INIT,
+            ),
+        }));
+        ClassSynthesizedField::new(ty)
+    }
+}
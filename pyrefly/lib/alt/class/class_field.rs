@@ -47,6 +47,7 @@ use crate::types::callable::FuncMetadata;
 use crate::types::callable::Function;
 use crate::types::callable::FunctionKind;
 use crate::types::callable::Param;
+use crate::types::callable::Params;
 use crate::types::callable::Required;
 use crate::types::class::Class;
 use crate::types::class::ClassType;
@@ -71,14 +72,26 @@ use crate::types::types::Type;
 /// are assigned values in the class body.
 #[derive(Clone, Debug, TypeEq, VisitMut, PartialEq, Eq)]
 pub enum ClassFieldInitialization {
-    /// If this is a dataclass field, BoolKeywords stores the field's dataclass
-    /// flags (which are boolean options that control how fields behave).
-    Class(Option<BoolKeywords>),
+    /// If this is a dataclass field, DataclassFieldKeywords stores the keywords
+    /// passed to the field specifier.
+    Class(Option<DataclassFieldKeywords>),
     /// The boolean indicates whether we know the field may have been initialized
     /// outside of the class body or not.
     Instance(bool),
 }
 
+/// The keywords passed to a dataclass field specifier, such as `dataclasses.field()` or
+/// `attrs.field()`.
+#[derive(Clone, Debug, TypeEq, VisitMut, PartialEq, Eq)]
+pub struct DataclassFieldKeywords {
+    /// Boolean options that control how the field behaves.
+    pub flags: BoolKeywords,
+    /// For an `attrs` field with a `converter`, the type that `__init__` accepts for the field.
+    pub converter_param: Option<Type>,
+    /// For an `attrs` field with an `alias`, the name of the `__init__` parameter.
+    pub alias: Option<Name>,
+}
+
 impl Display for ClassFieldInitialization {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -178,7 +191,7 @@ impl ClassField {
 
     /// Get the raw type. Only suitable for use in this module, this type may
     /// not correspond to the type of any actual operations on the attribute.
-    fn raw_type(&self) -> &Type {
+    pub(in crate::alt::class) fn raw_type(&self) -> &Type {
         match &self.0 {
             ClassFieldInner::Simple { ty, .. } => ty,
         }
@@ -369,6 +382,24 @@ impl ClassField {
         }
     }
 
+    /// If this field is a plain `(self, other)` method, such as a rich comparison method, get the
+    /// type of the `other` parameter.
+    pub fn as_binary_method_other_param(&self) -> Option<Type> {
+        match &self.0 {
+            ClassFieldInner::Simple {
+                ty: Type::Function(func),
+                ..
+            } => match &func.signature.params {
+                Params::List(params) => match params.items() {
+                    [_, Param::Pos(_, ty, _) | Param::PosOnly(_, ty, _)] => Some(ty.clone()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
     pub fn is_function_without_return_annotation(&self) -> bool {
         match &self.0 {
             ClassFieldInner::Simple {
@@ -378,11 +409,22 @@ impl ClassField {
         }
     }
 
+    /// The keywords of the field specifier call this field was initialized with, if any.
+    pub fn dataclass_field_keywords(&self) -> Option<&DataclassFieldKeywords> {
+        match &self.0 {
+            ClassFieldInner::Simple {
+                initialization: ClassFieldInitialization::Class(field_kws),
+                ..
+            } => field_kws.as_ref(),
+            _ => None,
+        }
+    }
+
     fn dataclass_flags_of(&self, kw_only: bool) -> BoolKeywords {
         match &self.0 {
             ClassFieldInner::Simple { initialization, .. } => {
                 let mut flags = match initialization {
-                    ClassFieldInitialization::Class(Some(field_flags)) => field_flags.clone(),
+                    ClassFieldInitialization::Class(Some(field_kws)) => field_kws.flags.clone(),
                     ClassFieldInitialization::Class(None) => {
                         let mut kws = BoolKeywords::new();
                         kws.set(DataclassKeywords::DEFAULT.0, true);
@@ -630,6 +672,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         };
         let initialization =
             self.get_class_field_initialization(&metadata, initial_value, magically_initialized);
+        if matches!(initialization, ClassFieldInitialization::Instance(_)) {
+            self.check_instance_attribute_in_slots(class, name, range, errors);
+        }
 
         // Ban typed dict from containing values; fields should be annotation-only.
         // TODO(stroxler): we ought to look into this more: class-level attributes make sense on a `TypedDict` class;
@@ -877,32 +922,46 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     && let Expr::Call(ExprCall {
                         range: _,
                         func,
-                        arguments: Arguments { keywords, .. },
+                        arguments: Arguments { args, keywords, .. },
                     }) = e
                 {
                     // We already type-checked this expression as part of computing the type for the ClassField,
                     // so we can ignore any errors encountered here.
                     let ignore_errors = self.error_swallower();
                     let func_ty = self.expr_infer(func, &ignore_errors);
-                    if matches!(
-                        func_ty.callee_kind(),
-                        Some(CalleeKind::Function(FunctionKind::DataclassField))
-                    ) {
-                        let mut flags = BoolKeywords::new();
-                        for kw in keywords {
-                            if let Some(id) = &kw.arg
-                                && (id.id == DataclassKeywords::DEFAULT.0
-                                    || id.id == "default_factory")
-                            {
-                                flags.set(DataclassKeywords::DEFAULT.0, true);
-                            } else {
-                                let val = self.expr_infer(&kw.value, &ignore_errors);
-                                flags.set_keyword(kw.arg.as_ref(), val);
+                    match func_ty.callee_kind() {
+                        Some(CalleeKind::Function(FunctionKind::DataclassField)) => {
+                            let mut flags = BoolKeywords::new();
+                            for kw in keywords {
+                                if let Some(id) = &kw.arg
+                                    && (id.id == DataclassKeywords::DEFAULT.0
+                                        || id.id == "default_factory")
+                                {
+                                    flags.set(DataclassKeywords::DEFAULT.0, true);
+                                } else {
+                                    let val = self.expr_infer(&kw.value, &ignore_errors);
+                                    flags.set_keyword(kw.arg.as_ref(), val);
+                                }
                             }
+                            ClassFieldInitialization::Class(Some(DataclassFieldKeywords {
+                                flags,
+                                converter_param: None,
+                                alias: None,
+                            }))
                         }
-                        ClassFieldInitialization::Class(Some(flags))
-                    } else {
-                        ClassFieldInitialization::Class(None)
+                        Some(CalleeKind::Function(FunctionKind::AttrsField)) => {
+                            ClassFieldInitialization::Class(Some(
+                                self.attrs_field_keywords(keywords, &ignore_errors),
+                            ))
+                        }
+                        Some(CalleeKind::Function(FunctionKind::PydanticField)) => {
+                            ClassFieldInitialization::Class(Some(self.pydantic_field_keywords(
+                                args,
+                                keywords,
+                                &ignore_errors,
+                            )))
+                        }
+                        _ => ClassFieldInitialization::Class(None),
                     }
                 } else {
                     ClassFieldInitialization::Class(None)
@@ -1239,7 +1298,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
-    fn get_non_synthesized_field_from_current_class_only(
+    pub(in crate::alt::class) fn get_non_synthesized_field_from_current_class_only(
         &self,
         cls: &Class,
         name: &Name,
@@ -1312,6 +1371,34 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         self.get_class_member_impl(cls, name, false)
     }
 
+    /// Like `get_class_member`, but ignores the synthesized fields of `cls` itself (fields
+    /// synthesized for its ancestors are still visible). This is what synthesizers need in order
+    /// to inspect the class they are synthesizing fields for without creating a cycle.
+    pub(in crate::alt::class) fn get_non_synthesized_class_member(
+        &self,
+        cls: &Class,
+        name: &Name,
+    ) -> Option<WithDefiningClass<Arc<ClassField>>> {
+        if let Some(field) =
+            self.get_non_synthesized_field_from_current_class_only(cls, name, false)
+        {
+            Some(WithDefiningClass {
+                value: field,
+                defining_class: cls.dupe(),
+            })
+        } else {
+            self.get_metadata_for_class(cls)
+                .ancestors(self.stdlib)
+                .find_map(|ancestor| {
+                    self.get_field_from_current_class_only(ancestor.class_object(), name, false)
+                        .map(|field| WithDefiningClass {
+                            value: Arc::new(field.instantiate_for(&Instance::of_class(ancestor))),
+                            defining_class: ancestor.class_object().dupe(),
+                        })
+                })
+        }
+    }
+
     pub fn get_instance_attribute(&self, cls: &ClassType, name: &Name) -> Option<Attribute> {
         self.get_class_member(cls.class_object(), name)
             .map(|member| self.as_instance_attribute(&member.value, &Instance::of_class(cls)))
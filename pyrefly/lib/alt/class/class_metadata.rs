@@ -24,13 +24,16 @@ use crate::alt::answers::AnswersSolver;
 use crate::alt::answers::LookupAnswer;
 use crate::alt::solve::TypeFormContext;
 use crate::alt::types::class_metadata::ClassMetadata;
+use crate::alt::types::class_metadata::DataclassKind;
 use crate::alt::types::class_metadata::DataclassMetadata;
 use crate::alt::types::class_metadata::EnumMetadata;
 use crate::alt::types::class_metadata::NamedTupleMetadata;
 use crate::alt::types::class_metadata::ProtocolMetadata;
+use crate::alt::types::class_metadata::SlotsMetadata;
 use crate::alt::types::class_metadata::TypedDictMetadata;
 use crate::binding::binding::Key;
 use crate::binding::binding::KeyLegacyTypeParam;
+use crate::dunder;
 use crate::error::collector::ErrorCollector;
 use crate::error::kind::ErrorKind;
 use crate::graph::index::Idx;
@@ -156,6 +159,8 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         decorators: &[Idx<Key>],
         is_new_type: bool,
         special_base: &Option<Box<BaseClass>>,
+        slots: Option<&[Name]>,
+        model_config: Option<&[(Name, Expr)]>,
         errors: &ErrorCollector,
     ) -> ClassMetadata {
         let mut is_typed_dict = false;
@@ -405,19 +410,40 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             }
         }
         let mut is_final = false;
+        let mut is_total_ordering = false;
+        let mut is_decorated_dataclass = false;
+        let mut plugin = None;
         for decorator in decorators {
             let decorator = self.get_idx(*decorator);
+            if plugin.is_none() {
+                plugin = self.get_decorator_plugin(decorator.ty());
+            }
             match decorator.ty().callee_kind() {
                 Some(CalleeKind::Function(FunctionKind::Dataclass(kws))) => {
                     let dataclass_fields = self.get_dataclass_fields(cls, &bases_with_metadata);
+                    is_decorated_dataclass = true;
                     dataclass_metadata = Some(DataclassMetadata {
                         fields: dataclass_fields,
                         kws: *kws,
+                        kind: DataclassKind::Dataclass,
+                    });
+                }
+                Some(CalleeKind::Function(FunctionKind::AttrsClass(kws))) => {
+                    let attrs_fields = self.get_attrs_fields(cls, &bases_with_metadata);
+                    is_decorated_dataclass = true;
+                    dataclass_metadata = Some(DataclassMetadata {
+                        fields: attrs_fields,
+                        kws: *kws,
+                        kind: DataclassKind::Attrs,
                     });
                 }
                 Some(CalleeKind::Function(FunctionKind::Final)) => {
                     is_final = true;
                 }
+                Some(CalleeKind::Function(FunctionKind::TotalOrdering)) => {
+                    is_total_ordering = true;
+                    self.validate_total_ordering_root(cls, &bases_with_metadata, errors);
+                }
                 Some(CalleeKind::Function(FunctionKind::RuntimeCheckable)) => {
                     if let Some(proto) = &mut protocol_metadata {
                         proto.is_runtime_checkable = true;
@@ -434,6 +460,17 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 _ => {}
             }
         }
+        // A Pydantic base takes precedence over metadata inherited from other dataclass bases, but
+        // not over the class's own dataclass decorator.
+        if !is_decorated_dataclass
+            && let Some(pydantic) =
+                self.get_pydantic_metadata(cls, &bases_with_metadata, &keywords, model_config)
+        {
+            dataclass_metadata = Some(pydantic);
+        }
+        let slots_metadata = slots
+            .map(|slots| self.calculate_slots_metadata(slots, &bases_with_metadata, has_base_any));
+        let plugin = plugin.or_else(|| self.get_base_plugin(&bases_with_metadata));
         if is_typed_dict
             && let Some(bad) = bases_with_metadata.iter().find(|x| !x.1.is_typed_dict())
         {
@@ -473,9 +510,12 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             enum_metadata,
             protocol_metadata,
             dataclass_metadata,
+            slots_metadata,
             has_base_any,
             is_new_type,
             is_final,
+            is_total_ordering,
+            plugin,
             has_unknown_tparams,
             errors,
         )
@@ -501,6 +541,32 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         all_fields
     }
 
+    fn calculate_slots_metadata(
+        &self,
+        slots: &[Name],
+        bases_with_metadata: &[(ClassType, Arc<ClassMetadata>)],
+        has_base_any: bool,
+    ) -> SlotsMetadata {
+        let mut all_slots = slots.iter().cloned().collect::<SmallSet<_>>();
+        let mut has_dict = has_base_any || all_slots.contains(&dunder::DICT);
+        for (base, metadata) in bases_with_metadata {
+            if base.class_object().is_builtin("object") {
+                continue;
+            }
+            match metadata.slots_metadata() {
+                Some(base_slots) => {
+                    all_slots.extend(base_slots.slots.iter().cloned());
+                    has_dict |= base_slots.has_dict;
+                }
+                None => has_dict = true,
+            }
+        }
+        SlotsMetadata {
+            slots: all_slots,
+            has_dict,
+        }
+    }
+
     /// This helper deals with special cases where we want to intercept an `Expr`
     /// manually and create a special variant of `BaseClass` instead of calling
     /// `expr_untype` and creating a `BaseClass::Type`.
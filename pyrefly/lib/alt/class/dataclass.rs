@@ -18,6 +18,8 @@ use crate::alt::class::class_field::DataclassMember;
 use crate::alt::types::class_metadata::ClassMetadata;
 use crate::alt::types::class_metadata::ClassSynthesizedField;
 use crate::alt::types::class_metadata::ClassSynthesizedFields;
+use crate::alt::types::class_metadata::DataclassKind;
+use crate::alt::types::class_metadata::DataclassMetadata;
 use crate::dunder;
 use crate::types::callable::BoolKeywords;
 use crate::types::callable::Callable;
@@ -60,23 +62,29 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         let dataclass = metadata.dataclass_metadata()?;
         let mut fields = SmallMap::new();
         if dataclass.kws.is_set(&DataclassKeywords::INIT) {
-            fields.insert(
-                dunder::INIT,
-                self.get_dataclass_init(
+            let init = match dataclass.kind {
+                DataclassKind::Dataclass => self.get_dataclass_init(
                     cls,
-                    &dataclass.fields,
+                    dataclass,
                     dataclass.kws.is_set(&DataclassKeywords::KW_ONLY),
                 ),
+                DataclassKind::Attrs => self.get_attrs_init(cls, dataclass),
+                DataclassKind::PydanticModel | DataclassKind::PydanticRootModel => {
+                    self.get_pydantic_init(cls, dataclass)
+                }
+            };
+            fields.insert(dunder::INIT, init);
+        }
+        if dataclass.kind == DataclassKind::Dataclass {
+            let dataclass_fields_type = self.stdlib.dict(
+                self.stdlib.str().clone().to_type(),
+                Type::Any(AnyStyle::Implicit),
+            );
+            fields.insert(
+                dunder::DATACLASS_FIELDS,
+                ClassSynthesizedField::new(dataclass_fields_type.to_type()),
             );
         }
-        let dataclass_fields_type = self.stdlib.dict(
-            self.stdlib.str().clone().to_type(),
-            Type::Any(AnyStyle::Implicit),
-        );
-        fields.insert(
-            dunder::DATACLASS_FIELDS,
-            ClassSynthesizedField::new(dataclass_fields_type.to_type()),
-        );
 
         if dataclass.kws.is_set(&DataclassKeywords::ORDER) {
             fields.extend(self.get_dataclass_rich_comparison_methods(cls));
@@ -86,7 +94,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 dunder::MATCH_ARGS,
                 self.get_dataclass_match_args(
                     cls,
-                    &dataclass.fields,
+                    dataclass,
                     dataclass.kws.is_set(&DataclassKeywords::KW_ONLY),
                 ),
             );
@@ -104,14 +112,20 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         Some(ClassSynthesizedFields::new(fields))
     }
 
-    fn iter_fields(
+    pub fn iter_fields(
         &self,
         cls: &Class,
-        fields: &SmallSet<Name>,
+        dataclass: &DataclassMetadata,
         include_initvar: bool,
     ) -> Vec<(Name, ClassField, BoolKeywords)> {
         let mut kw_only = false;
-        fields
+        let attrs_auto_attribs = if dataclass.kind == DataclassKind::Attrs {
+            Some(self.attrs_auto_attribs(cls, &dataclass.kws))
+        } else {
+            None
+        };
+        dataclass
+            .fields
             .iter()
             .filter_map(|name| match self.get_dataclass_member(cls, name, kw_only) {
                 DataclassMember::KwOnlyMarker => {
@@ -119,6 +133,12 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     None
                 }
                 DataclassMember::NotAField => None,
+                DataclassMember::Field(field, _)
+                    if let Some(auto_attribs) = attrs_auto_attribs
+                        && !self.is_attrs_field_of(cls, name, &field, auto_attribs) =>
+                {
+                    None
+                }
                 DataclassMember::Field(field, keywords) => Some((name.clone(), field, keywords)),
                 DataclassMember::InitVar(field) => {
                     if include_initvar {
@@ -135,11 +155,11 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
     fn get_dataclass_init(
         &self,
         cls: &Class,
-        fields: &SmallSet<Name>,
+        dataclass: &DataclassMetadata,
         kw_only: bool,
     ) -> ClassSynthesizedField {
         let mut params = vec![self.class_self_param(cls, false)];
-        for (name, field, field_flags) in self.iter_fields(cls, fields, true) {
+        for (name, field, field_flags) in self.iter_fields(cls, dataclass, true) {
             if field_flags.is_set(&DataclassKeywords::INIT) {
                 params.push(field.as_param(
                     &name,
@@ -162,14 +182,14 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
     fn get_dataclass_match_args(
         &self,
         cls: &Class,
-        fields: &SmallSet<Name>,
+        dataclass: &DataclassMetadata,
         kw_only: bool,
     ) -> ClassSynthesizedField {
         // Keyword-only fields do not appear in __match_args__.
         let ts = if kw_only {
             Vec::new()
         } else {
-            let filtered_fields = self.iter_fields(cls, fields, false);
+            let filtered_fields = self.iter_fields(cls, dataclass, false);
             filtered_fields
                 .iter()
                 .filter_map(|(name, _, field_flags)| {
@@ -5,6 +5,7 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+pub mod attrs;
 pub mod class_field;
 pub mod class_metadata;
 pub mod classdef;
@@ -12,6 +13,10 @@ pub mod dataclass;
 pub mod enums;
 pub mod named_tuple;
 pub mod new_type;
+pub mod plugin;
+pub mod pydantic;
+pub mod slots;
 pub mod targs;
+pub mod total_ordering;
 pub mod typed_dict;
 pub mod variance_inference;
@@ -0,0 +1,78 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use ruff_python_ast::name::Name;
+
+use crate::alt::answers::AnswersSolver;
+use crate::alt::answers::LookupAnswer;
+use crate::alt::types::class_metadata::ClassMetadata;
+use crate::alt::types::class_metadata::ClassSynthesizedFields;
+use crate::plugin::PluginContext;
+use crate::plugin::PluginKey;
+use crate::types::class::Class;
+use crate::types::class::ClassType;
+use crate::types::stdlib::Stdlib;
+use crate::types::types::CalleeKind;
+use crate::types::types::Type;
+
+impl<'a, Ans: LookupAnswer> PluginContext for AnswersSolver<'a, Ans> {
+    fn stdlib(&self) -> &Stdlib {
+        self.stdlib
+    }
+
+    fn instantiate(&self, cls: &Class) -> Type {
+        AnswersSolver::instantiate(self, cls)
+    }
+
+    fn class_field_type(&self, cls: &Class, name: &Name) -> Option<Type> {
+        self.get_non_synthesized_class_member(cls, name)
+            .map(|member| member.value.raw_type().clone())
+    }
+
+    fn has_ancestor(&self, cls: &Class, module: &str, name: &str) -> bool {
+        self.get_metadata_for_class(cls)
+            .ancestors(self.stdlib)
+            .any(|ancestor| ancestor.has_qname(module, name))
+    }
+}
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    /// Find the plugin registered for a class decorator, if any.
+    pub fn get_decorator_plugin(&self, decorator: &Type) -> Option<PluginKey> {
+        if self.plugins.is_empty() {
+            return None;
+        }
+        let key = match decorator.callee_kind()? {
+            CalleeKind::Function(kind) => PluginKey::of_func_id(&kind.as_func_id()),
+            _ => return None,
+        };
+        self.plugins.get(&key).map(|_| key)
+    }
+
+    /// Find the plugin registered for one of the bases of a class, or inherited by it.
+    pub fn get_base_plugin(
+        &self,
+        bases_with_metadata: &[(ClassType, Arc<ClassMetadata>)],
+    ) -> Option<PluginKey> {
+        bases_with_metadata.iter().find_map(|(base, metadata)| {
+            metadata.plugin().cloned().or_else(|| {
+                let base = base.class_object();
+                self.plugins
+                    .get_by_name(base.module_name(), base.name())
+                    .map(|(key, _)| key)
+            })
+        })
+    }
+
+    pub fn get_plugin_synthesized_fields(&self, cls: &Class) -> Option<ClassSynthesizedFields> {
+        let metadata = self.get_metadata_for_class(cls);
+        let plugin = self.plugins.get(metadata.plugin()?)?;
+        plugin.class_synthesized_fields(self, cls)
+    }
+}
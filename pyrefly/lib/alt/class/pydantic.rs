@@ -0,0 +1,321 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use ruff_python_ast::Expr;
+use ruff_python_ast::Keyword;
+use ruff_python_ast::name::Name;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+use starlark_map::small_set::SmallSet;
+
+use crate::alt::answers::AnswersSolver;
+use crate::alt::answers::LookupAnswer;
+use crate::alt::class::class_field::DataclassFieldKeywords;
+use crate::alt::types::class_metadata::ClassMetadata;
+use crate::alt::types::class_metadata::ClassSynthesizedField;
+use crate::alt::types::class_metadata::DataclassKind;
+use crate::alt::types::class_metadata::DataclassMetadata;
+use crate::binding::binding::Binding;
+use crate::binding::binding::Key;
+use crate::dunder;
+use crate::error::collector::ErrorCollector;
+use crate::error::kind::ErrorKind;
+use crate::graph::index::Idx;
+use crate::module::module_name::ModuleName;
+use crate::types::callable::BoolKeywords;
+use crate::types::callable::Callable;
+use crate::types::callable::DataclassKeywords;
+use crate::types::callable::FuncMetadata;
+use crate::types::callable::Function;
+use crate::types::callable::FunctionKind;
+use crate::types::callable::Param;
+use crate::types::callable::ParamList;
+use crate::types::callable::PydanticKeywords;
+use crate::types::class::Class;
+use crate::types::class::ClassType;
+use crate::types::literal::Lit;
+use crate::types::types::CalleeKind;
+use crate::types::types::Type;
+
+const ROOT: Name = Name::new_static("root");
+const MODEL_CONFIG: Name = Name::new_static("model_config");
+const CHECK_FIELDS: Name = Name::new_static("check_fields");
+const MODE: Name = Name::new_static("mode");
+
+/// A method decorated with `@field_validator(...)` or `@model_validator(...)`.
+pub struct PydanticValidator {
+    /// Pydantic wraps validators in `classmethod` unless they already are one, except for
+    /// `@model_validator(mode="after")`, which runs on the instance.
+    pub is_classmethod: bool,
+    /// The fields named by a `field_validator`, which must exist on the model unless
+    /// `check_fields=False` is passed.
+    pub fields: Vec<(Name, TextRange)>,
+}
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    /// Gets dataclass-like metadata for a subclass of `pydantic.BaseModel`. The model config is
+    /// inherited from the base models, and can be overridden by class keywords (e.g.
+    /// `class M(BaseModel, frozen=True)`) or a `model_config = ConfigDict(...)` assignment.
+    pub fn get_pydantic_metadata(
+        &self,
+        cls: &Class,
+        bases_with_metadata: &[(ClassType, Arc<ClassMetadata>)],
+        keywords: &[(Name, Type)],
+        model_config: Option<&[(Name, Expr)]>,
+    ) -> Option<DataclassMetadata> {
+        let mut kind = None;
+        let mut base_kws = None;
+        let mut fields = SmallSet::new();
+        for (base, metadata) in bases_with_metadata.iter().rev() {
+            let base_kind = if base.has_qname("pydantic.root_model", "RootModel") {
+                Some(DataclassKind::PydanticRootModel)
+            } else if base.has_qname("pydantic.main", "BaseModel") {
+                Some(DataclassKind::PydanticModel)
+            } else {
+                metadata
+                    .dataclass_metadata()
+                    .filter(|dataclass| dataclass.kind.is_pydantic())
+                    .map(|dataclass| {
+                        fields.extend(dataclass.fields.iter().cloned());
+                        base_kws = Some(dataclass.kws.clone());
+                        dataclass.kind
+                    })
+            };
+            if let Some(base_kind) = base_kind
+                && kind != Some(DataclassKind::PydanticRootModel)
+            {
+                kind = Some(base_kind);
+            }
+        }
+        let kind = kind?;
+        let mut kws = base_kws.unwrap_or_else(|| {
+            let mut kws = BoolKeywords::new();
+            // Pydantic models are constructed with keyword arguments only, and don't define
+            // `__match_args__`.
+            kws.set(DataclassKeywords::KW_ONLY.0, true);
+            kws.set(DataclassKeywords::MATCH_ARGS.0, false);
+            kws
+        });
+        let ignore_errors = self.error_swallower();
+        let config = keywords.iter().cloned().chain(
+            model_config
+                .into_iter()
+                .flatten()
+                .map(|(name, value)| (name.clone(), self.expr_infer(value, &ignore_errors))),
+        );
+        for (name, value) in config {
+            match value {
+                Type::Literal(Lit::Bool(b)) => kws.set(name, b),
+                Type::Literal(Lit::Str(s)) if name == PydanticKeywords::EXTRA => {
+                    kws.set(name, &*s == "allow")
+                }
+                _ => {}
+            }
+        }
+        if kind == DataclassKind::PydanticRootModel {
+            fields = SmallSet::new();
+            fields.insert(ROOT);
+        } else {
+            for name in cls.fields() {
+                // Names starting with an underscore are private attributes, not fields.
+                if cls.is_field_annotated(name)
+                    && !name.as_str().starts_with('_')
+                    && *name != MODEL_CONFIG
+                {
+                    fields.insert(name.clone());
+                }
+            }
+        }
+        Some(DataclassMetadata { fields, kws, kind })
+    }
+
+    /// Collects the arguments passed to `pydantic.Field()`. The default can be passed
+    /// positionally, where `...` means that the field is required.
+    pub fn pydantic_field_keywords(
+        &self,
+        args: &[Expr],
+        keywords: &[Keyword],
+        errors: &ErrorCollector,
+    ) -> DataclassFieldKeywords {
+        let mut flags = BoolKeywords::new();
+        if args
+            .first()
+            .is_some_and(|arg| !matches!(arg, Expr::EllipsisLiteral(_)))
+        {
+            flags.set(DataclassKeywords::DEFAULT.0, true);
+        }
+        let mut alias = None;
+        for kw in keywords {
+            let Some(id) = &kw.arg else {
+                continue;
+            };
+            if id.id == DataclassKeywords::DEFAULT.0 {
+                if !matches!(kw.value, Expr::EllipsisLiteral(_)) {
+                    flags.set(DataclassKeywords::DEFAULT.0, true);
+                }
+            } else if id.id == PydanticKeywords::DEFAULT_FACTORY {
+                flags.set(DataclassKeywords::DEFAULT.0, true);
+            } else if id.id == PydanticKeywords::ALIAS {
+                if let Type::Literal(Lit::Str(s)) = self.expr_infer(&kw.value, errors) {
+                    alias = Some(Name::new(&*s));
+                }
+            } else {
+                let val = self.expr_infer(&kw.value, errors);
+                flags.set_keyword(kw.arg.as_ref(), val);
+            }
+        }
+        DataclassFieldKeywords {
+            flags,
+            converter_param: None,
+            alias,
+        }
+    }
+
+    /// Gets `__init__` method for a Pydantic model. Parameters are keyword-only and named after
+    /// the field's alias, unless `populate_by_name` is set, in which case they are optional, named
+    /// after the field, and the aliases are accepted as extra keyword arguments. A `RootModel` takes
+    /// its root value as the only argument.
+    pub fn get_pydantic_init(
+        &self,
+        cls: &Class,
+        dataclass: &DataclassMetadata,
+    ) -> ClassSynthesizedField {
+        let is_root_model = dataclass.kind == DataclassKind::PydanticRootModel;
+        let kw_only = !is_root_model && dataclass.kws.is_set(&DataclassKeywords::KW_ONLY);
+        let populate_by_name = dataclass
+            .kws
+            .get(&PydanticKeywords::POPULATE_BY_NAME)
+            .unwrap_or(false);
+        let mut accepts_extra = dataclass.kws.get(&PydanticKeywords::EXTRA).unwrap_or(false);
+        let mut params = vec![self.class_self_param(cls, false)];
+        for (name, field, field_flags) in self.iter_fields(cls, dataclass, false) {
+            if !field_flags.is_set(&DataclassKeywords::INIT) {
+                continue;
+            }
+            let alias = field
+                .dataclass_field_keywords()
+                .and_then(|field_kws| field_kws.alias.clone());
+            let mut has_default = field_flags.is_set(&DataclassKeywords::DEFAULT);
+            let param_name = match alias {
+                // The field may be passed by its alias instead, so we can't insist on the name.
+                Some(_) if populate_by_name => {
+                    accepts_extra = true;
+                    has_default = true;
+                    name
+                }
+                Some(alias) => alias,
+                None => name,
+            };
+            params.push(field.as_param(
+                &param_name,
+                has_default,
+                kw_only || field_flags.is_set(&DataclassKeywords::KW_ONLY),
+            ));
+        }
+        if accepts_extra && !is_root_model {
+            params.push(Param::Kwargs(None, Type::any_implicit()));
+        }
+        let ty = Type::Function(Box::new(Function {
+            signature: Callable::list(ParamList::new(params), Type::None),
+            metadata: FuncMetadata::def(
+                self.module_info().name(),
+                cls.name().clone(),
+                dunder::INIT,
+            ),
+        }));
+        ClassSynthesizedField::new(ty)
+    }
+
+    /// If `decorator` is a call to `pydantic.field_validator` or `pydantic.model_validator`,
+    /// return how it affects the decorated method.
+    pub fn pydantic_validator(&self, decorator: Idx<Key>) -> Option<PydanticValidator> {
+        let Binding::Decorator(Expr::Call(call)) = self.bindings().get(decorator) else {
+            return None;
+        };
+        let Some(CalleeKind::Function(FunctionKind::Def(id))) = self
+            .expr_infer(&call.func, &self.error_swallower())
+            .callee_kind()
+        else {
+            return None;
+        };
+        if id.module != ModuleName::pydantic_functional_validators() || id.cls.is_some() {
+            return None;
+        }
+        let keyword = |name: &Name| {
+            call.arguments
+                .keywords
+                .iter()
+                .find(|kw| kw.arg.as_ref().is_some_and(|arg| arg.id == *name))
+                .map(|kw| &kw.value)
+        };
+        match id.func.as_str() {
+            "field_validator" => {
+                let check_fields =
+                    !matches!(keyword(&CHECK_FIELDS), Some(Expr::BooleanLiteral(b)) if !b.value);
+                let fields = if check_fields {
+                    call.arguments
+                        .args
+                        .iter()
+                        .filter_map(|arg| match arg {
+                            // `"*"` validates every field.
+                            Expr::StringLiteral(s) if s.value.to_str() != "*" => {
+                                Some((Name::new(s.value.to_str()), s.range()))
+                            }
+                            _ => None,
+                        })
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+                Some(PydanticValidator {
+                    is_classmethod: true,
+                    fields,
+                })
+            }
+            "model_validator" => Some(PydanticValidator {
+                is_classmethod: !matches!(
+                    keyword(&MODE),
+                    Some(Expr::StringLiteral(s)) if s.value.to_str() == "after"
+                ),
+                fields: Vec::new(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Check that the fields named by a `field_validator` on a method of `cls` exist.
+    pub fn check_pydantic_validator(
+        &self,
+        cls: &Class,
+        validator: &PydanticValidator,
+        errors: &ErrorCollector,
+    ) {
+        let metadata = self.get_metadata_for_class(cls);
+        let Some(dataclass) = metadata
+            .dataclass_metadata()
+            .filter(|dataclass| dataclass.kind.is_pydantic())
+        else {
+            return;
+        };
+        for (field, range) in &validator.fields {
+            if !dataclass.fields.contains(field) {
+                self.error(
+                    errors,
+                    *range,
+                    ErrorKind::InvalidArgument,
+                    None,
+                    format!(
+                        "Validator refers to `{field}`, which is not a field of `{}`",
+                        cls.name()
+                    ),
+                );
+            }
+        }
+    }
+}
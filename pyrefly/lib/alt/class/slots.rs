@@ -0,0 +1,82 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use ruff_python_ast::name::Name;
+use ruff_text_size::TextRange;
+use starlark_map::small_map::SmallMap;
+
+use crate::alt::answers::AnswersSolver;
+use crate::alt::answers::LookupAnswer;
+use crate::alt::types::class_metadata::ClassSynthesizedField;
+use crate::alt::types::class_metadata::ClassSynthesizedFields;
+use crate::dunder;
+use crate::error::collector::ErrorCollector;
+use crate::error::kind::ErrorKind;
+use crate::types::class::Class;
+use crate::types::types::Type;
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    /// Check that an attribute which is not initialized in the class body can actually be stored
+    /// on instances, i.e. that it is one of the class's `__slots__`, if it has any.
+    pub fn check_instance_attribute_in_slots(
+        &self,
+        cls: &Class,
+        name: &Name,
+        range: TextRange,
+        errors: &ErrorCollector,
+    ) {
+        let metadata = self.get_metadata_for_class(cls);
+        let Some(slots) = metadata.slots_metadata() else {
+            return;
+        };
+        if slots.has_dict || slots.slots.contains(name) {
+            return;
+        }
+        // If a parent defines the attribute (say, as a property), assignment goes through that.
+        if metadata
+            .bases_with_metadata()
+            .iter()
+            .any(|(base, _)| self.get_class_member(base.class_object(), name).is_some())
+        {
+            return;
+        }
+        self.error(
+            errors,
+            range,
+            ErrorKind::MissingAttribute,
+            None,
+            format!(
+                "Attribute `{}` is not declared in `__slots__` of class `{}`",
+                name,
+                cls.name(),
+            ),
+        );
+    }
+
+    /// Slots that are not otherwise declared in the class body still exist as attributes on
+    /// instances, so we synthesize a field for them.
+    pub fn get_slots_synthesized_fields(&self, cls: &Class) -> Option<ClassSynthesizedFields> {
+        let metadata = self.get_metadata_for_class(cls);
+        let slots = metadata.slots_metadata()?;
+        let fields = slots
+            .slots
+            .iter()
+            .filter(|name| {
+                **name != dunder::DICT
+                    && **name != dunder::WEAKREF
+                    && self.get_non_synthesized_class_member(cls, name).is_none()
+            })
+            .map(|name| {
+                (
+                    name.clone(),
+                    ClassSynthesizedField::new(Type::any_implicit()),
+                )
+            })
+            .collect::<SmallMap<_, _>>();
+        Some(ClassSynthesizedFields::new(fields))
+    }
+}
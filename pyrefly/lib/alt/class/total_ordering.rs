@@ -0,0 +1,120 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use ruff_python_ast::name::Name;
+use starlark_map::small_map::SmallMap;
+
+use crate::alt::answers::AnswersSolver;
+use crate::alt::answers::LookupAnswer;
+use crate::alt::class::class_field::ClassField;
+use crate::alt::types::class_metadata::ClassMetadata;
+use crate::alt::types::class_metadata::ClassSynthesizedField;
+use crate::alt::types::class_metadata::ClassSynthesizedFields;
+use crate::dunder;
+use crate::error::collector::ErrorCollector;
+use crate::error::kind::ErrorKind;
+use crate::types::callable::Callable;
+use crate::types::callable::FuncMetadata;
+use crate::types::callable::Function;
+use crate::types::callable::Param;
+use crate::types::callable::ParamList;
+use crate::types::callable::Required;
+use crate::types::class::Class;
+use crate::types::class::ClassType;
+use crate::types::types::Type;
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    /// `@total_ordering` raises at runtime if the class does not define at least one ordering
+    /// method. We check this while computing metadata, so we can only look at the class body and
+    /// the (already solved) base classes.
+    pub fn validate_total_ordering_root(
+        &self,
+        cls: &Class,
+        bases_with_metadata: &[(ClassType, Arc<ClassMetadata>)],
+        errors: &ErrorCollector,
+    ) {
+        let has_root = dunder::RICH_CMPS_TOTAL_ORDERING.iter().any(|name| {
+            cls.contains(name)
+                || bases_with_metadata.iter().any(|(base, _)| {
+                    self.get_class_member(base.class_object(), name)
+                        .is_some_and(|member| !member.defined_on("builtins", "object"))
+                })
+        });
+        if !has_root {
+            self.error(
+                errors,
+                cls.range(),
+                ErrorKind::BadClassDefinition,
+                None,
+                format!(
+                    "Class `{}` must define at least one of `__lt__`, `__le__`, `__gt__`, or `__ge__` to use `functools.total_ordering`",
+                    cls.name(),
+                ),
+            );
+        }
+    }
+
+    /// Find the ordering method that `@total_ordering` derives the others from, along with the
+    /// names of the ordering methods that it will fill in.
+    fn get_total_ordering_root(&self, cls: &Class) -> (Option<Arc<ClassField>>, Vec<Name>) {
+        let mut root = None;
+        let mut missing = Vec::new();
+        for name in dunder::RICH_CMPS_TOTAL_ORDERING {
+            match self.get_non_synthesized_class_member(cls, name) {
+                Some(member) if !member.defined_on("builtins", "object") => {
+                    if root.is_none() {
+                        root = Some(member.value);
+                    }
+                }
+                _ => missing.push(name.clone()),
+            }
+        }
+        (root, missing)
+    }
+
+    /// Synthesizes the ordering methods that a `@functools.total_ordering`-decorated class does
+    /// not define itself, using the `other` parameter type of the method it does define.
+    pub fn get_total_ordering_synthesized_fields(
+        &self,
+        cls: &Class,
+    ) -> Option<ClassSynthesizedFields> {
+        let metadata = self.get_metadata_for_class(cls);
+        if !metadata.is_total_ordering() {
+            return None;
+        }
+        let (root, missing) = self.get_total_ordering_root(cls);
+        // If there is no root we already reported an error; fall back to comparing with `Self`
+        // so that uses of the class don't produce additional errors.
+        let other_type = root
+            .and_then(|root| root.as_binary_method_other_param())
+            .unwrap_or_else(|| self.instantiate(cls));
+        let signature = Callable::list(
+            ParamList::new(vec![
+                self.class_self_param(cls, false),
+                Param::Pos(Name::new_static("other"), other_type, Required::Required),
+            ]),
+            self.stdlib.bool().clone().to_type(),
+        );
+        let fields = missing
+            .into_iter()
+            .map(|name| {
+                let ty = Type::Function(Box::new(Function {
+                    signature: signature.clone(),
+                    metadata: FuncMetadata::def(
+                        self.module_info().name(),
+                        cls.name().clone(),
+                        name.clone(),
+                    ),
+                }));
+                (name, ClassSynthesizedField::new(ty))
+            })
+            .collect::<SmallMap<_, _>>();
+        Some(ClassSynthesizedFields::new(fields))
+    }
+}
@@ -1368,12 +1368,22 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                         Some(CalleeKind::Function(FunctionKind::IsInstance))
                             if self.has_exactly_two_posargs(&x.arguments) =>
                         {
-                            self.call_isinstance(&x.arguments.args[0], &x.arguments.args[1], errors)
+                            self.call_isinstance(
+                                &x.arguments.args[0],
+                                &x.arguments.args[1],
+                                x.range,
+                                errors,
+                            )
                         }
                         Some(CalleeKind::Function(FunctionKind::IsSubclass))
                             if self.has_exactly_two_posargs(&x.arguments) =>
                         {
-                            self.call_issubclass(&x.arguments.args[0], &x.arguments.args[1], errors)
+                            self.call_issubclass(
+                                &x.arguments.args[0],
+                                &x.arguments.args[1],
+                                x.range,
+                                errors,
+                            )
                         }
                         _ => {
                             let callable = self.as_call_target_or_error(
@@ -142,6 +142,20 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
+    /// Report an unannotated parameter, for strict mode.
+    fn implicit_any_param(&self, name: &Identifier, errors: &ErrorCollector) {
+        if !self.is_enabled(ErrorKind::ImplicitAny) {
+            return;
+        }
+        self.error(
+            errors,
+            name.range,
+            ErrorKind::ImplicitAny,
+            None,
+            format!("Parameter `{name}` is unannotated, so its type is implicitly `Any`"),
+        );
+    }
+
     pub fn function_definition(
         &self,
         def: &StmtFunctionDef,
@@ -170,6 +184,8 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         let mut has_enum_member_decoration = false;
         let mut is_override = false;
         let mut has_final_decoration = false;
+        let mut singledispatch_registers = Vec::new();
+        let mut pydantic_validators = Vec::new();
         let decorators = decorators
             .iter()
             .filter(|k| {
@@ -211,7 +227,20 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                         has_final_decoration = true;
                         false
                     }
-                    _ => true,
+                    _ => {
+                        if let Some(validator) = self.pydantic_validator(**k) {
+                            is_classmethod |= validator.is_classmethod;
+                            pydantic_validators.push(validator);
+                            return true;
+                        }
+                        match self.singledispatch_register(**k) {
+                            Some(register) => {
+                                singledispatch_registers.push(register);
+                                false
+                            }
+                            None => true,
+                        }
+                    }
                 }
             })
             .collect::<Vec<_>>();
@@ -225,6 +254,8 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             self_type = self_type.map(Type::type_form);
         }
 
+        // The first parameter of a method is its receiver, which needn't be annotated.
+        let mut is_receiver = defining_cls.is_some() && !is_staticmethod;
         // Determine the type of the parameter based on its binding. Left is annotated parameter, right is unannotated
         let mut get_param_ty = |name: &Identifier, default: Option<&Expr>| {
             let ty = match self.bindings().get_function_param(name) {
@@ -248,6 +279,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     param_ty
                 }
                 Either::Right(var) => {
+                    if !is_receiver {
+                        self.implicit_any_param(name, errors);
+                    }
                     // If this is the first parameter and there is a self type, solve to `Self`.
                     // We only try to solve the first param for now. Other unannotated params
                     // are also Var. If a default value of type T is provided, it will resolve to Any | T.
@@ -270,6 +304,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 }
             };
             self_type = None; // Stop using `self` type solve Var params after the first param.
+            is_receiver = false;
             ty
         };
         let mut paramspec_args = None;
@@ -329,7 +364,10 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     let annot = self.get_idx(idx);
                     annot.annotation.get_type().clone()
                 }
-                Either::Right(var) => self.solver().force_var(var),
+                Either::Right(var) => {
+                    self.implicit_any_param(&x.name, errors);
+                    self.solver().force_var(var)
+                }
             };
             if let Type::Kwargs(q) = &ty {
                 paramspec_kwargs = Some(q.clone());
@@ -459,6 +497,22 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 t => t,
             }
         }
+        if let Some(cls) = &defining_cls {
+            for validator in &pydantic_validators {
+                self.check_pydantic_validator(cls, validator, errors);
+            }
+        }
+        // `register` returns the implementation unchanged, so we only check the implementation
+        // against the `singledispatch` function.
+        for register in singledispatch_registers {
+            self.check_singledispatch_register(
+                def,
+                &ty,
+                &register,
+                defining_cls.is_some() && !is_staticmethod,
+                errors,
+            );
+        }
         Arc::new(DecoratedFunction {
             id_range: def.name.range,
             ty,
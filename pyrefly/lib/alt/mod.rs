@@ -15,6 +15,7 @@ pub mod expr;
 pub mod function;
 pub mod narrow;
 pub mod operators;
+pub mod singledispatch;
 pub mod solve;
 pub mod special_calls;
 pub mod specials;
@@ -170,6 +170,50 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
+    /// Whether an `isinstance` check of a value of type `left` against the class or tuple of
+    /// classes `right` always has the same result, or `None` if it could go either way.
+    pub fn isinstance_result(&self, left: &Type, right: &Type) -> Option<bool> {
+        if left.is_never() {
+            // The check is unreachable, which is reported elsewhere if at all.
+            None
+        } else if self.narrow_is_not_instance(left, right).is_never() {
+            Some(true)
+        } else if self.isinstance_is_never(left, right) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    fn isinstance_is_never(&self, left: &Type, right: &Type) -> bool {
+        if let Some(ts) = right.as_decomposed_tuple_or_union(self.stdlib) {
+            ts.iter().all(|t| self.isinstance_is_never(left, t))
+        } else if let Some(right) = self.unwrap_class_object_silently(right) {
+            let is_disjoint = |left: &Type| {
+                // Unless one side can't be subclassed, some class might inherit from both.
+                (self.is_final_type(left) || self.is_final_type(&right))
+                    && !self.is_subset_eq(left, &right)
+                    && !self.is_subset_eq(&right, left)
+            };
+            match left {
+                Type::Union(ts) => ts.iter().all(is_disjoint),
+                _ => is_disjoint(left),
+            }
+        } else {
+            false
+        }
+    }
+
+    /// Whether every value of this type has exactly the same class, so it can't be an instance
+    /// of any other class.
+    fn is_final_type(&self, ty: &Type) -> bool {
+        match ty {
+            Type::Literal(_) | Type::None => true,
+            Type::ClassType(cls) => self.get_metadata_for_class(cls.class_object()).is_final(),
+            _ => false,
+        }
+    }
+
     fn narrow_issubclass(&self, left: &Type, right: &Type, range: TextRange) -> Type {
         if let Some(ts) = right.as_decomposed_tuple_or_union(self.stdlib) {
             self.unions(
@@ -33,6 +33,35 @@ use crate::types::literal::Lit;
 use crate::types::tuple::Tuple;
 use crate::types::types::Type;
 
+/// Whether `left == right` is known to be `False`, because both sides are literals (or unions of
+/// them) and no pair of them is equal.
+fn literals_never_equal(left: &Type, right: &Type) -> bool {
+    fn literals(ty: &Type) -> Option<Vec<&Lit>> {
+        match ty {
+            Type::Literal(lit) => Some(vec![lit]),
+            Type::Union(ts) => ts
+                .iter()
+                .map(|t| match t {
+                    Type::Literal(lit) => Some(lit),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+    let (Some(left), Some(right)) = (literals(left), literals(right)) else {
+        return false;
+    };
+    left.iter().all(|l| {
+        right.iter().all(|r| match (l, r) {
+            // Enums may define their own `__eq__`, and `1 == True`.
+            (Lit::Enum(_), _) | (_, Lit::Enum(_)) => false,
+            (Lit::Int(_), Lit::Bool(_)) | (Lit::Bool(_), Lit::Int(_)) => false,
+            _ => l != r,
+        })
+    })
+}
+
 impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
     fn callable_dunder_helper(
         &self,
@@ -313,6 +342,24 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             comparisons
                 .map(|(op, comparator)| {
                     let right = self.expr_infer(comparator, errors);
+                    if matches!(op, CmpOp::Eq | CmpOp::NotEq)
+                        && self.is_enabled(ErrorKind::UnnecessaryComparison)
+                        && literals_never_equal(&left, &right)
+                    {
+                        self.error(
+                            errors,
+                            x.range,
+                            ErrorKind::UnnecessaryComparison,
+                            None,
+                            format!(
+                                "`{}` between `{}` and `{}` is always `{}`, since the literals can never be equal",
+                                op.as_str(),
+                                self.for_display(left.clone()),
+                                self.for_display(right.clone()),
+                                if *op == CmpOp::Eq { "False" } else { "True" },
+                            ),
+                        );
+                    }
                     self.distribute_over_union(&left, |left| {
                         self.distribute_over_union(&right, |right| {
                             let context = || {
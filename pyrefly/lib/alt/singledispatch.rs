@@ -0,0 +1,189 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use itertools::Either;
+use ruff_python_ast::Expr;
+use ruff_python_ast::StmtFunctionDef;
+use ruff_text_size::Ranged;
+
+use crate::alt::answers::AnswersSolver;
+use crate::alt::answers::LookupAnswer;
+use crate::binding::binding::Binding;
+use crate::binding::binding::Key;
+use crate::error::collector::ErrorCollector;
+use crate::error::kind::ErrorKind;
+use crate::graph::index::Idx;
+use crate::module::module_name::ModuleName;
+use crate::types::callable::FunctionKind;
+use crate::types::types::Type;
+
+/// How a function is registered as an implementation of a `functools.singledispatch` function
+/// (or `singledispatchmethod`).
+///
+/// We check each implementation against the dispatcher, but calls to the dispatcher are still
+/// checked against its base signature only: the implementations are registered after the
+/// dispatcher is defined, so we don't synthesize an overload for each of them.
+pub struct SingleDispatchRegister {
+    pub kind: SingleDispatchRegisterKind,
+    /// The return type of the `singledispatch` function, which implementations must match.
+    pub ret: Option<Type>,
+}
+
+pub enum SingleDispatchRegisterKind {
+    /// `@f.register`, which dispatches on the annotation of the first parameter.
+    Annotated,
+    /// `@f.register(cls)`, which dispatches on `cls`.
+    Class(Expr),
+}
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    /// If this is the `register` method of a `singledispatch` function or `singledispatchmethod`,
+    /// return the dispatcher's return type, if known.
+    fn singledispatch_register_method(&self, ty: &Type) -> Option<Option<Type>> {
+        let Type::BoundMethod(method) = ty else {
+            return None;
+        };
+        let is_register = match &method.func.metadata().kind {
+            FunctionKind::Def(id) => {
+                id.module == ModuleName::functools()
+                    && id.func.as_str() == "register"
+                    && id.cls.as_ref().is_some_and(|cls| {
+                        matches!(
+                            cls.as_str(),
+                            "_SingleDispatchCallable" | "singledispatchmethod"
+                        )
+                    })
+            }
+            _ => false,
+        };
+        if !is_register {
+            return None;
+        }
+        Some(match &method.obj {
+            Type::ClassType(cls) => cls.targs().as_slice().first().cloned(),
+            _ => None,
+        })
+    }
+
+    /// If `decorator` registers the decorated function as an implementation of a
+    /// `singledispatch` function, return how it is registered.
+    pub fn singledispatch_register(&self, decorator: Idx<Key>) -> Option<SingleDispatchRegister> {
+        if let Some(ret) = self.singledispatch_register_method(self.get_idx(decorator).ty()) {
+            return Some(SingleDispatchRegister {
+                kind: SingleDispatchRegisterKind::Annotated,
+                ret,
+            });
+        }
+        // For `@f.register(cls)`, the decorator is the result of the call, so look at the callee.
+        if let Binding::Decorator(Expr::Call(call)) = self.bindings().get(decorator)
+            && let Expr::Attribute(attr) = &*call.func
+            && attr.attr.id.as_str() == "register"
+            && let [cls] = &*call.arguments.args
+            && call.arguments.keywords.is_empty()
+            && let Some(ret) = self.singledispatch_register_method(
+                &self.expr_infer(&call.func, &self.error_swallower()),
+            )
+        {
+            return Some(SingleDispatchRegister {
+                kind: SingleDispatchRegisterKind::Class(cls.clone()),
+                ret,
+            });
+        }
+        None
+    }
+
+    /// Check that a registered implementation can be dispatched to: `@f.register` needs an
+    /// annotated first parameter, and `@f.register(cls)` needs a first parameter that accepts `cls`.
+    /// For `singledispatchmethod`, the receiver is skipped. The implementation `ty` must also
+    /// return what the dispatcher returns.
+    pub fn check_singledispatch_register(
+        &self,
+        def: &StmtFunctionDef,
+        ty: &Type,
+        register: &SingleDispatchRegister,
+        has_receiver: bool,
+        errors: &ErrorCollector,
+    ) {
+        if let Some(want) = &register.ret
+            && let Some(got) = ty.clone().callable_return_type()
+            && !self.is_subset_eq(&got, want)
+        {
+            self.error(
+                errors,
+                def.returns.as_ref().map_or(def.name.range, |x| x.range()),
+                ErrorKind::InvalidArgument,
+                None,
+                format!(
+                    "`{}` is registered with a `singledispatch` function returning `{}`, but returns `{}`",
+                    def.name.id,
+                    self.for_display(want.clone()),
+                    self.for_display(got),
+                ),
+            );
+        }
+        let param = def
+            .parameters
+            .posonlyargs
+            .iter()
+            .chain(&def.parameters.args)
+            .nth(if has_receiver { 1 } else { 0 });
+        match &register.kind {
+            SingleDispatchRegisterKind::Annotated => {
+                if param.is_none_or(|p| p.parameter.annotation.is_none()) {
+                    self.error(
+                        errors,
+                        def.name.range,
+                        ErrorKind::InvalidArgument,
+                        None,
+                        format!(
+                            "Cannot register `{}` with `register`, the argument it dispatches on must be annotated",
+                            def.name.id
+                        ),
+                    );
+                }
+            }
+            SingleDispatchRegisterKind::Class(cls) => {
+                let Some(param) = param else {
+                    self.error(
+                        errors,
+                        def.name.range,
+                        ErrorKind::InvalidArgument,
+                        None,
+                        format!(
+                            "Cannot register `{}` with `register`, it has no argument to dispatch on",
+                            def.name.id
+                        ),
+                    );
+                    return;
+                };
+                let param_ty = match self.bindings().get_function_param(&param.parameter.name) {
+                    Either::Left(idx) => self.get_idx(idx).annotation.get_type().clone(),
+                    Either::Right(_) => return,
+                };
+                let Type::ClassDef(cls_def) = self.expr_infer(cls, &self.error_swallower()) else {
+                    return;
+                };
+                let instance = self.promote_silently(&cls_def);
+                if !self.is_subset_eq(&instance, &param_ty) {
+                    self.error(
+                        errors,
+                        cls.range(),
+                        ErrorKind::InvalidArgument,
+                        None,
+                        format!(
+                            "`{}` is registered for `{}`, but its argument `{}` has type `{}`",
+                            def.name.id,
+                            self.for_display(instance),
+                            param.parameter.name.id,
+                            self.for_display(param_ty),
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
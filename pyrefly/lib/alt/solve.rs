@@ -208,6 +208,8 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             decorators,
             is_new_type,
             special_base,
+            slots,
+            model_config,
         } = binding;
         let metadata = match &self.get_idx(*k).0 {
             None => ClassMetadata::recursive(),
@@ -218,6 +220,8 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 decorators,
                 *is_new_type,
                 special_base,
+                slots.as_deref(),
+                model_config.as_deref(),
                 errors,
             ),
         };
@@ -1119,6 +1123,17 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 let ty = self.expr_infer(x, &self.error_swallower());
                 self.check_dunder_bool_is_callable(&ty, *range, errors);
             }
+            BindingExpect::Unreachable(xs, range) => {
+                if xs.iter().any(|x| self.get_idx(*x).ty().is_never()) {
+                    self.error(
+                        errors,
+                        *range,
+                        ErrorKind::Unreachable,
+                        None,
+                        "This code is unreachable".to_owned(),
+                    );
+                }
+            }
             BindingExpect::Delete(x) => match &**x {
                 Expr::Name(_) => {
                     self.expr_infer(x, errors);
@@ -1299,6 +1314,35 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         Arc::new(NoneIfRecursive(Some(cls)))
     }
 
+    /// The module ran out of solve budget before we got to this class. Create the class,
+    /// since other bindings refer to it, but skip working out its type parameters.
+    pub fn solve_class_timed_out(
+        &self,
+        cls: &BindingClass,
+        errors: &ErrorCollector,
+    ) -> Arc<NoneIfRecursive<Class>> {
+        let (def_index, name, fields) = match cls {
+            BindingClass::ClassDef(x) => (x.def_index, &x.def.name, &x.fields),
+            BindingClass::FunctionalClassDef(def_index, name, fields) => (*def_index, name, fields),
+        };
+        self.error(
+            errors,
+            name.range,
+            ErrorKind::TooComplex,
+            None,
+            format!(
+                "Class `{name}` was not solved within the solve budget, so it is treated as having no type parameters",
+            ),
+        );
+        Arc::new(NoneIfRecursive(Some(Class::new(
+            def_index,
+            name.clone(),
+            self.module_info().dupe(),
+            TParams::default(),
+            fields.clone(),
+        ))))
+    }
+
     pub fn solve_class_field(
         &self,
         field: &BindingClassField,
@@ -1330,12 +1374,24 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
     ) -> Arc<ClassSynthesizedFields> {
         let fields = match &self.get_idx(fields.0).0 {
             None => ClassSynthesizedFields::default(),
-            Some(cls) => self
-                .get_typed_dict_synthesized_fields(cls)
-                .or_else(|| self.get_dataclass_synthesized_fields(cls))
-                .or_else(|| self.get_named_tuple_synthesized_fields(cls))
-                .or_else(|| self.get_new_type_synthesized_fields(cls))
-                .unwrap_or_default(),
+            Some(cls) => {
+                let fields = self
+                    .get_plugin_synthesized_fields(cls)
+                    .or_else(|| self.get_typed_dict_synthesized_fields(cls))
+                    .or_else(|| self.get_dataclass_synthesized_fields(cls))
+                    .or_else(|| self.get_named_tuple_synthesized_fields(cls))
+                    .or_else(|| self.get_new_type_synthesized_fields(cls))
+                    .unwrap_or_default();
+                // `@total_ordering` and `__slots__` can be combined with any of the above, and
+                // only fill in fields that are still missing.
+                [
+                    self.get_total_ordering_synthesized_fields(cls),
+                    self.get_slots_synthesized_fields(cls),
+                ]
+                .into_iter()
+                .flatten()
+                .fold(fields, ClassSynthesizedFields::combine)
+            }
         };
         Arc::new(fields)
     }
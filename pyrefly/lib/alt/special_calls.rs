@@ -225,14 +225,35 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 "`typing.cast` missing required argument `typ`".to_owned(),
             )
         };
-        if val.is_none() {
-            self.error(
-                errors,
-                range,
-                ErrorKind::MissingArgument,
-                None,
-                "`typing.cast` missing required argument `val`".to_owned(),
-            );
+        match val {
+            None => {
+                self.error(
+                    errors,
+                    range,
+                    ErrorKind::MissingArgument,
+                    None,
+                    "`typing.cast` missing required argument `val`".to_owned(),
+                );
+            }
+            Some(val) if !ret.is_any() && self.is_enabled(ErrorKind::UnnecessaryCast) => {
+                // The value is otherwise unchecked, so don't report any errors from it.
+                let val_ty = self
+                    .solver()
+                    .deep_force(self.expr_infer(val, &self.error_swallower()));
+                if val_ty == ret {
+                    self.error(
+                        errors,
+                        range,
+                        ErrorKind::UnnecessaryCast,
+                        None,
+                        format!(
+                            "Unnecessary `typing.cast`, the value already has type `{}`",
+                            self.for_display(val_ty)
+                        ),
+                    );
+                }
+            }
+            Some(_) => {}
         }
         ret
     }
@@ -241,12 +262,21 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         &self,
         obj: &Expr,
         class_or_tuple: &Expr,
+        range: TextRange,
         errors: &ErrorCollector,
     ) -> Type {
-        // We call expr_infer in order to check for errors, but we don't need to do anything with
-        // the result, as the `obj` parameter has type `object`.
-        self.expr_infer(obj, errors);
-        self.check_arg_is_class_object(class_or_tuple, &FunctionKind::IsInstance, errors);
+        // The `obj` parameter has type `object`, so we only need its type to tell if the check
+        // is redundant.
+        let obj_ty = self.expr_infer(obj, errors);
+        let class_ty =
+            self.check_arg_is_class_object(class_or_tuple, &FunctionKind::IsInstance, errors);
+        self.check_unnecessary_isinstance(
+            obj_ty,
+            &class_ty,
+            &FunctionKind::IsInstance,
+            range,
+            errors,
+        );
         self.stdlib.bool().clone().to_type()
     }
 
@@ -254,12 +284,14 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         &self,
         cls: &Expr,
         class_or_tuple: &Expr,
+        range: TextRange,
         errors: &ErrorCollector,
     ) -> Type {
         // Verify that the `cls` argument has type `type`.
+        let cls_ty = self.expr_infer(cls, errors);
         self.check_type(
             &self.stdlib.builtins_type().clone().to_type(),
-            &self.expr_infer(cls, errors),
+            &cls_ty,
             cls.range(),
             errors,
             &|| {
@@ -269,10 +301,52 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 ))
             },
         );
-        self.check_arg_is_class_object(class_or_tuple, &FunctionKind::IsSubclass, errors);
+        let class_ty =
+            self.check_arg_is_class_object(class_or_tuple, &FunctionKind::IsSubclass, errors);
+        if self.is_enabled(ErrorKind::UnnecessaryIsinstance)
+            && let Some(instance_ty) = self.untype_opt(cls_ty, cls.range())
+        {
+            self.check_unnecessary_isinstance(
+                instance_ty,
+                &class_ty,
+                &FunctionKind::IsSubclass,
+                range,
+                errors,
+            );
+        }
         self.stdlib.bool().clone().to_type()
     }
 
+    /// Report an `isinstance` check of a value of type `ty`, or an `issubclass` check of a class
+    /// whose instances have type `ty`, that always has the same result.
+    fn check_unnecessary_isinstance(
+        &self,
+        ty: Type,
+        class_or_tuple: &Type,
+        func_kind: &FunctionKind,
+        range: TextRange,
+        errors: &ErrorCollector,
+    ) {
+        if !self.is_enabled(ErrorKind::UnnecessaryIsinstance) {
+            return;
+        }
+        let ty = self.solver().deep_force(ty);
+        if let Some(result) = self.isinstance_result(&ty, class_or_tuple) {
+            self.error(
+                errors,
+                range,
+                ErrorKind::UnnecessaryIsinstance,
+                None,
+                format!(
+                    "Unnecessary `{}()` call, it is always `{}` for `{}`",
+                    func_kind.as_func_id().format(self.module_info().name()),
+                    if result { "True" } else { "False" },
+                    self.for_display(ty)
+                ),
+            );
+        }
+    }
+
     fn check_type_is_class_object(
         &self,
         ty: Type,
@@ -405,12 +479,13 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         false
     }
 
+    /// Check the class or tuple of classes passed to `isinstance` or `issubclass`, and return its type.
     fn check_arg_is_class_object(
         &self,
         arg_expr: &Expr,
         func_kind: &FunctionKind,
         errors: &ErrorCollector,
-    ) {
+    ) -> Type {
         let arg_class_type = self.expr_infer(arg_expr, errors);
         let mut contains_subscript = false;
         arg_expr.visit(&mut |e| {
@@ -420,11 +495,12 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         });
 
         self.check_type_is_class_object(
-            arg_class_type,
+            arg_class_type.clone(),
             contains_subscript,
             arg_expr.range(),
             func_kind,
             errors,
         );
+        arg_class_type
     }
 }
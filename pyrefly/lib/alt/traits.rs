@@ -122,6 +122,18 @@ pub trait Solve<Ans: LookupAnswer>: SolveRecursive {
     /// sufficient for now.
     fn promote_recursive(x: Self::Recursive) -> Self::Answer;
 
+    /// The module ran out of solve budget before this binding was solved.
+    /// Produce an answer without doing any real work.
+    fn time_out(
+        answers: &AnswersSolver<Ans>,
+        binding: &Self::Value,
+        _errors: &ErrorCollector,
+    ) -> Arc<Self::Answer> {
+        Arc::new(Self::promote_recursive(Self::create_recursive(
+            answers, binding,
+        )))
+    }
+
     /// We solved a binding, but during its execution we gave some people back a recursive value.
     /// Record that recursive value along with the answer.
     fn record_recursive(
@@ -151,6 +163,10 @@ impl<Ans: LookupAnswer> Solve<Ans> for Key {
         TypeInfo::of_ty(Type::Var(x))
     }
 
+    fn time_out(_: &AnswersSolver<Ans>, _: &Self::Value, _: &ErrorCollector) -> Arc<Self::Answer> {
+        Arc::new(TypeInfo::of_ty(Type::any_implicit()))
+    }
+
     fn record_recursive(
         answers: &AnswersSolver<Ans>,
         range: TextRange,
@@ -195,6 +211,10 @@ impl<Ans: LookupAnswer> Solve<Ans> for KeyExport {
         Type::Var(x)
     }
 
+    fn time_out(_: &AnswersSolver<Ans>, _: &Self::Value, _: &ErrorCollector) -> Arc<Self::Answer> {
+        Arc::new(Type::any_implicit())
+    }
+
     fn record_recursive(
         answers: &AnswersSolver<Ans>,
         range: TextRange,
@@ -233,6 +253,14 @@ impl<Ans: LookupAnswer> Solve<Ans> for KeyClass {
         answers.solve_class(binding, errors)
     }
 
+    fn time_out(
+        answers: &AnswersSolver<Ans>,
+        binding: &BindingClass,
+        errors: &ErrorCollector,
+    ) -> Arc<NoneIfRecursive<Class>> {
+        answers.solve_class_timed_out(binding, errors)
+    }
+
     fn create_recursive(_: &AnswersSolver<Ans>, _: &Self::Value) -> Self::Recursive {}
 
     fn promote_recursive(_: Self::Recursive) -> Self::Answer {
@@ -24,6 +24,7 @@ use vec1::vec1;
 use crate::alt::class::class_field::ClassField;
 use crate::error::collector::ErrorCollector;
 use crate::error::kind::ErrorKind;
+use crate::plugin::PluginKey;
 use crate::types::callable::BoolKeywords;
 use crate::types::callable::DataclassKeywords;
 use crate::types::class::Class;
@@ -42,10 +43,15 @@ pub struct ClassMetadata {
     enum_metadata: Option<EnumMetadata>,
     protocol_metadata: Option<ProtocolMetadata>,
     dataclass_metadata: Option<DataclassMetadata>,
+    slots_metadata: Option<SlotsMetadata>,
     bases_with_metadata: Vec<(ClassType, Arc<ClassMetadata>)>,
     has_base_any: bool,
     is_new_type: bool,
     is_final: bool,
+    /// Is this class decorated with `@functools.total_ordering`?
+    is_total_ordering: bool,
+    /// The plugin that synthesizes fields for this class, if any.
+    plugin: Option<PluginKey>,
     /// Is it possible for this class to have type parameters that we don't know about?
     /// This can happen if, e.g., a class inherits from Any.
     has_unknown_tparams: bool,
@@ -76,9 +82,12 @@ impl ClassMetadata {
         enum_metadata: Option<EnumMetadata>,
         protocol_metadata: Option<ProtocolMetadata>,
         dataclass_metadata: Option<DataclassMetadata>,
+        slots_metadata: Option<SlotsMetadata>,
         has_base_any: bool,
         is_new_type: bool,
         is_final: bool,
+        is_total_ordering: bool,
+        plugin: Option<PluginKey>,
         has_unknown_tparams: bool,
         errors: &ErrorCollector,
     ) -> ClassMetadata {
@@ -98,10 +107,13 @@ impl ClassMetadata {
             enum_metadata,
             protocol_metadata,
             dataclass_metadata,
+            slots_metadata,
             bases_with_metadata,
             has_base_any,
             is_new_type,
             is_final,
+            is_total_ordering,
+            plugin,
             has_unknown_tparams,
         }
     }
@@ -112,9 +124,14 @@ impl ClassMetadata {
         bases_with_metadata: &[(ClassType, Arc<ClassMetadata>)],
         errors: &ErrorCollector,
     ) {
-        if let Some(dataclass_metadata) = dataclass_metadata {
+        // Only `dataclasses` restricts mixing frozen and non-frozen classes.
+        if let Some(dataclass_metadata) = dataclass_metadata
+            && dataclass_metadata.kind == DataclassKind::Dataclass
+        {
             for (base_type, base_metadata) in bases_with_metadata {
-                if let Some(base_dataclass_metadata) = base_metadata.dataclass_metadata() {
+                if let Some(base_dataclass_metadata) = base_metadata.dataclass_metadata()
+                    && base_dataclass_metadata.kind == DataclassKind::Dataclass
+                {
                     let is_base_frozen = base_dataclass_metadata
                         .kws
                         .is_set(&DataclassKeywords::FROZEN);
@@ -161,10 +178,13 @@ impl ClassMetadata {
             enum_metadata: None,
             protocol_metadata: None,
             dataclass_metadata: None,
+            slots_metadata: None,
             bases_with_metadata: Vec::new(),
             has_base_any: false,
             is_new_type: false,
             is_final: false,
+            is_total_ordering: false,
+            plugin: None,
             has_unknown_tparams: false,
         }
     }
@@ -186,6 +206,14 @@ impl ClassMetadata {
         self.is_final
     }
 
+    pub fn is_total_ordering(&self) -> bool {
+        self.is_total_ordering
+    }
+
+    pub fn plugin(&self) -> Option<&PluginKey> {
+        self.plugin.as_ref()
+    }
+
     pub fn has_base_any(&self) -> bool {
         self.has_base_any
     }
@@ -236,6 +264,10 @@ impl ClassMetadata {
         self.dataclass_metadata.as_ref()
     }
 
+    pub fn slots_metadata(&self) -> Option<&SlotsMetadata> {
+        self.slots_metadata.as_ref()
+    }
+
     pub fn ancestors<'a>(&'a self, stdlib: &'a Stdlib) -> impl Iterator<Item = &'a ClassType> {
         self.ancestors_no_object()
             .iter()
@@ -296,6 +328,16 @@ impl ClassSynthesizedFields {
     pub fn get(&self, name: &Name) -> Option<&ClassSynthesizedField> {
         self.0.get(name)
     }
+
+    /// Combine two sets of synthesized fields. Fields in `self` take precedence.
+    pub fn combine(mut self, other: Self) -> Self {
+        for (name, field) in other.0 {
+            if !self.0.contains_key(&name) {
+                self.0.insert(name, field);
+            }
+        }
+        self
+    }
 }
 
 impl Display for ClassSynthesizedFields {
@@ -363,11 +405,34 @@ pub struct NamedTupleMetadata {
     pub elements: SmallSet<Name>,
 }
 
+/// The library that turned a class into a dataclass-like class. They mostly share the same
+/// machinery, but differ in which fields they pick up and how `__init__` looks.
+#[derive(Clone, Copy, Debug, TypeEq, PartialEq, Eq)]
+pub enum DataclassKind {
+    /// `@dataclasses.dataclass`
+    Dataclass,
+    /// `@attr.s`, `@attrs.define`, and friends.
+    Attrs,
+    /// A subclass of `pydantic.BaseModel`.
+    PydanticModel,
+    /// A subclass of `pydantic.RootModel`, whose `__init__` takes the root value.
+    PydanticRootModel,
+}
+
+impl DataclassKind {
+    pub fn is_pydantic(self) -> bool {
+        matches!(self, Self::PydanticModel | Self::PydanticRootModel)
+    }
+}
+
 #[derive(Clone, Debug, TypeEq, PartialEq, Eq)]
 pub struct DataclassMetadata {
     /// The dataclass fields, e.g., `{'x'}` for `@dataclass class C: x: int`.
+    /// For `attrs` classes, these are the candidate fields, which are filtered further once we know
+    /// how each field is initialized.
     pub fields: SmallSet<Name>,
     pub kws: BoolKeywords,
+    pub kind: DataclassKind,
 }
 
 impl DataclassMetadata {
@@ -379,10 +444,20 @@ impl DataclassMetadata {
             fields: self.fields.clone(),
             // The remaining metadata are irrelevant, so just set them to some sensible-seeming value.
             kws: self.kws.clone(),
+            kind: self.kind,
         }
     }
 }
 
+#[derive(Clone, Debug, TypeEq, PartialEq, Eq)]
+pub struct SlotsMetadata {
+    /// All slots available on instances, including those declared by ancestors.
+    pub slots: SmallSet<Name>,
+    /// Whether instances still get a `__dict__`, either because `__dict__` is one of the slots or
+    /// because some ancestor does not define `__slots__`. If so, any attribute may be assigned.
+    pub has_dict: bool,
+}
+
 #[derive(Clone, Debug, TypeEq, PartialEq, Eq)]
 pub struct ProtocolMetadata {
     /// All members of the protocol, excluding ones defined on `object` and not overridden in a subclass.
@@ -86,7 +86,7 @@ assert_words!(Binding, 9);
 assert_words!(BindingExpect, 8);
 assert_words!(BindingAnnotation, 13);
 assert_words!(BindingClass, 22);
-assert_words!(BindingClassMetadata, 8);
+assert_words!(BindingClassMetadata, 12);
 assert_words!(BindingClassField, 26);
 assert_bytes!(BindingClassSynthesizedFields, 4);
 assert_bytes!(BindingLegacyTypeParam, 4);
@@ -419,6 +419,9 @@ pub enum BindingExpect {
     Delete(Box<Expr>),
     /// Expression used in a boolean context (`bool()`, `if`, or `while`)
     Bool(Box<Expr>, TextRange),
+    /// Code that is unreachable if any of these bindings has type `Never`, e.g. the statements
+    /// after a call that never returns, or an `if` branch whose test narrows a name to `Never`.
+    Unreachable(Box<[Idx<Key>]>, TextRange),
 }
 
 impl DisplayWith<Bindings> for BindingExpect {
@@ -431,6 +434,14 @@ impl DisplayWith<Bindings> for BindingExpect {
             Self::Bool(x, ..) => {
                 write!(f, "Bool({})", m.display(x))
             }
+            Self::Unreachable(xs, range) => {
+                write!(
+                    f,
+                    "Unreachable({} if any of {})",
+                    m.display(range),
+                    commas_iter(|| xs.iter().map(|x| ctx.display(*x)))
+                )
+            }
             Self::Delete(x) => {
                 write!(f, "Delete({})", m.display(x))
             }
@@ -1534,6 +1545,9 @@ impl DisplayWith<Bindings> for BindingVariance {
 /// The `[Expr]` contains the base classes from the class header.
 /// The `[(Name, Expr)]` contains the class keywords from the class header.
 /// The `[Idx<Key>]` points to the class's decorators.
+/// The `slots` are the names declared in `__slots__`, if it is assigned a literal in the class body.
+/// The `model_config` holds the keywords of a `model_config = ConfigDict(...)` (or dict literal)
+/// assignment in the class body, which configures Pydantic models.
 #[derive(Clone, Debug)]
 pub struct BindingClassMetadata {
     pub class_idx: Idx<KeyClass>,
@@ -1542,6 +1556,8 @@ pub struct BindingClassMetadata {
     pub decorators: Box<[Idx<Key>]>,
     pub is_new_type: bool,
     pub special_base: Option<Box<BaseClass>>,
+    pub slots: Option<Box<[Name]>>,
+    pub model_config: Option<Box<[(Name, Expr)]>>,
 }
 
 impl DisplayWith<Bindings> for BindingClassMetadata {
@@ -38,12 +38,14 @@ use vec1::vec1;
 use crate::binding::binding::AnnotationTarget;
 use crate::binding::binding::Binding;
 use crate::binding::binding::BindingAnnotation;
+use crate::binding::binding::BindingExpect;
 use crate::binding::binding::BindingExport;
 use crate::binding::binding::BindingLegacyTypeParam;
 use crate::binding::binding::FirstUse;
 use crate::binding::binding::Key;
 use crate::binding::binding::KeyAnnotation;
 use crate::binding::binding::KeyClass;
+use crate::binding::binding::KeyExpect;
 use crate::binding::binding::KeyExport;
 use crate::binding::binding::KeyFunction;
 use crate::binding::binding::KeyLegacyTypeParam;
@@ -63,9 +65,12 @@ use crate::binding::scope::Scopes;
 use crate::binding::table::TableKeyed;
 use crate::common::symbol_kind::SymbolKind;
 use crate::config::base::UntypedDefBehavior;
+use crate::config::error::OptInErrors;
 use crate::error::collector::ErrorCollector;
 use crate::error::context::ErrorContext;
 use crate::error::kind::ErrorKind;
+use crate::export::definitions::DefinitionStyle;
+use crate::export::definitions::is_private_name;
 use crate::export::exports::Exports;
 use crate::export::exports::LookupExport;
 use crate::export::special::SpecialExport;
@@ -136,6 +141,7 @@ pub struct BindingsBuilder<'a> {
     pub scopes: Scopes,
     table: BindingTable,
     pub untyped_def_behavior: UntypedDefBehavior,
+    pub opt_in_errors: OptInErrors,
 }
 
 impl Bindings {
@@ -279,6 +285,7 @@ impl Bindings {
         uniques: &UniqueFactory,
         enable_trace: bool,
         untyped_def_behavior: UntypedDefBehavior,
+        opt_in_errors: OptInErrors,
     ) -> Self {
         let mut builder = BindingsBuilder {
             module_info: module_info.dupe(),
@@ -292,12 +299,14 @@ impl Bindings {
             scopes: Scopes::module(x.range, enable_trace),
             table: Default::default(),
             untyped_def_behavior,
+            opt_in_errors,
         };
         builder.init_static_scope(&x.body, true);
         if module_info.name() != ModuleName::builtins() {
             builder.inject_builtins();
         }
         builder.stmts(x.body);
+        builder.report_unused(Some(&exports));
         assert_eq!(builder.scopes.loop_depth(), 0);
         let scope_trace = builder.scopes.finish();
         let last_scope = scope_trace.toplevel_scope();
@@ -614,12 +623,123 @@ impl<'a> BindingsBuilder<'a> {
         current.flow.info.reserve(current.stat.0.capacity());
     }
 
+    /// Report the names defined in the current scope that are never read. At the top level of a
+    /// module (when `exports` is given), we only report imports that aren't re-exported and
+    /// private functions, since everything else may be used by other modules. In functions, we
+    /// report unused imports and variables.
+    pub fn report_unused(&self, exports: Option<&Exports>) {
+        if !self.reports_unused() {
+            return;
+        }
+        let scope = self.scopes.current();
+        let is_explicit_export =
+            |name: &Name| exports.is_some_and(|x| x.is_explicit_export(name, self.lookup));
+        for (name, info) in scope.stat.0.iter() {
+            if scope.used.contains(name) {
+                continue;
+            }
+            let (kind, msg) = match info.style {
+                // Names from `from m import *` are recorded as imports of this module.
+                DefinitionStyle::Import(m)
+                | DefinitionStyle::ImportAs(m)
+                | DefinitionStyle::ImportAsEq(m)
+                | DefinitionStyle::ImportModule(m)
+                    if m != ModuleName::future()
+                        && m != self.module_info.name()
+                        && !name.starts_with('_') =>
+                {
+                    // The imports in an `__init__.py` are usually there to re-export them.
+                    if exports.is_some()
+                        && (self.module_info.path().is_init() || is_explicit_export(name))
+                    {
+                        continue;
+                    }
+                    (
+                        ErrorKind::UnusedImport,
+                        format!("Import `{name}` is unused"),
+                    )
+                }
+                DefinitionStyle::Local(SymbolKind::Variable)
+                    if exports.is_none() && !name.starts_with('_') =>
+                {
+                    (
+                        ErrorKind::UnusedVariable,
+                        format!("Variable `{name}` is unused"),
+                    )
+                }
+                DefinitionStyle::Local(SymbolKind::Function)
+                    if exports.is_some() && is_private_name(name) && !is_explicit_export(name) =>
+                {
+                    (
+                        ErrorKind::UnusedFunction,
+                        format!("Private function `{name}` is never used"),
+                    )
+                }
+                _ => continue,
+            };
+            if self.opt_in_errors.is_enabled(kind) {
+                self.error(info.loc, kind, None, msg);
+            }
+        }
+    }
+
+    /// Whether any of the unused name checks are enabled, so we need to track which names are used.
+    pub fn reports_unused(&self) -> bool {
+        [
+            ErrorKind::UnusedImport,
+            ErrorKind::UnusedVariable,
+            ErrorKind::UnusedFunction,
+        ]
+        .into_iter()
+        .any(|kind| self.opt_in_errors.is_enabled(kind))
+    }
+
     pub fn stmts(&mut self, xs: Vec<Stmt>) {
-        for x in xs {
+        let Some(end) = xs.last().map(|x| x.range().end()) else {
+            return;
+        };
+        // If the block starts out unreachable, that was reported for the enclosing block. If we
+        // aren't reporting unreachable code, treat everything as already reported.
+        let mut unreachable = self.scopes.current().flow.has_terminated
+            || !self.opt_in_errors.is_enabled(ErrorKind::Unreachable);
+        let mut xs = xs.into_iter().peekable();
+        while let Some(x) = xs.next() {
+            let flow = &self.scopes.current().flow;
+            if !unreachable && flow.has_terminated && !flow.has_terminated_statically {
+                // Only report the first unreachable statement in each block, covering the rest.
+                unreachable = true;
+                self.error(
+                    TextRange::new(x.range().start(), end),
+                    ErrorKind::Unreachable,
+                    None,
+                    "This code is unreachable".to_owned(),
+                );
+            }
+            let call = match &x {
+                Stmt::Expr(x) if !unreachable && x.value.is_call_expr() => Some(x.value.range()),
+                _ => None,
+            };
             self.stmt(x);
+            // Whether a call never returns is only known once it is solved.
+            if let Some(call) = call
+                && let Some(next) = xs.peek()
+                && !self.scopes.current().flow.has_terminated
+            {
+                let idx = self.table.types.0.insert(Key::StmtExpr(call));
+                self.expect_reachable(Box::new([idx]), TextRange::new(next.range().start(), end));
+            }
         }
     }
 
+    /// Report the code in `range` as unreachable if any of `keys` turns out to be `Never`.
+    pub fn expect_reachable(&mut self, keys: Box<[Idx<Key>]>, range: TextRange) {
+        // Statements have their own expectations, so key this by the empty range at the start.
+        self.insert_binding(
+            KeyExpect(TextRange::empty(range.start())),
+            BindingExpect::Unreachable(keys, range),
+        );
+    }
+
     fn inject_builtins(&mut self) {
         let builtins_module = ModuleName::builtins();
         match self.lookup.get(builtins_module) {
@@ -784,13 +904,18 @@ impl<'a> BindingsBuilder<'a> {
         kind: LookupKind,
         usage: &mut Usage,
     ) -> Result<Idx<Key>, LookupError> {
-        self.lookup_name_inner(name, kind, usage)
+        let result = self
+            .lookup_name_inner(name, kind, usage)
             .map(|(result, first_use)| {
                 if let Some(used_idx) = first_use {
                     self.record_possible_first_use(used_idx, usage);
                 }
                 result
-            })
+            });
+        if result.is_ok() && self.reports_unused() {
+            self.scopes.mark_used(name);
+        }
+        result
     }
 
     /// Helper function, needed to work around the borrow checker given heavy use of mutable refs.
@@ -1122,7 +1247,13 @@ impl<'a> BindingsBuilder<'a> {
         }
     }
 
-    pub fn bind_narrow_ops(&mut self, narrow_ops: &NarrowOps, use_range: TextRange) {
+    /// Bind the narrowed names, and return their new keys.
+    pub fn bind_narrow_ops(
+        &mut self,
+        narrow_ops: &NarrowOps,
+        use_range: TextRange,
+    ) -> Vec<Idx<Key>> {
+        let mut keys = Vec::new();
         for (name, (op, op_range)) in narrow_ops.0.iter_hashed() {
             if let Ok(name_key) = self.lookup_name(name, LookupKind::Regular) {
                 let binding_key = self.insert_binding(
@@ -1130,8 +1261,10 @@ impl<'a> BindingsBuilder<'a> {
                     Binding::Narrow(name_key, Box::new(op.clone()), use_range),
                 );
                 self.scopes.update_flow_info(name, binding_key, None);
+                keys.push(binding_key);
             }
         }
+        keys
     }
 
     pub fn bind_lambda_param(&mut self, name: &Identifier) {
@@ -1242,6 +1375,8 @@ impl<'a> BindingsBuilder<'a> {
         if visible_branches.is_empty() {
             visible_branches = hidden_branches;
         }
+        let has_terminated_statically =
+            no_next && visible_branches.iter().any(|x| x.has_terminated_statically);
 
         // Collect all the information that we care about from all branches
         let mut names: SmallMap<Name, (Idx<Key>, Idx<Key>, SmallSet<Idx<Key>>, Vec<FlowStyle>)> =
@@ -1305,6 +1440,7 @@ impl<'a> BindingsBuilder<'a> {
         Flow {
             info: res,
             has_terminated: no_next,
+            has_terminated_statically,
         }
     }
 
@@ -11,12 +11,17 @@ use std::sync::LazyLock;
 use pyrefly_util::prelude::SliceExt;
 use regex::Regex;
 use ruff_python_ast::Expr;
+use ruff_python_ast::ExprCall;
 use ruff_python_ast::ExprDict;
 use ruff_python_ast::ExprList;
 use ruff_python_ast::ExprName;
+use ruff_python_ast::ExprSet;
 use ruff_python_ast::ExprTuple;
 use ruff_python_ast::Identifier;
 use ruff_python_ast::Keyword;
+use ruff_python_ast::Stmt;
+use ruff_python_ast::StmtAnnAssign;
+use ruff_python_ast::StmtAssign;
 use ruff_python_ast::StmtClassDef;
 use ruff_python_ast::name::Name;
 use ruff_text_size::Ranged;
@@ -52,6 +57,7 @@ use crate::binding::scope::InstanceAttribute;
 use crate::binding::scope::MethodThatSetsAttr;
 use crate::binding::scope::Scope;
 use crate::binding::scope::ScopeKind;
+use crate::dunder;
 use crate::error::kind::ErrorKind;
 use crate::graph::index::Idx;
 use crate::module::module_name::ModuleName;
@@ -174,6 +180,8 @@ impl<'a> BindingsBuilder<'a> {
             });
         }
 
+        let slots = Self::slots_of_class_body(&body);
+        let model_config = Self::model_config_of_class_body(&body);
         self.insert_binding_idx(
             class_indices.metadata_idx,
             BindingClassMetadata {
@@ -183,6 +191,10 @@ impl<'a> BindingsBuilder<'a> {
                 decorators: decorators.clone().into_boxed_slice(),
                 is_new_type: false,
                 special_base: None,
+                slots: slots
+                    .as_ref()
+                    .map(|slots| slots.map(|(name, _)| name.clone()).into_boxed_slice()),
+                model_config: model_config.map(Vec::into_boxed_slice),
             },
         );
         self.insert_binding_idx(
@@ -242,6 +254,20 @@ impl<'a> BindingsBuilder<'a> {
                 self.insert_binding(key_field, binding);
             }
         }
+        // Python raises a `ValueError` if a slot has the same name as a class variable.
+        for (name, range) in slots.iter().flatten() {
+            if let Some(info) = last_scope.flow.info.get(name)
+                && last_scope.stat.0.get(name).is_some()
+                && matches!(info.as_initial_value(), ClassFieldInitialValue::Class(_))
+            {
+                self.error(
+                    *range,
+                    ErrorKind::BadClassDefinition,
+                    None,
+                    format!("`{name}` in `__slots__` conflicts with class variable"),
+                );
+            }
+        }
         if let ScopeKind::Class(class_scope) = last_scope.kind {
             for (
                 name,
@@ -317,6 +343,82 @@ impl<'a> BindingsBuilder<'a> {
         );
     }
 
+    /// Find the names declared by the last assignment to `__slots__` in a class body. We only
+    /// understand a string literal, or a tuple, list, set, or dict literal of string literals; for
+    /// anything else we return `None` and treat the class as if it had no `__slots__`.
+    fn slots_of_class_body(body: &[Stmt]) -> Option<Vec<(Name, TextRange)>> {
+        let value = body.iter().rev().find_map(|stmt| match stmt {
+            Stmt::Assign(StmtAssign { targets, value, .. })
+                if matches!(targets.as_slice(), [Expr::Name(x)] if x.id == dunder::SLOTS) =>
+            {
+                Some(&**value)
+            }
+            Stmt::AnnAssign(StmtAnnAssign {
+                target,
+                value: Some(value),
+                ..
+            }) if matches!(&**target, Expr::Name(x) if x.id == dunder::SLOTS) => Some(&**value),
+            _ => None,
+        })?;
+        let items = match value {
+            Expr::StringLiteral(x) => return Some(vec![(Name::new(x.value.to_str()), x.range)]),
+            Expr::Tuple(ExprTuple { elts, .. })
+            | Expr::List(ExprList { elts, .. })
+            | Expr::Set(ExprSet { elts, .. }) => elts.iter().collect::<Vec<_>>(),
+            Expr::Dict(ExprDict { items, .. }) => items
+                .iter()
+                .map(|item| item.key.as_ref())
+                .collect::<Option<Vec<_>>>()?,
+            _ => return None,
+        };
+        items
+            .into_iter()
+            .map(|item| match item {
+                Expr::StringLiteral(x) => Some((Name::new(x.value.to_str()), x.range)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Find the options set by the last assignment to `model_config` in a class body, if it is
+    /// a call like `ConfigDict(frozen=True)` or a dict literal with string keys.
+    fn model_config_of_class_body(body: &[Stmt]) -> Option<Vec<(Name, Expr)>> {
+        let value = body.iter().rev().find_map(|stmt| match stmt {
+            Stmt::Assign(StmtAssign { targets, value, .. })
+                if matches!(targets.as_slice(), [Expr::Name(x)] if x.id == "model_config") =>
+            {
+                Some(&**value)
+            }
+            Stmt::AnnAssign(StmtAnnAssign {
+                target,
+                value: Some(value),
+                ..
+            }) if matches!(&**target, Expr::Name(x) if x.id == "model_config") => Some(&**value),
+            _ => None,
+        })?;
+        match value {
+            Expr::Call(ExprCall { arguments, .. }) => Some(
+                arguments
+                    .keywords
+                    .iter()
+                    .filter_map(|kw| Some((kw.arg.as_ref()?.id.clone(), kw.value.clone())))
+                    .collect(),
+            ),
+            Expr::Dict(ExprDict { items, .. }) => Some(
+                items
+                    .iter()
+                    .filter_map(|item| match &item.key {
+                        Some(Expr::StringLiteral(x)) => {
+                            Some((Name::new(x.value.to_str()), item.value.clone()))
+                        }
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
     fn extract_string_literals(
         &mut self,
         items: &[Expr],
@@ -410,6 +512,8 @@ impl<'a> BindingsBuilder<'a> {
                 decorators: Box::new([]),
                 is_new_type: class_kind == SynthesizedClassKind::NewType,
                 special_base,
+                slots: None,
+                model_config: None,
             },
         );
         self.insert_binding_idx(
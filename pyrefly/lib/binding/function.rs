@@ -46,8 +46,10 @@ use crate::binding::expr::Usage;
 use crate::binding::scope::FlowStyle;
 use crate::binding::scope::InstanceAttribute;
 use crate::binding::scope::Scope;
+use crate::binding::scope::ScopeKind;
 use crate::binding::scope::YieldsAndReturns;
 use crate::config::base::UntypedDefBehavior;
+use crate::error::kind::ErrorKind;
 use crate::export::special::SpecialExport;
 use crate::graph::index::Idx;
 use crate::module::short_identifier::ShortIdentifier;
@@ -277,6 +279,7 @@ impl<'a> BindingsBuilder<'a> {
         self.parameters(parameters, function_idx, class_key);
         self.init_static_scope(&body, false);
         self.stmts(body);
+        self.report_unused(None);
         self.scopes.pop_function_scope()
     }
 
@@ -397,6 +400,41 @@ impl<'a> BindingsBuilder<'a> {
         );
     }
 
+    /// Report a function without a return annotation, for strict mode. Public functions should
+    /// always be annotated, while for the others we only care if the return type becomes `Any`.
+    fn missing_return_annotation(
+        &self,
+        func_name: &Identifier,
+        is_public: bool,
+        decorators: &Decorators,
+    ) {
+        if is_public {
+            if self
+                .opt_in_errors
+                .is_enabled(ErrorKind::MissingReturnAnnotation)
+            {
+                self.error(
+                    func_name.range,
+                    ErrorKind::MissingReturnAnnotation,
+                    None,
+                    format!("Public function `{func_name}` is missing a return annotation"),
+                );
+            }
+        } else if self.opt_in_errors.is_enabled(ErrorKind::ImplicitAny)
+            && (decorators.has_no_type_check
+                || self.untyped_def_behavior != UntypedDefBehavior::CheckAndInferReturnType)
+        {
+            self.error(
+                func_name.range,
+                ErrorKind::ImplicitAny,
+                None,
+                format!(
+                    "Return type of `{func_name}` is implicitly `Any` because it is unannotated"
+                ),
+            );
+        }
+    }
+
     fn decorators(&mut self, decorator_list: Vec<Decorator>, usage: &mut Usage) -> Decorators {
         let has_no_type_check = decorator_list
             .iter()
@@ -503,12 +541,20 @@ impl<'a> BindingsBuilder<'a> {
             Some((class_key, metadata_key)) => (Some(class_key), Some(metadata_key)),
             _ => (None, None),
         };
+        let is_public = !func_name.id.starts_with('_')
+            && matches!(
+                self.scopes.current().kind,
+                ScopeKind::Module | ScopeKind::Class(_)
+            );
 
         self.scopes.push(Scope::annotation(x.range));
         let (return_ann_with_range, legacy_tparams) =
             self.function_header(&mut x, &func_name, class_key, def_user.usage());
 
         let decorators = self.decorators(mem::take(&mut x.decorator_list), def_user.usage());
+        if return_ann_with_range.is_none() {
+            self.missing_return_annotation(&func_name, is_public, &decorators);
+        }
 
         let (stub_or_impl, self_assignments) = self.function_body(
             &mut x.parameters,
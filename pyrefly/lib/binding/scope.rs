@@ -180,6 +180,10 @@ pub struct Flow {
     // We continue to analyze the rest of the code after a flow terminates, but
     // we don't include terminated flows when merging after loops and branches.
     pub has_terminated: bool,
+    // Did control flow terminate in a branch that was picked based on the Python version or
+    // platform (or `TYPE_CHECKING`)? Then the code after it is only unreachable under this
+    // configuration, so we don't report it as unreachable.
+    pub has_terminated_statically: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -460,6 +464,8 @@ pub struct Scope {
     pub kind: ScopeKind,
     /// Stack of for/while loops we're in. Does not include comprehensions.
     pub loops: Vec<Loop>,
+    /// Names defined in this scope that have been read, either here or from a nested scope.
+    pub used: SmallSet<Name>,
 }
 
 impl Scope {
@@ -471,6 +477,7 @@ impl Scope {
             barrier,
             kind,
             loops: Default::default(),
+            used: Default::default(),
         }
     }
 
@@ -688,6 +695,34 @@ impl Scopes {
         self.scopes.iter_mut().map(|node| &mut node.scope).rev()
     }
 
+    /// Record that a name has been read, against the scope that statically defines it.
+    /// Like name lookup, class scopes are skipped unless they are the current scope.
+    pub fn mark_used(&mut self, name: Hashed<&Name>) {
+        for (idx, scope) in self.iter_rev_mut().enumerate() {
+            if (idx == 0 || !matches!(scope.kind, ScopeKind::Class(_)))
+                && scope.stat.0.contains_key_hashed(name)
+            {
+                scope.used.insert_hashed(name.cloned());
+                return;
+            }
+        }
+    }
+
+    /// Record that a name declared `global` or `nonlocal` has been used, both in the current scope
+    /// and in the enclosing scope that defines it, since assignments in one are visible in the other.
+    pub fn mark_used_captured(&mut self, name: Hashed<&Name>) {
+        for (idx, scope) in self.iter_rev_mut().enumerate() {
+            if (idx == 0 || !matches!(scope.kind, ScopeKind::Class(_)))
+                && scope.stat.0.contains_key_hashed(name)
+            {
+                scope.used.insert_hashed(name.cloned());
+                if idx > 0 {
+                    return;
+                }
+            }
+        }
+    }
+
     /// In methods, we track assignments to `self` attribute targets so that we can
     /// be aware of class fields implicitly defined in methods.
     ///
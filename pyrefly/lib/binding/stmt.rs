@@ -13,6 +13,7 @@ use ruff_python_ast::Stmt;
 use ruff_python_ast::StmtAssign;
 use ruff_python_ast::StmtImportFrom;
 use ruff_python_ast::StmtReturn;
+use ruff_python_ast::name::Name;
 use ruff_text_size::Ranged;
 use ruff_text_size::TextRange;
 use starlark_map::Hashed;
@@ -37,6 +38,7 @@ use crate::binding::narrow::NarrowOps;
 use crate::binding::scope::FlowStyle;
 use crate::binding::scope::LoopExit;
 use crate::error::kind::ErrorKind;
+use crate::export::exports::Exports;
 use crate::export::special::SpecialExport;
 use crate::graph::index::Idx;
 use crate::module::module_name::ModuleName;
@@ -47,6 +49,29 @@ use crate::types::alias::resolve_typeshed_alias;
 use crate::types::special_form::SpecialForm;
 use crate::types::types::Type;
 
+/// Whether a branch starts with a `raise` or an `assert_never()` call, as in an exhaustiveness
+/// check, in which case we expect it to be unreachable when its narrowed names are `Never`.
+fn is_exhaustiveness_check(body: &[Stmt]) -> bool {
+    match body.first() {
+        Some(Stmt::Raise(_)) => true,
+        Some(Stmt::Expr(x)) => match &*x.value {
+            Expr::Call(call) => match &*call.func {
+                Expr::Name(x) => x.id == "assert_never",
+                Expr::Attribute(x) => match &*x.value {
+                    Expr::Name(m) => {
+                        x.attr.id == "assert_never"
+                            && matches!(m.id.as_str(), "typing" | "typing_extensions")
+                    }
+                    _ => false,
+                },
+                _ => false,
+            },
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
 impl<'a> BindingsBuilder<'a> {
     fn bind_unimportable_names(&mut self, x: &StmtImportFrom) {
         for x in &x.names {
@@ -58,6 +83,45 @@ impl<'a> BindingsBuilder<'a> {
         }
     }
 
+    /// Report an import of a package without a `py.typed` file or stubs, for strict mode.
+    fn check_untyped_import(&self, m: ModuleName, range: TextRange) {
+        if self.opt_in_errors.is_enabled(ErrorKind::UntypedImport) && self.lookup.is_untyped(m) {
+            self.error(
+                range,
+                ErrorKind::UntypedImport,
+                None,
+                format!(
+                    "Imported module `{m}` is from a package without a `py.typed` file or stubs"
+                ),
+            );
+        }
+    }
+
+    /// Report an import of a name that module `m` imports, but doesn't explicitly re-export, when
+    /// `m` is a stub (or a `py.typed` package, if configured). Submodules can always be imported.
+    fn check_implicit_reexport(
+        &self,
+        m: ModuleName,
+        exports: &Exports,
+        name: &Name,
+        range: TextRange,
+    ) {
+        if exports.is_implicit_reexport(name, self.lookup)
+            && self.lookup.requires_explicit_reexport(
+                m,
+                self.module_info.inline_config().config().explicit_reexport,
+            )
+            && self.lookup.get(m.append(name)).is_err()
+        {
+            self.error(
+                range,
+                ErrorKind::ImplicitReexport,
+                None,
+                format!("Module `{m}` does not explicitly re-export `{name}`"),
+            );
+        }
+    }
+
     /// Bind a special assignment where we do not want the usage tracking or placeholder var pinning
     /// used for normal assignments.
     ///
@@ -159,6 +223,9 @@ impl<'a> BindingsBuilder<'a> {
     }
 
     fn define_nonlocal_name(&mut self, name: &Identifier) {
+        if self.reports_unused() {
+            self.scopes.mark_used_captured(Hashed::new(&name.id));
+        }
         let key = Key::Definition(ShortIdentifier::new(name));
         let binding =
             match self.lookup_mutable_captured_name(&name.id, MutableCaptureLookupKind::Nonlocal) {
@@ -177,6 +244,9 @@ impl<'a> BindingsBuilder<'a> {
     }
 
     fn define_global_name(&mut self, name: &Identifier) {
+        if self.reports_unused() {
+            self.scopes.mark_used_captured(Hashed::new(&name.id));
+        }
         let key = Key::Definition(ShortIdentifier::new(name));
         let binding =
             match self.lookup_mutable_captured_name(&name.id, MutableCaptureLookupKind::Global) {
@@ -589,6 +659,12 @@ impl<'a> BindingsBuilder<'a> {
                 self.bind_target_with_expr(&mut x.target, &mut x.iter, &|expr, ann| {
                     Binding::IterableValue(ann, expr.clone(), IsAsync::new(x.is_async))
                 });
+                if self.reports_unused() {
+                    // Loop targets are often unused, as in `for i in range(n)`, so don't report them.
+                    Ast::expr_lvalue(&x.target, &mut |name| {
+                        self.scopes.mark_used(Hashed::new(&name.id))
+                    });
+                }
                 // Note that we set up the loop *after* the header is fully bound, because the
                 // loop iterator is only evaluated once before the loop begins.
                 self.setup_loop(x.range, &NarrowOps::new());
@@ -626,12 +702,14 @@ impl<'a> BindingsBuilder<'a> {
                 // is carried over to the else branch.
                 let mut negated_prev_ops = NarrowOps::new();
                 let mut implicit_else = true;
+                let mut is_static = false;
                 for (range, mut test, body) in Ast::if_branches_owned(x) {
                     let this_branch_chosen = self.sys_info.evaluate_bool_opt(test.as_ref());
+                    is_static |= test.is_some() && this_branch_chosen.is_some();
                     if this_branch_chosen == Some(false) {
                         continue; // We definitely won't pick this branch
                     }
-                    self.bind_narrow_ops(&negated_prev_ops, range);
+                    let mut narrowed = self.bind_narrow_ops(&negated_prev_ops, range);
                     let mut base = self.scopes.clone_current_flow();
                     self.ensure_expr_opt(test.as_mut(), &mut Usage::Narrowing);
                     let new_narrow_ops = NarrowOps::from_expr(self, test.as_ref());
@@ -648,7 +726,17 @@ impl<'a> BindingsBuilder<'a> {
                     } else {
                         implicit_else = false;
                     }
-                    self.bind_narrow_ops(&new_narrow_ops, range);
+                    narrowed.extend(self.bind_narrow_ops(&new_narrow_ops, range));
+                    if !narrowed.is_empty()
+                        && self.opt_in_errors.is_enabled(ErrorKind::Unreachable)
+                        && !is_exhaustiveness_check(&body)
+                        && let (Some(first), Some(last)) = (body.first(), body.last())
+                    {
+                        self.expect_reachable(
+                            narrowed.into_boxed_slice(),
+                            TextRange::new(first.range().start(), last.range().end()),
+                        );
+                    }
                     negated_prev_ops.and_all(new_narrow_ops.negate());
                     self.stmts(body);
                     self.scopes.swap_current_flow_with(&mut base);
@@ -675,6 +763,9 @@ impl<'a> BindingsBuilder<'a> {
                     }
                     self.merge_branches_into_current(branches, range);
                 }
+                if is_static && self.scopes.current().flow.has_terminated {
+                    self.scopes.current_mut().flow.has_terminated_statically = true;
+                }
             }
             Stmt::With(x) => {
                 let kind = IsAsync::new(x.is_async);
@@ -782,9 +873,18 @@ impl<'a> BindingsBuilder<'a> {
             Stmt::Import(x) => {
                 for x in x.names {
                     let m = ModuleName::from_name(&x.name.id);
-                    if let Err(err @ FindError::NotFound(..)) = self.lookup.get(m) {
-                        let (ctx, msg) = err.display();
-                        self.error_multiline(x.range, ErrorKind::ImportError, ctx.as_deref(), msg);
+                    match self.lookup.get(m) {
+                        Ok(_) => self.check_untyped_import(m, x.range),
+                        Err(err @ FindError::NotFound(..)) => {
+                            let (ctx, msg) = err.display();
+                            self.error_multiline(
+                                x.range,
+                                ErrorKind::ImportError,
+                                ctx.as_deref(),
+                                msg,
+                            );
+                        }
+                        Err(_) => {}
                     }
                     match x.asname {
                         Some(asname) => {
@@ -822,6 +922,7 @@ impl<'a> BindingsBuilder<'a> {
                 ) {
                     match self.lookup.get(m) {
                         Ok(module_exports) => {
+                            self.check_untyped_import(m, x.range);
                             let exported = module_exports.exports(self.lookup);
                             for x in x.names {
                                 if &x.name == "*" {
@@ -858,6 +959,12 @@ impl<'a> BindingsBuilder<'a> {
                                     let val = if (self.module_info.name() != m)
                                         && exported.contains_key(&x.name.id)
                                     {
+                                        self.check_implicit_reexport(
+                                            m,
+                                            &module_exports,
+                                            &x.name.id,
+                                            x.range,
+                                        );
                                         Binding::Import(m, x.name.id.clone())
                                     } else {
                                         let x_as_module_name = m.append(&x.name.id);
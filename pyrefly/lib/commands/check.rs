@@ -6,11 +6,16 @@
  */
 
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Display;
 use std::fs::File;
 use std::io::BufWriter;
 use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
 use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -31,30 +36,46 @@ use pyrefly_util::events::CategorizedEvents;
 use pyrefly_util::forgetter::Forgetter;
 use pyrefly_util::fs_anyhow;
 use pyrefly_util::globs::FilteredGlobs;
+use pyrefly_util::memory::Bytes;
 use pyrefly_util::memory::MemoryUsageTrace;
 use pyrefly_util::prelude::SliceExt;
 use pyrefly_util::watcher::Watcher;
-use ruff_source_file::OneIndexed;
+use serde::Deserialize;
+use serde::Serialize;
 use starlark_map::small_map::SmallMap;
 use starlark_map::small_set::SmallSet;
 use tracing::debug;
 
+#[cfg(unix)]
+use crate::commands::daemon;
+#[cfg(unix)]
+use crate::commands::daemon::DaemonRequest;
+#[cfg(unix)]
+use crate::commands::daemon::DaemonResponse;
 use crate::commands::run::CommandExitStatus;
+use crate::commands::shard::Shard;
+use crate::commands::shard::ShardReport;
 use crate::commands::suppress;
+use crate::commands::util::GitChanges;
+use crate::commands::util::git_changed_files;
 use crate::commands::util::module_from_path;
 use crate::config::base::UntypedDefBehavior;
 use crate::config::config::ConfigFile;
 use crate::config::config::validate_path;
 use crate::config::environment::environment::SitePackagePathSource;
+use crate::config::error::ErrorConfig;
+use crate::config::error::ErrorDisplayConfig;
 use crate::config::finder::ConfigError;
 use crate::config::finder::ConfigFinder;
+use crate::error::collector::CollectedErrors;
+use crate::error::collector::ErrorCollector;
 use crate::error::error::Error;
 use crate::error::error::print_error_counts;
 use crate::error::kind::Severity;
 use crate::error::legacy::LegacyErrors;
+use crate::error::style::ErrorStyle;
 use crate::error::summarise::print_error_summary;
 use crate::module::bundled::stdlib_search_path;
-use crate::module::ignore::SuppressionKind;
 use crate::module::module_name::ModuleName;
 use crate::module::module_path::ModulePath;
 use crate::module::module_path::ModulePathDetails;
@@ -64,13 +85,30 @@ use crate::state::handle::Handle;
 use crate::state::require::Require;
 use crate::state::state::State;
 use crate::state::state::Transaction;
+use crate::state::steps::Step;
 use crate::state::subscriber::ProgressBarSubscriber;
+use crate::state::subscriber::Subscriber;
+use crate::state::subscriber::TelemetrySubscriber;
+use crate::state::trace::ChromeTrace;
 use crate::sys_info::PythonPlatform;
 use crate::sys_info::PythonVersion;
 use crate::sys_info::SysInfo;
 
-#[derive(Debug, Clone, ValueEnum, Default)]
-enum OutputFormat {
+/// How often `--progress` prints what the checker is working on.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    ValueEnum,
+    Default,
+    Serialize,
+    Deserialize
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputFormat {
     /// Minimal text output, one line per error
     MinText,
     #[default]
@@ -99,6 +137,10 @@ struct OutputArgs {
     /// Set the error output format.
     #[arg(long, value_enum, default_value_t, env = clap_env("OUTPUT_FORMAT"))]
     output_format: OutputFormat,
+    /// With `--output-format=json`, also include the errors suppressed by ignore comments,
+    /// marked with `suppressed: true`.
+    #[arg(long, env = clap_env("INCLUDE_SUPPRESSED"))]
+    include_suppressed: bool,
     /// Produce debugging information about the type checking process.
     #[arg(long, env = clap_env("DEBUG_INFO"), value_name = "OUTPUT_FILE")]
     debug_info: Option<PathBuf>,
@@ -111,6 +153,10 @@ struct OutputArgs {
     /// Process each module individually to figure out how long each step takes.
     #[arg(long, env = clap_env("REPORT_TIMINGS"), value_name = "OUTPUT_FILE")]
     report_timings: Option<PathBuf>,
+    /// Record how long each step of each module takes during the check, and when modules are blocked
+    /// waiting on each other, as a Chrome trace file (viewable in Perfetto).
+    #[arg(long, env = clap_env("REPORT_CHROME_TRACE"), value_name = "OUTPUT_FILE")]
+    report_chrome_trace: Option<PathBuf>,
     /// Generate a Glean-compatible JSON file for each module
     #[arg(long, env = clap_env("REPORT_GLEAN"), value_name = "OUTPUT_FILE")]
     report_glean: Option<PathBuf>,
@@ -139,6 +185,11 @@ struct OutputArgs {
     /// Omit the summary in the last line of the output.
     #[arg(long, env = clap_env("NO_SUMMARY"))]
     no_summary: bool,
+    /// Print what the checker is working on to stderr as plain text lines, instead of drawing a
+    /// progress bar, followed by a summary of step timings, cache hits, and how many modules keep
+    /// their AST and answers at each `Require` level.
+    #[arg(long, env = clap_env("PROGRESS"))]
+    progress: bool,
 }
 
 /// non-config type checker behavior
@@ -156,21 +207,53 @@ struct BehaviorArgs {
     /// Remove unused ignores from the input files.
     #[arg(long, env = clap_env("REMOVE_UNUSED_IGNORES"))]
     remove_unused_ignores: bool,
+    /// Only check the Python files that git reports as changed since this revision, together with
+    /// the files that transitively import them, and only report errors in those files.
+    #[arg(
+        long,
+        env = clap_env("CHANGED_SINCE"),
+        value_name = "REV",
+        conflicts_with = "check_all"
+    )]
+    changed_since: Option<String>,
+    /// Once the checker uses more than this much memory (e.g. `16GB`), drop the ASTs and bindings
+    /// of modules that are solved and only keep their exports and solutions.
+    /// The reports that need everything retained, other than `--report-binding-memory`, can't be used.
+    #[arg(
+        long,
+        env = clap_env("MEMORY_BUDGET"),
+        value_name = "SIZE",
+        conflicts_with_all = ["debug_info", "report_trace", "report_glean"]
+    )]
+    memory_budget: Option<Bytes>,
+    /// Split the files to check into N partitions, and only check partition i (1-based).
+    /// Dependencies in other partitions are only loaded as far as needed to check this one.
+    /// The errors are written as JSON that `pyrefly merge-shards` combines into a single report,
+    /// so `--output-format` can't be used.
+    #[arg(
+        long,
+        env = clap_env("SHARD"),
+        value_name = "i/N",
+        conflicts_with_all = ["check_all", "output_format"]
+    )]
+    shard: Option<Shard>,
 }
 
 /// config overrides
 #[derive(Debug, Parser, Clone)]
-struct ConfigOverrideArgs {
+pub struct ConfigOverrideArgs {
     /// The list of directories where imports are imported from, including
     /// type checked files.
     #[arg(long, env = clap_env("SEARCH_PATH"))]
     search_path: Option<Vec<PathBuf>>,
     /// The Python version any `sys.version` checks should evaluate against.
-    #[arg(long, env = clap_env("PYTHON_VERSION"))]
-    python_version: Option<PythonVersion>,
+    /// Give several, separated by commas, to check against each of them.
+    #[arg(long, env = clap_env("PYTHON_VERSION"), value_delimiter = ',')]
+    python_version: Option<Vec<PythonVersion>>,
     /// The platform any `sys.platform` checks should evaluate against.
-    #[arg(long, env = clap_env("PLATFORM"))]
-    python_platform: Option<PythonPlatform>,
+    /// Give several, separated by commas, to check against each of them.
+    #[arg(long, env = clap_env("PLATFORM"), value_delimiter = ',')]
+    python_platform: Option<Vec<PythonPlatform>>,
     /// Directories containing third-party package imports, searched
     /// after first checking `search_path` and `typeshed`.
     #[arg(long, env = clap_env("SITE_PACKAGE_PATH"))]
@@ -179,6 +262,10 @@ struct ConfigOverrideArgs {
     /// `python_platform`, or `site_package_path` if any of the values are missing.
     #[arg(long, env = clap_env("PYTHON_INTERPRETER"), value_name = "EXE_PATH")]
     python_interpreter: Option<PathBuf>,
+    /// The name of a conda environment whose interpreter will be queried for `python_version`,
+    /// `python_platform`, or `site_package_path` if any of the values are missing.
+    #[arg(long, env = clap_env("CONDA_ENVIRONMENT"), value_name = "NAME")]
+    conda_environment: Option<String>,
     /// Whether to search imports in `site-package-path` that do not have a `py.typed` file unconditionally.
     #[arg(long, env = clap_env("USE_UNTYPED_IMPORTS"))]
     use_untyped_imports: Option<bool>,
@@ -194,6 +281,100 @@ struct ConfigOverrideArgs {
     /// Controls how Pyrefly analyzes function definitions that lack type annotations on parameters and return values.
     #[arg(long, env = clap_env("UNTYPED_DEF_BEHAVIOR"))]
     untyped_def_behavior: Option<UntypedDefBehavior>,
+    /// How long, in milliseconds, solving a single module may take before the rest of it is treated as `Any`.
+    #[arg(long, env = clap_env("SOLVE_BUDGET_MS"), value_name = "MILLISECONDS")]
+    solve_budget_ms: Option<u64>,
+    /// Whether to turn on the checks that are off by default, such as reporting implicit `Any`s.
+    #[arg(long, env = clap_env("STRICT"))]
+    strict: Option<bool>,
+    /// Whether names imported into a module are only re-exported when marked explicitly. When unset,
+    /// this is only enforced for stubs; `true` also enforces it for packages with a `py.typed` file.
+    #[arg(long, env = clap_env("EXPLICIT_REEXPORT"))]
+    explicit_reexport: Option<bool>,
+}
+
+impl ConfigOverrideArgs {
+    pub fn search_path(&self) -> &[PathBuf] {
+        self.search_path.as_deref().unwrap_or_default()
+    }
+
+    pub fn absolute_search_path(&mut self) {
+        if let Some(paths) = self.search_path.as_mut() {
+            for x in paths.iter_mut() {
+                if let Ok(v) = x.absolutize() {
+                    *x = v.into_owned();
+                }
+            }
+        }
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        fn validate_arg(arg_name: &str, paths: Option<&[PathBuf]>) -> anyhow::Result<()> {
+            if let Some(paths) = paths {
+                for path in paths {
+                    validate_path(path).with_context(|| format!("Invalid {}", arg_name))?;
+                }
+            }
+            Ok(())
+        }
+        validate_arg("--site-package-path", self.site_package_path.as_deref())?;
+        validate_arg("--search-path", self.search_path.as_deref())?;
+        Ok(())
+    }
+
+    pub fn override_config(&self, mut config: ConfigFile) -> (ArcId<ConfigFile>, Vec<ConfigError>) {
+        if let Some(x) = &self.python_platform {
+            config.python_environment.set_python_platforms(x.clone());
+        }
+        if let Some(x) = &self.python_version {
+            config.python_environment.set_python_versions(x.clone());
+        }
+        if let Some(x) = &self.search_path {
+            config.search_path_from_args = x.clone();
+        }
+        if let Some(x) = &self.site_package_path {
+            config.python_environment.site_package_path = Some(x.clone());
+            config.python_environment.site_package_path_source = SitePackagePathSource::CommandLine;
+        }
+        if let Some(x) = &self.python_interpreter {
+            config.python_interpreter = Some(x.clone());
+        }
+        if let Some(x) = &self.conda_environment {
+            config.conda_environment = Some(x.clone());
+        }
+        if let Some(x) = &self.use_untyped_imports {
+            config.use_untyped_imports = *x;
+        }
+        if let Some(x) = &self.ignore_missing_source {
+            config.ignore_missing_source = *x;
+        }
+        if let Some(x) = &self.untyped_def_behavior {
+            config.root.untyped_def_behavior = Some(*x);
+        }
+        if let Some(x) = self.solve_budget_ms {
+            config.root.solve_budget_ms = Some(x);
+        }
+        if let Some(x) = self.strict {
+            config.root.strict = Some(x);
+        }
+        if let Some(x) = self.explicit_reexport {
+            config.root.explicit_reexport = Some(x);
+        }
+        if let Some(wildcards) = &self.replace_imports_with_any {
+            config.root.replace_imports_with_any = Some(
+                wildcards
+                    .iter()
+                    .filter_map(|x| ModuleWildcard::new(x).ok())
+                    .collect(),
+            );
+        }
+        if let Some(x) = &self.ignore_errors_in_generated_code {
+            config.root.ignore_errors_in_generated_code = Some(*x);
+        }
+        config.configure();
+        let errors = config.validate();
+        (ArcId::new(config), errors)
+    }
 }
 
 impl OutputFormat {
@@ -217,45 +398,92 @@ impl OutputFormat {
         Ok(())
     }
 
-    fn write_error_json(writer: &mut impl Write, errors: &[Error]) -> anyhow::Result<()> {
-        let legacy_errors = LegacyErrors::from_errors(errors);
+    /// JSON output can also include the errors suppressed by ignore comments, marked as such.
+    fn write_error_json(
+        writer: &mut impl Write,
+        errors: &CollectedErrors,
+        include_suppressed: bool,
+    ) -> anyhow::Result<()> {
+        let legacy_errors = LegacyErrors::from_collected(errors, include_suppressed);
         serde_json::to_writer_pretty(writer, &legacy_errors)?;
         Ok(())
     }
 
-    fn buffered_write_error_json(writer: impl Write, errors: &[Error]) -> anyhow::Result<()> {
+    fn buffered_write_error_json(
+        writer: impl Write,
+        errors: &CollectedErrors,
+        include_suppressed: bool,
+    ) -> anyhow::Result<()> {
         let mut writer = BufWriter::new(writer);
-        Self::write_error_json(&mut writer, errors)?;
+        Self::write_error_json(&mut writer, errors, include_suppressed)?;
         writer.flush()?;
         Ok(())
     }
 
-    fn write_error_json_to_file(path: &Path, errors: &[Error]) -> anyhow::Result<()> {
-        fn f(path: &Path, errors: &[Error]) -> anyhow::Result<()> {
+    fn write_error_json_to_file(
+        path: &Path,
+        errors: &CollectedErrors,
+        include_suppressed: bool,
+    ) -> anyhow::Result<()> {
+        fn f(
+            path: &Path,
+            errors: &CollectedErrors,
+            include_suppressed: bool,
+        ) -> anyhow::Result<()> {
             let file = File::create(path)?;
-            OutputFormat::buffered_write_error_json(file, errors)
+            OutputFormat::buffered_write_error_json(file, errors, include_suppressed)
         }
-        f(path, errors)
+        f(path, errors, include_suppressed)
             .with_context(|| format!("while writing JSON errors to `{}`", path.display()))
     }
 
-    fn write_error_json_to_console(errors: &[Error]) -> anyhow::Result<()> {
-        Self::buffered_write_error_json(stdout(), errors)
+    fn write_error_json_to_console(
+        errors: &CollectedErrors,
+        include_suppressed: bool,
+    ) -> anyhow::Result<()> {
+        Self::buffered_write_error_json(stdout(), errors, include_suppressed)
+    }
+
+    fn write_errors_to_file(
+        &self,
+        path: &Path,
+        errors: &CollectedErrors,
+        include_suppressed: bool,
+    ) -> anyhow::Result<()> {
+        match self {
+            Self::MinText => Self::write_error_text_to_file(path, &errors.shown, false),
+            Self::FullText => Self::write_error_text_to_file(path, &errors.shown, true),
+            Self::Json => Self::write_error_json_to_file(path, errors, include_suppressed),
+        }
     }
 
-    fn write_errors_to_file(&self, path: &Path, errors: &[Error]) -> anyhow::Result<()> {
+    fn write_errors(
+        &self,
+        writer: &mut impl Write,
+        errors: &CollectedErrors,
+        include_suppressed: bool,
+    ) -> anyhow::Result<()> {
         match self {
-            Self::MinText => Self::write_error_text_to_file(path, errors, false),
-            Self::FullText => Self::write_error_text_to_file(path, errors, true),
-            Self::Json => Self::write_error_json_to_file(path, errors),
+            Self::MinText | Self::FullText => {
+                let verbose = matches!(self, Self::FullText);
+                for e in &errors.shown {
+                    e.write_line(&mut *writer, verbose)?;
+                }
+                Ok(())
+            }
+            Self::Json => Self::write_error_json(writer, errors, include_suppressed),
         }
     }
 
-    fn write_errors_to_console(&self, errors: &[Error]) -> anyhow::Result<()> {
+    fn write_errors_to_console(
+        &self,
+        errors: &CollectedErrors,
+        include_suppressed: bool,
+    ) -> anyhow::Result<()> {
         match self {
-            Self::MinText => Self::write_error_text_to_console(errors, false),
-            Self::FullText => Self::write_error_text_to_console(errors, true),
-            Self::Json => Self::write_error_json_to_console(errors),
+            Self::MinText => Self::write_error_text_to_console(&errors.shown, false),
+            Self::FullText => Self::write_error_text_to_console(&errors.shown, true),
+            Self::Json => Self::write_error_json_to_console(errors, include_suppressed),
         }
     }
 }
@@ -263,8 +491,9 @@ impl OutputFormat {
 /// A data structure to facilitate the creation of handles for all the files we want to check.
 pub struct Handles {
     /// A mapping from a file to all other information needed to create a `Handle`.
-    /// The value type is basically everything else in `Handle` except for the file path.
-    path_data: HashMap<PathBuf, (ModuleName, SysInfo)>,
+    /// The value type is basically everything else in `Handle` except for the file path,
+    /// with a `SysInfo` for each Python version and platform the file is checked against.
+    path_data: HashMap<PathBuf, (ModuleName, Vec<SysInfo>)>,
 }
 
 impl Handles {
@@ -287,35 +516,98 @@ impl Handles {
         path: PathBuf,
         args_search_path: &[PathBuf],
         config_finder: &ConfigFinder,
-    ) -> &(ModuleName, SysInfo) {
+    ) -> &(ModuleName, Vec<SysInfo>) {
         let module_path = ModulePath::filesystem(path.clone());
-        let unknown = ModuleName::unknown();
-        let config = config_finder.python_file(unknown, &module_path);
-
-        let search_path = args_search_path.iter().chain(config.search_path());
-        let module_name = module_from_path(&path, search_path).unwrap_or(unknown);
+        let config = config_finder.python_file(ModuleName::unknown(), &module_path);
+        let module_name = Self::module_name(&path, args_search_path, &config);
 
         self.path_data
             .entry(path)
-            .or_insert((module_name, config.get_sys_info()))
+            .or_insert((module_name, config.get_sys_info_matrix()))
+    }
+
+    fn module_name(path: &Path, args_search_path: &[PathBuf], config: &ConfigFile) -> ModuleName {
+        let search_path = args_search_path.iter().chain(config.search_path());
+        module_from_path(path, search_path).unwrap_or_else(ModuleName::unknown)
+    }
+
+    /// The handles among `handles` for files that changed, or that transitively import one that
+    /// did, according to the imports `transaction` found when binding them. A file that was
+    /// removed can't be imported anymore, so we look for the files that failed to import it.
+    /// Paths are compared after resolving symlinks, since git reports them under the real
+    /// repository root.
+    fn affected_by(
+        &self,
+        changes: &GitChanges,
+        handles: &[(Handle, Require)],
+        transaction: &Transaction,
+        args_search_path: &[PathBuf],
+        config_finder: &ConfigFinder,
+    ) -> HashSet<Handle> {
+        let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let changed = changes
+            .changed
+            .iter()
+            .map(|path| canonical(path))
+            .collect::<HashSet<_>>();
+        let removed = changes
+            .removed
+            .iter()
+            .map(|path| {
+                let config = config_finder
+                    .python_file(ModuleName::unknown(), &ModulePath::filesystem(path.clone()));
+                Self::module_name(path, args_search_path, &config)
+            })
+            .filter(|module| *module != ModuleName::unknown())
+            .collect::<HashSet<_>>();
+        // A changed file might not be one we were asked to check, but still be imported by one.
+        let mut graph = HashSet::new();
+        let mut affected = HashSet::new();
+        for (handle, _) in handles {
+            graph.insert(handle.dupe());
+            graph.extend(transaction.get_imports(handle));
+            if transaction
+                .get_missing_imports(handle)
+                .iter()
+                .any(|module| removed.contains(module))
+            {
+                affected.insert(handle.dupe());
+            }
+        }
+        for handle in graph {
+            if changed.contains(&canonical(handle.path().as_path())) {
+                affected.extend(transaction.get_transitive_rdeps(handle));
+            }
+        }
+        affected
     }
 
     pub fn all(&self, specified_require: Require) -> Vec<(Handle, Require)> {
         self.path_data
             .iter()
-            .map(|(path, (module_name, runtime_metadata))| {
-                (
-                    Handle::new(
-                        module_name.dupe(),
-                        ModulePath::filesystem(path.to_path_buf()),
-                        runtime_metadata.dupe(),
-                    ),
-                    specified_require,
-                )
+            .flat_map(|(path, (module_name, sys_infos))| {
+                sys_infos.iter().map(move |sys_info| {
+                    (
+                        Handle::new(
+                            module_name.dupe(),
+                            ModulePath::filesystem(path.to_path_buf()),
+                            sys_info.dupe(),
+                        ),
+                        specified_require,
+                    )
+                })
             })
             .collect()
     }
 
+    fn contains(&self, path: &Path) -> bool {
+        self.path_data.contains_key(path)
+    }
+
+    fn paths(&self) -> Vec<PathBuf> {
+        self.path_data.keys().cloned().collect()
+    }
+
     fn update<'a>(
         &mut self,
         created_files: impl Iterator<Item = &'a PathBuf>,
@@ -411,14 +703,12 @@ impl Timings {
 }
 
 impl Args {
-    pub fn absolute_search_path(&mut self) {
-        if let Some(paths) = self.config_override.search_path.as_mut() {
-            for x in paths.iter_mut() {
-                if let Ok(v) = x.absolutize() {
-                    *x = v.into_owned();
-                }
-            }
-        }
+    pub fn config_override(&self) -> &ConfigOverrideArgs {
+        &self.config_override
+    }
+
+    pub fn config_override_mut(&mut self) -> &mut ConfigOverrideArgs {
+        &mut self.config_override
     }
 
     pub fn get_handles(
@@ -472,11 +762,65 @@ impl Args {
                 .new_transaction(require_levels.default, None),
             allow_forget,
         );
-        self.run_inner(
-            timings,
-            transaction.as_mut(),
-            &handles.all(require_levels.specified),
-        )
+        let handles = match &self.behavior.changed_since {
+            Some(rev) => Self::changed_since(
+                rev,
+                &handles,
+                transaction.as_mut(),
+                self.config_override
+                    .search_path
+                    .as_deref()
+                    .unwrap_or_default(),
+                holder.as_ref().config_finder(),
+                require_levels.specified,
+            )?,
+            None => handles.all(require_levels.specified),
+        };
+        let handles = match self.behavior.shard {
+            Some(shard) => {
+                let config_finder = holder.as_ref().config_finder();
+                handles
+                    .into_iter()
+                    .filter(|(handle, _)| {
+                        let config = config_finder.python_file(handle.module(), handle.path());
+                        shard.contains(handle, config.source.root())
+                    })
+                    .collect()
+            }
+            None => handles,
+        };
+        self.run_inner(timings, transaction.as_mut(), &handles)
+    }
+
+    /// The handles to check for `--changed-since`: the files git reports as changed since `rev`,
+    /// and every file that transitively imports them. We learn the imports by binding every file,
+    /// which doesn't need to solve anything.
+    fn changed_since(
+        rev: &str,
+        handles: &Handles,
+        transaction: &mut Transaction,
+        args_search_path: &[PathBuf],
+        config_finder: &ConfigFinder,
+        specified: Require,
+    ) -> anyhow::Result<Vec<(Handle, Require)>> {
+        let changes = git_changed_files(rev)?;
+        let all = handles.all(specified);
+        transaction.set_subscriber(Some(Box::new(ProgressBarSubscriber::new())));
+        transaction.run_until(&all, Step::Answers);
+        transaction.set_subscriber(None);
+        let affected =
+            handles.affected_by(&changes, &all, transaction, args_search_path, config_finder);
+        let res = all
+            .into_iter()
+            .filter(|(handle, _)| affected.contains(handle))
+            .collect::<Vec<_>>();
+        debug!(
+            "{} files changed and {} removed since `{rev}`, checking {} files affected by them",
+            changes.changed.len(),
+            changes.removed.len(),
+            res.len(),
+        );
+        Ok(res)
     }
 
     pub async fn run_watch(
@@ -531,63 +875,206 @@ impl Args {
         }
     }
 
-    pub fn validate(&self) -> anyhow::Result<()> {
-        fn validate_arg(arg_name: &str, paths: Option<&[PathBuf]>) -> anyhow::Result<()> {
-            if let Some(paths) = paths {
-                for path in paths {
-                    validate_path(path).with_context(|| format!("Invalid {}", arg_name))?;
+    /// Keep the files to check warm in a `State`, and recheck them whenever a `pyrefly check --daemon`
+    /// client connects to `socket`.
+    #[cfg(unix)]
+    pub fn run_daemon(
+        self,
+        socket: &Path,
+        files_to_check: FilteredGlobs,
+        config_finder: ConfigFinder,
+    ) -> anyhow::Result<()> {
+        if UnixStream::connect(socket).is_ok() {
+            return Err(anyhow::anyhow!(
+                "A daemon is already listening on `{}`",
+                socket.display()
+            ));
+        }
+        // Nobody is listening, so any existing socket was left behind by a daemon that died.
+        daemon::remove_stale_socket(socket)?;
+        let listener = UnixListener::bind(socket)
+            .with_context(|| format!("while listening on `{}`", socket.display()))?;
+
+        let expanded_file_list = checkpoint(files_to_check.files(), &config_finder)?;
+        let require_levels = self.get_required_levels();
+        let mut handles = Handles::new(
+            expanded_file_list,
+            self.config_override
+                .search_path
+                .as_deref()
+                .unwrap_or_default(),
+            &config_finder,
+        );
+        let state = State::new(config_finder);
+        // Check everything up front, so that the first client doesn't start cold.
+        state.run(
+            &handles.all(require_levels.specified),
+            require_levels.default,
+            Some(Box::new(ProgressBarSubscriber::new())),
+        );
+        eprintln!("Daemon listening on `{}`", socket.display());
+
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    eprintln!("{e:#}");
+                    continue;
+                }
+            };
+            let request = daemon::receive::<DaemonRequest>(&stream, daemon::REQUEST_TIMEOUT);
+            let stop = request.as_ref().is_ok_and(|request| request.stop);
+            let response = request.and_then(|request| {
+                if request.stop {
+                    return Ok(DaemonResponse::default());
                 }
+                self.daemon_check(
+                    &state,
+                    &mut handles,
+                    &files_to_check,
+                    &require_levels,
+                    &request,
+                )
+            });
+            let response = response.unwrap_or_else(|e| DaemonResponse {
+                failure: Some(format!("{e:#}")),
+                ..Default::default()
+            });
+            if let Err(e) = daemon::send(&mut stream, &response) {
+                eprintln!("{e:#}");
+            }
+            if stop {
+                break;
             }
-            Ok(())
         }
-        validate_arg(
-            "--site-package-path",
-            self.config_override.site_package_path.as_deref(),
-        )?;
-        validate_arg("--search-path", self.config_override.search_path.as_deref())?;
+        // Nobody will connect once we stop listening, so don't leave the socket behind.
+        drop(listener);
+        daemon::remove_stale_socket(socket)?;
         Ok(())
     }
 
-    pub fn override_config(&self, mut config: ConfigFile) -> (ArcId<ConfigFile>, Vec<ConfigError>) {
-        if let Some(x) = &self.config_override.python_platform {
-            config.python_environment.python_platform = Some(x.clone());
-        }
-        if let Some(x) = &self.config_override.python_version {
-            config.python_environment.python_version = Some(*x);
-        }
-        if let Some(x) = &self.config_override.search_path {
-            config.search_path_from_args = x.clone();
-        }
-        if let Some(x) = &self.config_override.site_package_path {
-            config.python_environment.site_package_path = Some(x.clone());
-            config.python_environment.site_package_path_source = SitePackagePathSource::CommandLine;
-        }
-        if let Some(x) = &self.config_override.python_interpreter {
-            config.python_interpreter = Some(x.clone());
+    /// Recheck on behalf of a daemon client, reusing everything that didn't change since the last check.
+    #[cfg(unix)]
+    fn daemon_check(
+        &self,
+        state: &State,
+        handles: &mut Handles,
+        files_to_check: &FilteredGlobs,
+        require_levels: &RequireLevels,
+        request: &DaemonRequest,
+    ) -> anyhow::Result<DaemonResponse> {
+        let mut events = CategorizedEvents {
+            created: Vec::new(),
+            modified: Vec::new(),
+            removed: Vec::new(),
+            unknown: Vec::new(),
+        };
+        for path in request.changed.clone().unwrap_or_else(|| handles.paths()) {
+            if !path.exists() {
+                events.removed.push(path);
+            } else if handles.contains(&path) {
+                events.modified.push(path);
+            } else {
+                events.created.push(path);
+            }
         }
-        if let Some(x) = &self.config_override.use_untyped_imports {
-            config.use_untyped_imports = *x;
+        let mut transaction = state.new_committable_transaction(
+            require_levels.default,
+            Some(Box::new(ProgressBarSubscriber::new())),
+        );
+        transaction.as_mut().invalidate_events(&events);
+        handles.update(
+            events.created.iter().filter(|p| files_to_check.covers(p)),
+            events.removed.iter().filter(|p| files_to_check.covers(p)),
+            self.config_override
+                .search_path
+                .as_deref()
+                .unwrap_or_default(),
+            state.config_finder(),
+        );
+        let handles = handles.all(require_levels.specified);
+        state.run_with_committing_transaction(transaction, &handles);
+
+        let transaction = state.transaction();
+        let loads = if self.behavior.check_all {
+            transaction.get_all_errors()
+        } else {
+            transaction.get_errors(handles.iter().map(|(handle, _)| handle))
+        };
+        let errors = loads.collect_errors();
+        let mut output = Vec::new();
+        request
+            .output_format
+            .write_errors(&mut output, &errors, request.include_suppressed)?;
+        Ok(DaemonResponse {
+            output: String::from_utf8(output)?,
+            shown: errors
+                .shown
+                .iter()
+                .filter(|e| e.severity() == Severity::Error)
+                .count(),
+            ignored: errors.disabled.len() + errors.suppressed.len(),
+            modules: handles.len(),
+            failure: None,
+        })
+    }
+
+    /// Ask the daemon listening on `socket` to recheck, after the files in `changed` were modified
+    /// (or every file, if `None`), and report the errors it finds. If `stop`, ask it to stop instead.
+    #[cfg(unix)]
+    pub fn run_daemon_client(
+        self,
+        socket: &Path,
+        changed: Option<Vec<PathBuf>>,
+        stop: bool,
+    ) -> anyhow::Result<CommandExitStatus> {
+        let timings = Timings::new();
+        let changed = match changed {
+            Some(changed) => Some(
+                changed
+                    .into_iter()
+                    .map(|x| Ok(x.absolutize()?.into_owned()))
+                    .collect::<anyhow::Result<Vec<_>>>()?,
+            ),
+            None => None,
+        };
+        let response = daemon::request(
+            socket,
+            &DaemonRequest {
+                changed,
+                output_format: self.output.output_format.clone(),
+                include_suppressed: self.output.include_suppressed,
+                stop,
+            },
+        )?;
+        if let Some(failure) = response.failure {
+            return Err(anyhow::anyhow!("The daemon failed to check: {failure}"));
         }
-        if let Some(x) = &self.config_override.ignore_missing_source {
-            config.ignore_missing_source = *x;
+        if stop {
+            return Ok(CommandExitStatus::Success);
         }
-        if let Some(x) = &self.config_override.untyped_def_behavior {
-            config.root.untyped_def_behavior = Some(*x);
+        match &self.output.output {
+            Some(path) => fs_anyhow::write(path, response.output.as_bytes())?,
+            None => {
+                let mut out = stdout();
+                out.write_all(response.output.as_bytes())?;
+                out.flush()?;
+            }
         }
-        if let Some(wildcards) = &self.config_override.replace_imports_with_any {
-            config.root.replace_imports_with_any = Some(
-                wildcards
-                    .iter()
-                    .filter_map(|x| ModuleWildcard::new(x).ok())
-                    .collect(),
+        if !self.output.no_summary {
+            anstream::eprintln!(
+                "{} errors shown: {}, errors ignored: {}, modules: {}, time: {timings}",
+                Severity::Info.painted(),
+                number_thousands(response.shown),
+                number_thousands(response.ignored),
+                number_thousands(response.modules),
             );
         }
-        if let Some(x) = &self.config_override.ignore_errors_in_generated_code {
-            config.root.ignore_errors_in_generated_code = Some(*x);
+        if response.shown > 0 {
+            Ok(CommandExitStatus::UserError)
+        } else {
+            Ok(CommandExitStatus::Success)
         }
-        config.configure();
-        let errors = config.validate();
-        (ArcId::new(config), errors)
     }
 
     fn get_required_levels(&self) -> RequireLevels {
@@ -603,6 +1090,9 @@ impl Args {
             },
             default: if retain {
                 Require::Everything
+            } else if self.behavior.shard.is_some() {
+                // Other shards report the errors in our dependencies.
+                Require::Exports
             } else if self.behavior.check_all || stdlib_search_path().is_some() {
                 Require::Errors
             } else {
@@ -620,9 +1110,37 @@ impl Args {
         let mut memory_trace = MemoryUsageTrace::start(Duration::from_secs_f32(0.1));
 
         let type_check_start = Instant::now();
-        transaction.set_subscriber(Some(Box::new(ProgressBarSubscriber::new())));
-        transaction.run(handles);
+        let telemetry = self.output.progress.then(|| {
+            TelemetrySubscriber::new(PROGRESS_INTERVAL, Box::new(|report| eprintln!("{report}")))
+        });
+        let subscriber: Box<dyn Subscriber> = match &telemetry {
+            Some(telemetry) => Box::new(telemetry.dupe()),
+            None => Box::new(ProgressBarSubscriber::new()),
+        };
+        let chrome_trace = self
+            .output
+            .report_chrome_trace
+            .as_ref()
+            .map(|_| ChromeTrace::new());
+        let subscriber = match &chrome_trace {
+            Some(chrome_trace) => chrome_trace.subscriber(subscriber),
+            None => subscriber,
+        };
+        transaction.set_subscriber(Some(subscriber));
+        transaction.set_memory_budget(self.behavior.memory_budget);
+        let (checked, mut configurations) =
+            run_matrix(transaction, handles, self.behavior.check_all);
+        let handles = checked.as_slice();
         transaction.set_subscriber(None);
+        if let Some(chrome_trace) = &chrome_trace
+            && let Some(path) = &self.output.report_chrome_trace
+        {
+            chrome_trace.write(path)?;
+        }
+        if let Some(telemetry) = &telemetry {
+            eprintln!("{}", telemetry.progress());
+            eprint!("{}", telemetry.telemetry());
+        }
 
         let loads = if self.behavior.check_all {
             transaction.get_all_errors()
@@ -634,19 +1152,53 @@ impl Args {
         let report_errors_start = Instant::now();
         let config_errors = transaction.get_config_errors();
         let config_errors_count = config_errors.len();
-        for error in config_errors {
+        for error in &config_errors {
             error.print();
         }
 
-        let errors = loads.collect_errors();
-        if let Some(path) = &self.output.output {
-            self.output
-                .output_format
-                .write_errors_to_file(path, &errors.shown)?;
+        let errors = if configurations.len() > 1 {
+            let handles = if self.behavior.check_all {
+                transaction.handles()
+            } else {
+                handles.map(|(handle, _)| handle.dupe())
+            };
+            for handle in handles {
+                configurations
+                    .entry(handle.sys_info().dupe())
+                    .or_default()
+                    .insert(handle);
+            }
+            collect_matrix_errors(transaction, &configurations)
+        } else {
+            loads.collect_errors()
+        };
+        let mut shown_errors_count = config_errors_count;
+        // Warnings and info are shown, but don't count towards the errors that fail the check.
+        for error in &errors.shown {
+            if error.severity() == Severity::Error {
+                shown_errors_count += 1;
+            }
+        }
+        if let Some(shard) = self.behavior.shard {
+            ShardReport::new(
+                shard,
+                &errors.shown,
+                &config_errors,
+                shown_errors_count - config_errors_count,
+                errors.disabled.len() + errors.suppressed.len(),
+                handles.len(),
+            )?
+            .write(self.output.output.as_ref())?;
+        } else if let Some(path) = &self.output.output {
+            self.output.output_format.write_errors_to_file(
+                path,
+                &errors,
+                self.output.include_suppressed,
+            )?;
         } else {
             self.output
                 .output_format
-                .write_errors_to_console(&errors.shown)?;
+                .write_errors_to_console(&errors, self.output.include_suppressed)?;
         }
         memory_trace.stop();
         if let Some(limit) = self.output.count_errors {
@@ -655,12 +1207,6 @@ impl Args {
         if let Some(path_index) = self.output.summarize_errors {
             print_error_summary(&errors.shown, path_index);
         }
-        let mut shown_errors_count = config_errors_count;
-        for error in &errors.shown {
-            if error.error_kind().severity() >= Severity::Warn {
-                shown_errors_count += 1;
-            }
-        }
         timings.report_errors = report_errors_start.elapsed();
 
         if !self.output.no_summary {
@@ -714,7 +1260,7 @@ impl Args {
         if self.behavior.suppress_errors {
             let mut errors_to_suppress: SmallMap<PathBuf, Vec<Error>> = SmallMap::new();
             for e in errors.shown {
-                if e.error_kind().severity() >= Severity::Warn
+                if e.severity() >= Severity::Warn
                     && let ModulePathDetails::FileSystem(path) = e.path().details()
                 {
                     errors_to_suppress.entry(path.clone()).or_default().push(e);
@@ -723,26 +1269,14 @@ impl Args {
             suppress::suppress_errors(&errors_to_suppress);
         }
         if self.behavior.remove_unused_ignores {
-            let mut all_ignores: SmallMap<&PathBuf, SmallSet<OneIndexed>> = SmallMap::new();
-            for (module_path, ignore) in loads.collect_ignores() {
-                if let ModulePathDetails::FileSystem(path) = module_path.details() {
-                    all_ignores.insert(path, ignore.get_ignores(SuppressionKind::Pyrefly));
-                }
-            }
-
-            let mut suppressed_errors: SmallMap<&PathBuf, SmallSet<OneIndexed>> = SmallMap::new();
-            for e in &errors.suppressed {
-                if e.is_ignored()
-                    && let ModulePathDetails::FileSystem(path) = e.path().details()
-                {
-                    suppressed_errors
-                        .entry(path)
-                        .or_default()
-                        .insert(e.source_range().start.line);
-                }
-            }
-
-            let unused_ignores = suppress::find_unused_ignores(all_ignores, suppressed_errors);
+            let unused_ignores = loads
+                .collect_unused_ignores()
+                .into_iter()
+                .filter_map(|(module_path, unused)| match module_path.details() {
+                    ModulePathDetails::FileSystem(path) => Some((path, unused)),
+                    _ => None,
+                })
+                .collect();
             suppress::remove_unused_ignores(unused_ignores);
         }
         if self.behavior.expectations {
@@ -756,6 +1290,170 @@ impl Args {
     }
 }
 
+/// Check `handles`, which may be checked against several Python versions or platforms.
+///
+/// Each file is checked under the first configuration. Under each later one, it is only checked
+/// again if that configuration would bind it, or a module it transitively imports, differently
+/// from every configuration it was already checked under: if an `if` tests the version or
+/// platform and goes the other way, or the file has different syntax errors under that version.
+/// Otherwise, the errors it has under the earlier configuration stand for the later one too.
+///
+/// Returns the handles that were checked, and for each configuration, the handles checked under
+/// another one whose errors stand for it. With `check_all`, those include everything they
+/// transitively import.
+fn run_matrix(
+    transaction: &mut Transaction,
+    handles: &[(Handle, Require)],
+    check_all: bool,
+) -> (Vec<(Handle, Require)>, SmallMap<SysInfo, SmallSet<Handle>>) {
+    let mut configurations: SmallMap<SysInfo, Vec<(Handle, Require)>> = SmallMap::new();
+    for (handle, require) in handles {
+        configurations
+            .entry(handle.sys_info().dupe())
+            .or_default()
+            .push((handle.dupe(), *require));
+    }
+    let mut checked = Vec::new();
+    let mut checked_under: HashMap<(ModuleName, ModulePath), Vec<Handle>> = HashMap::new();
+    let mut prunes_like = HashMap::new();
+    let mut shared = SmallMap::new();
+    for (sys_info, handles) in configurations {
+        let mut todo = Vec::new();
+        let mut sharing = SmallSet::new();
+        for (handle, require) in handles {
+            let earlier = checked_under
+                .get(&(handle.module(), handle.path().dupe()))
+                .into_iter()
+                .flatten()
+                .find_map(|earlier| {
+                    transitively_prunes_like(transaction, earlier, &sys_info, &mut prunes_like)
+                        .map(|closure| (earlier, closure))
+                });
+            match earlier {
+                Some((_, closure)) if check_all => sharing.extend(closure),
+                Some((earlier, _)) => {
+                    sharing.insert(earlier.dupe());
+                }
+                None => todo.push((handle, require)),
+            }
+        }
+        transaction.run(&todo);
+        for (handle, _) in &todo {
+            checked_under
+                .entry((handle.module(), handle.path().dupe()))
+                .or_default()
+                .push(handle.dupe());
+        }
+        checked.extend(todo);
+        shared.insert(sys_info, sharing);
+    }
+    (checked, shared)
+}
+
+/// If `handle` and every module it transitively imports would be bound the same way under
+/// `sys_info` as they were, return them all. Remembers the result for each module in `memo`.
+fn transitively_prunes_like(
+    transaction: &Transaction,
+    handle: &Handle,
+    sys_info: &SysInfo,
+    memo: &mut HashMap<(Handle, SysInfo), bool>,
+) -> Option<Vec<Handle>> {
+    let mut seen = HashSet::new();
+    seen.insert(handle.dupe());
+    let mut todo = vec![handle.dupe()];
+    while let Some(handle) = todo.pop() {
+        if !*memo
+            .entry((handle.dupe(), sys_info.dupe()))
+            .or_insert_with(|| prunes_like(transaction, &handle, sys_info))
+        {
+            return None;
+        }
+        for import in transaction.get_imports(&handle) {
+            if seen.insert(import.dupe()) {
+                todo.push(import);
+            }
+        }
+    }
+    Some(seen.into_iter().collect())
+}
+
+/// Whether `handle` would be bound the same way under `sys_info`, with the same syntax errors.
+fn prunes_like(transaction: &Transaction, handle: &Handle, sys_info: &SysInfo) -> bool {
+    let Some(module_info) = transaction.get_module_info(handle) else {
+        return false;
+    };
+    let parse = |version| {
+        let errors = ErrorCollector::new(module_info.dupe(), ErrorStyle::Delayed);
+        let ast = module_info.parse(version, &errors);
+        let config = ErrorDisplayConfig::default();
+        (
+            ast,
+            errors
+                .collect(&ErrorConfig::new(&config, false, false))
+                .shown,
+        )
+    };
+    let (ast, syntax_errors) = parse(handle.sys_info().version());
+    handle.sys_info().prunes_like(sys_info, &ast.body)
+        && (handle.sys_info().version() == sys_info.version()
+            || parse(sys_info.version()).1 == syntax_errors)
+}
+
+/// Collect the errors under each of several Python versions or platforms, from the handles whose
+/// errors occur under it. The same error is usually reported under each of them, so keep one copy
+/// of each error, labelled with the configurations it occurs under if it doesn't occur under all
+/// of them.
+fn collect_matrix_errors(
+    transaction: &Transaction,
+    configurations: &SmallMap<SysInfo, SmallSet<Handle>>,
+) -> CollectedErrors {
+    let key = |e: &Error| {
+        (
+            e.path().dupe(),
+            e.source_range().clone(),
+            e.error_kind(),
+            e.msg(),
+        )
+    };
+    let mut shown = SmallMap::new();
+    let mut suppressed = SmallMap::new();
+    let mut disabled = SmallMap::new();
+    for (sys_info, handles) in configurations {
+        let errors = transaction.get_errors(handles).collect_errors();
+        let label = sys_info.to_string();
+        for e in errors.shown {
+            let labels = &mut shown.entry(key(&e)).or_insert_with(|| (e, Vec::new())).1;
+            // With `--check-all`, a module can be among the handles under both configurations.
+            if labels.last() != Some(&label) {
+                labels.push(label.clone());
+            }
+        }
+        for e in errors.suppressed {
+            suppressed.entry(key(&e)).or_insert(e);
+        }
+        for e in errors.disabled {
+            disabled.entry(key(&e)).or_insert(e);
+        }
+    }
+    let mut shown = shown
+        .into_iter()
+        .map(|(_, (e, labels))| {
+            if labels.len() == configurations.len() {
+                e
+            } else {
+                e.with_configurations(labels)
+            }
+        })
+        .collect::<Vec<_>>();
+    // Errors that only occur under later configurations were added last, so put them back in order.
+    shown.sort_by(|a, b| (a.path(), a.source_range()).cmp(&(b.path(), b.source_range())));
+    CollectedErrors {
+        shown,
+        suppressed: suppressed.into_iter().map(|(_, e)| e).collect(),
+        disabled: disabled.into_iter().map(|(_, e)| e).collect(),
+    }
+}
+
 /// If we have an error, print all the errors that the config finder has accumulated. This is used
 /// to ensure that config errors are still surfaced if we exit early.
 pub fn checkpoint<T>(result: anyhow::Result<T>, config_finder: &ConfigFinder) -> anyhow::Result<T> {
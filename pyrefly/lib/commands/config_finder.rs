@@ -133,7 +133,7 @@ mod tests {
     use pyrefly_util::test_path::TestPath;
 
     use super::*;
-    use crate::commands::check::Args;
+    use crate::commands::check::ConfigOverrideArgs;
     use crate::config::config::ConfigSource;
     use crate::config::environment::environment::PythonEnvironment;
     use crate::module::module_name::ModuleName;
@@ -141,7 +141,7 @@ mod tests {
 
     #[test]
     fn test_site_package_path_from_environment() {
-        let args = Args::parse_from(Vec::<OsString>::new().iter());
+        let args = ConfigOverrideArgs::parse_from(Vec::<OsString>::new().iter());
         let config = standard_config_finder(Arc::new(move |_, x| args.override_config(x)))
             .python_file(ModuleName::unknown(), &ModulePath::filesystem("".into()));
         let env = PythonEnvironment::get_default_interpreter_env();
@@ -0,0 +1,330 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! The protocol spoken between `pyrefly daemon`, which keeps a `State` warm in the background,
+//! and `pyrefly check --daemon`, which asks it to recheck. Each side sends a single line of JSON
+//! over a Unix socket.
+
+use std::env;
+use std::fs;
+use std::fs::DirBuilder;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Write;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::DirBuilderExt;
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context as _;
+use serde::Deserialize;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::commands::check::OutputFormat;
+use crate::commands::util::git_toplevel;
+use crate::config::finder::ConfigFinder;
+
+/// How long the daemon waits for a client that connected to send its request.
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a client waits for the daemon to finish checking.
+pub const RESPONSE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonRequest {
+    /// The files that changed since the last check.
+    /// If `None`, every file the daemon is checking is assumed to have changed.
+    pub changed: Option<Vec<PathBuf>>,
+    /// How to render the errors.
+    pub output_format: OutputFormat,
+    /// Include the errors suppressed by ignore comments in JSON output.
+    pub include_suppressed: bool,
+    /// Stop the daemon, instead of checking.
+    pub stop: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonResponse {
+    /// The rendered errors, to be written to the output as is.
+    pub output: String,
+    /// The number of errors shown, excluding warnings and info.
+    pub shown: usize,
+    /// The number of errors that were ignored or suppressed.
+    pub ignored: usize,
+    /// The number of modules checked.
+    pub modules: usize,
+    /// Set if the daemon failed to check.
+    pub failure: Option<String>,
+}
+
+/// The root of the project in the current directory, which a daemon and its clients agree on
+/// regardless of which subdirectory they are started in: the directory of the config file for
+/// `config`, or else of the config found from the current directory, or else the root of the git
+/// repository, or else the current directory itself.
+pub fn project_root(
+    config: Option<&Path>,
+    config_finder: &ConfigFinder,
+) -> anyhow::Result<PathBuf> {
+    let current_dir = env::current_dir().context("cannot identify current dir")?;
+    if let Some(config) = config {
+        let config = current_dir.join(config);
+        if let Some(root) = config.parent() {
+            return Ok(root.to_path_buf());
+        }
+    }
+    if let Some(config) = config_finder.directory(&current_dir)
+        && let Some(root) = config.source.root()
+    {
+        return Ok(root.to_path_buf());
+    }
+    Ok(git_toplevel().unwrap_or(current_dir))
+}
+
+/// The socket a daemon started in `root` listens on, unless told otherwise. It lives in
+/// `$XDG_RUNTIME_DIR`, or else in a directory under the temporary directory that only the current
+/// user can access, and is named after a hash of `root` that is stable across builds, so that
+/// clients and daemons from different versions agree on it.
+pub fn default_socket(root: &Path) -> anyhow::Result<PathBuf> {
+    let dir = match env::var_os("XDG_RUNTIME_DIR") {
+        Some(runtime) if !runtime.is_empty() => PathBuf::from(runtime).join("pyrefly"),
+        _ => {
+            let user = env::var_os("USER")
+                .or_else(|| env::var_os("LOGNAME"))
+                .unwrap_or_default();
+            env::temp_dir().join(format!("pyrefly-{}", user.to_string_lossy()))
+        }
+    };
+    create_private_dir(&dir)?;
+    Ok(dir.join(socket_name(root)))
+}
+
+fn socket_name(root: &Path) -> String {
+    let hash = blake3::hash(root.as_os_str().as_bytes());
+    format!("daemon-{}.sock", &hash.to_hex()[..16])
+}
+
+/// Create `dir` if needed, and make sure that nobody but its owner can access it, so that other
+/// users can't connect to or replace the socket inside it.
+fn create_private_dir(dir: &Path) -> anyhow::Result<()> {
+    match DirBuilder::new().recursive(false).mode(0o700).create(dir) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(e) => {
+            return Err(e).with_context(|| format!("while creating `{}`", dir.display()));
+        }
+    }
+    let metadata =
+        fs::symlink_metadata(dir).with_context(|| format!("while reading `{}`", dir.display()))?;
+    if !metadata.is_dir() || metadata.permissions().mode() & 0o077 != 0 {
+        return Err(anyhow::anyhow!(
+            "`{}` must be a directory that only its owner can access",
+            dir.display()
+        ));
+    }
+    Ok(())
+}
+
+/// Remove the socket a daemon that died left behind at `socket`, if any. Refuses to remove
+/// anything that isn't a socket.
+pub fn remove_stale_socket(socket: &Path) -> anyhow::Result<()> {
+    match fs::symlink_metadata(socket) {
+        Ok(metadata) if metadata.file_type().is_socket() => fs::remove_file(socket)
+            .with_context(|| format!("while removing the stale socket `{}`", socket.display())),
+        Ok(_) => Err(anyhow::anyhow!(
+            "`{}` already exists and is not a socket",
+            socket.display()
+        )),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e).with_context(|| format!("while reading `{}`", socket.display())),
+    }
+}
+
+pub fn send<T: Serialize>(stream: &mut UnixStream, message: &T) -> anyhow::Result<()> {
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    stream.write_all(&line)?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Wait at most `timeout` for the other side to send a message.
+pub fn receive<T: DeserializeOwned>(stream: &UnixStream, timeout: Duration) -> anyhow::Result<T> {
+    stream.set_read_timeout(Some(timeout))?;
+    let mut line = String::new();
+    BufReader::new(stream)
+        .read_line(&mut line)
+        .context("while waiting for a daemon message")?;
+    if line.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Connection closed before a message was received"
+        ));
+    }
+    serde_json::from_str(&line).context("while decoding a daemon message")
+}
+
+/// Ask the daemon listening on `socket` to recheck.
+pub fn request(socket: &Path, request: &DaemonRequest) -> anyhow::Result<DaemonResponse> {
+    let mut stream = UnixStream::connect(socket).with_context(|| {
+        format!(
+            "Could not connect to a daemon on `{}`, start one with `pyrefly daemon`",
+            socket.display()
+        )
+    })?;
+    send(&mut stream, request)?;
+    receive(&stream, RESPONSE_TIMEOUT)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use std::time::Duration;
+
+    use clap::Parser;
+    use pyrefly_util::globs::FilteredGlobs;
+    use pyrefly_util::globs::Globs;
+
+    use super::*;
+    use crate::commands::check::Args;
+    use crate::test::util::TestEnv;
+
+    #[test]
+    fn test_round_trip() {
+        let (mut client, mut server) = UnixStream::pair().unwrap();
+        let req = DaemonRequest {
+            changed: Some(vec![PathBuf::from("a.py")]),
+            output_format: OutputFormat::Json,
+            include_suppressed: false,
+            stop: false,
+        };
+        send(&mut client, &req).unwrap();
+        assert_eq!(
+            receive::<DaemonRequest>(&server, REQUEST_TIMEOUT).unwrap(),
+            req
+        );
+
+        let res = DaemonResponse {
+            output: "ERROR a.py:1:1-2: bad\n".to_owned(),
+            shown: 1,
+            ignored: 0,
+            modules: 1,
+            failure: None,
+        };
+        send(&mut server, &res).unwrap();
+        assert_eq!(
+            receive::<DaemonResponse>(&client, RESPONSE_TIMEOUT).unwrap(),
+            res
+        );
+    }
+
+    #[test]
+    fn test_receive_timeout() {
+        let (_client, server) = UnixStream::pair().unwrap();
+        assert!(receive::<DaemonRequest>(&server, Duration::from_millis(10)).is_err());
+    }
+
+    #[test]
+    fn test_socket_name() {
+        assert_eq!(socket_name(Path::new("/a")), socket_name(Path::new("/a")));
+        assert_ne!(socket_name(Path::new("/a")), socket_name(Path::new("/b")));
+    }
+
+    #[test]
+    fn test_create_private_dir() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let private = tempdir.path().join("private");
+        create_private_dir(&private).unwrap();
+        assert_eq!(
+            fs::metadata(&private).unwrap().permissions().mode() & 0o777,
+            0o700
+        );
+        // Creating it again is fine.
+        create_private_dir(&private).unwrap();
+
+        let public = tempdir.path().join("public");
+        fs::create_dir(&public).unwrap();
+        fs::set_permissions(&public, fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(create_private_dir(&public).is_err());
+    }
+
+    #[test]
+    fn test_remove_stale_socket() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let socket = tempdir.path().join("daemon.sock");
+        remove_stale_socket(&socket).unwrap();
+
+        drop(std::os::unix::net::UnixListener::bind(&socket).unwrap());
+        remove_stale_socket(&socket).unwrap();
+        assert!(!socket.exists());
+
+        fs::write(&socket, "not a socket").unwrap();
+        assert!(remove_stale_socket(&socket).is_err());
+        assert!(socket.exists());
+    }
+
+    #[test]
+    fn test_daemon_round_trip() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        let path = root.join("main.py");
+        fs::write(&path, "x: int = ''\n").unwrap();
+        let socket = root.join("daemon.sock");
+
+        let mut env = TestEnv::new();
+        env.add_real_path("main", path.clone());
+        let config_finder = env.config_finder();
+        let files = FilteredGlobs::new(
+            Globs::new(vec![format!("{}/**/*.py", root.display())]),
+            Globs::new(vec![]),
+        );
+        let daemon_socket = socket.clone();
+        let daemon = thread::spawn(move || {
+            Args::parse_from(["check"])
+                .run_daemon(&daemon_socket, files, config_finder)
+                .unwrap()
+        });
+        for _ in 0..600 {
+            if UnixStream::connect(&socket).is_ok() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+
+        let send_request = |changed: Option<Vec<PathBuf>>, stop: bool| {
+            request(
+                &socket,
+                &DaemonRequest {
+                    changed,
+                    output_format: OutputFormat::MinText,
+                    include_suppressed: false,
+                    stop,
+                },
+            )
+            .unwrap()
+        };
+        let check = |changed| send_request(changed, false);
+        let first = check(None);
+        assert_eq!(first.failure, None);
+        assert_eq!(first.shown, 1, "{}", first.output);
+        assert!(first.output.contains("main.py"), "{}", first.output);
+
+        fs::write(&path, "x: int = 0\n").unwrap();
+        let second = check(Some(vec![path.clone()]));
+        assert_eq!(second.failure, None);
+        assert_eq!(second.shown, 0, "{}", second.output);
+        assert_eq!(second.modules, 1);
+
+        assert_eq!(send_request(None, true).failure, None);
+        daemon.join().unwrap();
+        assert!(!socket.exists());
+    }
+}
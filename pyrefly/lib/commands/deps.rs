@@ -0,0 +1,107 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! `pyrefly deps`, which prints the import graph of a project as resolved by the checker.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use anstream::stdout;
+use clap::Parser;
+use clap::ValueEnum;
+use pyrefly_util::args::clap_env;
+use pyrefly_util::display::number_thousands;
+use pyrefly_util::fs_anyhow;
+use pyrefly_util::globs::FilteredGlobs;
+use pyrefly_util::prelude::VecExt;
+
+use crate::commands::check::ConfigOverrideArgs;
+use crate::commands::check::Handles;
+use crate::commands::check::checkpoint;
+use crate::commands::run::CommandExitStatus;
+use crate::config::finder::ConfigFinder;
+use crate::error::kind::Severity;
+use crate::report;
+use crate::state::require::Require;
+use crate::state::state::State;
+use crate::state::subscriber::ProgressBarSubscriber;
+
+/// The format `pyrefly deps` writes the import graph in.
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+pub enum GraphFormat {
+    /// JSON, with lists of modules, imports and import cycles
+    #[default]
+    Json,
+    /// The Graphviz DOT language
+    Dot,
+}
+
+#[derive(Debug, Parser, Clone)]
+pub struct Args {
+    /// Write the import graph to a file, instead of printing it.
+    #[arg(long, short = 'o', env = clap_env("OUTPUT"), value_name = "OUTPUT_FILE")]
+    output: Option<PathBuf>,
+    /// The format to write the import graph in.
+    #[arg(long, value_enum, default_value_t)]
+    graph_format: GraphFormat,
+    /// Omit the summary in the last line of the output.
+    #[arg(long, env = clap_env("NO_SUMMARY"))]
+    no_summary: bool,
+    #[command(flatten, next_help_heading = "Config Overrides")]
+    config_override: ConfigOverrideArgs,
+}
+
+impl Args {
+    pub fn config_override_mut(&mut self) -> &mut ConfigOverrideArgs {
+        &mut self.config_override
+    }
+
+    /// Check the files, then write out the import graph the checker resolved.
+    pub fn run(
+        self,
+        files_to_check: FilteredGlobs,
+        config_finder: ConfigFinder,
+    ) -> anyhow::Result<CommandExitStatus> {
+        let files = checkpoint(files_to_check.files(), &config_finder)?;
+        let state = State::new(config_finder);
+        let handles = Handles::new(
+            files,
+            self.config_override.search_path(),
+            state.config_finder(),
+        );
+        // Imports are resolved lazily, so we only see all of them once every binding is solved.
+        let handles = handles.all(Require::Errors);
+        let mut transaction = state.new_transaction(Require::Exports, None);
+        transaction.set_subscriber(Some(Box::new(ProgressBarSubscriber::new())));
+        transaction.run(&handles);
+        transaction.set_subscriber(None);
+        let handles = handles.into_map(|(handle, _)| handle);
+        let graph = match self.graph_format {
+            GraphFormat::Json => report::deps::deps_json(&transaction, &handles),
+            GraphFormat::Dot => report::deps::deps_dot(&transaction, &handles),
+        };
+        match &self.output {
+            Some(path) => fs_anyhow::write(path, graph.as_bytes())?,
+            None => {
+                let mut out = stdout();
+                out.write_all(graph.as_bytes())?;
+                out.flush()?;
+            }
+        }
+        if !self.no_summary {
+            let (modules, imports, cycles) = report::deps::deps_summary(&transaction, &handles);
+            anstream::eprintln!(
+                "{} modules: {}, imports: {}, import cycles: {}",
+                Severity::Info.painted(),
+                number_thousands(modules),
+                number_thousands(imports),
+                number_thousands(cycles),
+            );
+        }
+        Ok(CommandExitStatus::Success)
+    }
+}
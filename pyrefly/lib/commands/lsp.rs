@@ -14,6 +14,7 @@ use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicI32;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 
 use base64::Engine;
 use base64::engine::general_purpose;
@@ -47,6 +48,7 @@ use lsp_types::CompletionResponse;
 use lsp_types::ConfigurationItem;
 use lsp_types::ConfigurationParams;
 use lsp_types::Diagnostic;
+use lsp_types::DiagnosticRelatedInformation;
 use lsp_types::DidChangeTextDocumentParams;
 use lsp_types::DidChangeWatchedFilesClientCapabilities;
 use lsp_types::DidChangeWatchedFilesParams;
@@ -80,6 +82,9 @@ use lsp_types::MarkupContent;
 use lsp_types::MarkupKind;
 use lsp_types::NumberOrString;
 use lsp_types::OneOf;
+use lsp_types::ProgressParams;
+use lsp_types::ProgressParamsValue;
+use lsp_types::ProgressToken;
 use lsp_types::PublishDiagnosticsParams;
 use lsp_types::Range;
 use lsp_types::ReferenceParams;
@@ -106,6 +111,11 @@ use lsp_types::Unregistration;
 use lsp_types::UnregistrationParams;
 use lsp_types::Url;
 use lsp_types::WatchKind;
+use lsp_types::WorkDoneProgress;
+use lsp_types::WorkDoneProgressBegin;
+use lsp_types::WorkDoneProgressCreateParams;
+use lsp_types::WorkDoneProgressEnd;
+use lsp_types::WorkDoneProgressReport;
 use lsp_types::WorkspaceClientCapabilities;
 use lsp_types::WorkspaceEdit;
 use lsp_types::WorkspaceFoldersServerCapabilities;
@@ -120,6 +130,7 @@ use lsp_types::notification::DidOpenTextDocument;
 use lsp_types::notification::DidSaveTextDocument;
 use lsp_types::notification::Exit;
 use lsp_types::notification::Notification as _;
+use lsp_types::notification::Progress;
 use lsp_types::notification::PublishDiagnostics;
 use lsp_types::request::CodeActionRequest;
 use lsp_types::request::Completion;
@@ -135,6 +146,7 @@ use lsp_types::request::SemanticTokensFullRequest;
 use lsp_types::request::SemanticTokensRangeRequest;
 use lsp_types::request::SignatureHelpRequest;
 use lsp_types::request::UnregisterCapability;
+use lsp_types::request::WorkDoneProgressCreate;
 use lsp_types::request::WorkspaceConfiguration;
 use path_absolutize::Absolutize;
 use pyrefly_util::arc_id::ArcId;
@@ -175,6 +187,8 @@ use crate::state::state::CommittingTransaction;
 use crate::state::state::State;
 use crate::state::state::Transaction;
 use crate::state::state::TransactionData;
+use crate::state::subscriber::Subscriber;
+use crate::state::subscriber::TelemetrySubscriber;
 use crate::types::lsp::position_to_text_size;
 use crate::types::lsp::source_range_to_range;
 use crate::types::lsp::text_size_to_position;
@@ -306,6 +320,94 @@ impl ServerConnection {
     }
 }
 
+/// Where a progress token is in its lifecycle. The client only accepts `$/progress` for a token
+/// once it has responded to our `window/workDoneProgress/create` request.
+enum ProgressStatus {
+    /// Waiting for the client to respond, with the title to begin with.
+    Creating(String),
+    /// The client accepted the token, and we sent `Begin`.
+    Begun,
+    /// The check finished, or the client rejected the token, so we send nothing more.
+    Done,
+}
+
+/// Reports the progress of a long-running check to the client, using `$/progress` notifications.
+#[derive(Clone)]
+struct LspProgress {
+    sender: Sender<Message>,
+    token: ProgressToken,
+    status: Arc<Mutex<ProgressStatus>>,
+}
+
+/// How often we tell the client what a long-running check is working on.
+const LSP_PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+impl LspProgress {
+    /// Send `value` if the client has accepted the token. Holds the lock while sending, so that
+    /// reports can't overtake `Begin` or `End`.
+    fn send(&self, value: WorkDoneProgress) {
+        let status = self.status.lock();
+        if matches!(*status, ProgressStatus::Begun) {
+            self.send_unchecked(value);
+        }
+    }
+
+    fn send_unchecked(&self, value: WorkDoneProgress) {
+        // If the channel is closed, the client has gone away and doesn't care about progress.
+        let _ = self
+            .sender
+            .send(Message::Notification(new_notification::<Progress>(
+                ProgressParams {
+                    token: self.token.clone(),
+                    value: ProgressParamsValue::WorkDone(value),
+                },
+            )));
+    }
+
+    /// The client responded to the `window/workDoneProgress/create` request for our token.
+    fn created(&self, accepted: bool) {
+        let mut status = self.status.lock();
+        if let ProgressStatus::Creating(title) = &*status {
+            if accepted {
+                self.send_unchecked(WorkDoneProgress::Begin(WorkDoneProgressBegin {
+                    title: title.clone(),
+                    cancellable: Some(false),
+                    message: None,
+                    percentage: Some(0),
+                }));
+                *status = ProgressStatus::Begun;
+            } else {
+                *status = ProgressStatus::Done;
+            }
+        }
+    }
+
+    /// A subscriber that reports to the client what the transaction is working on.
+    fn subscriber(&self) -> TelemetrySubscriber {
+        let progress = self.clone();
+        TelemetrySubscriber::new(
+            LSP_PROGRESS_INTERVAL,
+            Box::new(move |report| {
+                progress.send(WorkDoneProgress::Report(WorkDoneProgressReport {
+                    cancellable: Some(false),
+                    message: Some(report.to_string()),
+                    percentage: Some((report.finished * 100 / report.started.max(1)) as u32),
+                }))
+            }),
+        )
+    }
+
+    fn end(self, subscriber: &TelemetrySubscriber) {
+        let mut status = self.status.lock();
+        if matches!(*status, ProgressStatus::Begun) {
+            self.send_unchecked(WorkDoneProgress::End(WorkDoneProgressEnd {
+                message: Some(subscriber.progress().to_string()),
+            }));
+        }
+        *status = ProgressStatus::Done;
+    }
+}
+
 struct Server {
     connection: ServerConnection,
     /// A thread pool of size one for heavy read operations on the State
@@ -319,6 +421,8 @@ struct Server {
     workspaces: Arc<Workspaces>,
     outgoing_request_id: Arc<AtomicI32>,
     outgoing_requests: Mutex<HashMap<RequestId, Request>>,
+    /// Progress tokens we asked the client to create, which it hasn't responded to yet.
+    pending_progress: Mutex<HashMap<String, LspProgress>>,
     filewatcher_registered: Arc<AtomicBool>,
 }
 
@@ -478,6 +582,7 @@ impl Workspaces {
         standard_config_finder(Arc::new(move |dir, mut config| {
             if let Some(dir) = dir
                 && config.python_interpreter.is_none()
+                && config.conda_environment.is_none()
             {
                 workspaces.get_with(dir.to_owned(), |w| {
                     let Some(PythonInfo { interpreter, env }) = w.python_info.clone() else {
@@ -603,7 +708,10 @@ fn initialize_connection(
         text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
         definition_provider: Some(OneOf::Left(true)),
         code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
-            code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+            code_action_kinds: Some(vec![
+                CodeActionKind::QUICKFIX,
+                CodeActionKind::SOURCE_FIX_ALL,
+            ]),
             ..Default::default()
         })),
         completion_provider: Some(CompletionOptions {
@@ -1012,6 +1120,7 @@ impl Server {
             workspaces,
             outgoing_request_id: Arc::new(AtomicI32::new(1)),
             outgoing_requests: Mutex::new(HashMap::new()),
+            pending_progress: Mutex::new(HashMap::new()),
             filewatcher_registered: Arc::new(AtomicBool::new(false)),
         };
         s.configure(&folders, &[]);
@@ -1037,6 +1146,37 @@ impl Server {
         self.outgoing_requests.lock().insert(id, request);
     }
 
+    /// Ask the client to show the progress of a long-running check, if it supports that.
+    fn start_progress(&self, title: &str) -> Option<LspProgress> {
+        let supported = self
+            .initialize_params
+            .capabilities
+            .window
+            .as_ref()
+            .and_then(|window| window.work_done_progress)
+            .unwrap_or(false);
+        if !supported {
+            return None;
+        }
+        let token = format!(
+            "pyrefly/{}",
+            self.outgoing_request_id.fetch_add(1, Ordering::SeqCst)
+        );
+        let progress = LspProgress {
+            sender: self.connection.0.sender.clone(),
+            token: ProgressToken::String(token.clone()),
+            status: Arc::new(Mutex::new(ProgressStatus::Creating(title.to_owned()))),
+        };
+        // `Begin` is sent once the client responds, see `handle_response`.
+        self.pending_progress
+            .lock()
+            .insert(token.clone(), progress.clone());
+        self.send_request::<WorkDoneProgressCreate>(WorkDoneProgressCreateParams {
+            token: ProgressToken::String(token),
+        });
+        Some(progress)
+    }
+
     fn validate_in_memory_for_transaction(
         state: &State,
         open_files: &RwLock<HashMap<PathBuf, Arc<String>>>,
@@ -1080,16 +1220,21 @@ impl Server {
                     path.to_path_buf(),
                     Diagnostic {
                         range: source_range_to_range(e.source_range()),
-                        severity: Some(match e.error_kind().severity() {
+                        severity: Some(match e.severity() {
                             Severity::Error => lsp_types::DiagnosticSeverity::ERROR,
                             Severity::Warn => lsp_types::DiagnosticSeverity::WARNING,
                             Severity::Info => lsp_types::DiagnosticSeverity::INFORMATION,
                         }),
                         source: Some("Pyrefly".to_owned()),
-                        message: e.msg().to_owned(),
+                        message: Self::diagnostic_message(e),
                         code: Some(lsp_types::NumberOrString::String(
                             e.error_kind().to_name().to_owned(),
                         )),
+                        related_information: Self::because_related_information(e),
+                        tags: e
+                            .error_kind()
+                            .is_unnecessary()
+                            .then(|| vec![lsp_types::DiagnosticTag::UNNECESSARY]),
                         ..Default::default()
                     },
                 ));
@@ -1098,6 +1243,45 @@ impl Server {
         None
     }
 
+    /// The error message, with the reasons why it happened that we can't point at a location
+    /// for. The others are presented as related information.
+    fn diagnostic_message(e: &Error) -> String {
+        let mut msg = e.msg_without_because();
+        for reason in e.because() {
+            if reason.location.is_none() {
+                msg.push_str("\n  ");
+                msg.push_str(&reason.msg);
+            }
+        }
+        msg
+    }
+
+    /// Reasons why the error happened, such as the protocol member that made a type not
+    /// assignable, each pointing at the definition it is about.
+    fn because_related_information(e: &Error) -> Option<Vec<DiagnosticRelatedInformation>> {
+        let related = e
+            .because()
+            .iter()
+            .filter_map(|reason| {
+                let location = reason.location.as_ref()?;
+                Some(DiagnosticRelatedInformation {
+                    location: Location {
+                        uri: module_info_to_uri(&location.module_info)?,
+                        range: source_range_to_range(
+                            &location.module_info.source_range(location.range),
+                        ),
+                    },
+                    message: reason.msg.trim_start().to_owned(),
+                })
+            })
+            .collect::<Vec<_>>();
+        if related.is_empty() {
+            None
+        } else {
+            Some(related)
+        }
+    }
+
     fn validate_in_memory<'a>(
         &'a self,
         ide_transaction_manager: &mut IDETransactionManager<'a>,
@@ -1160,11 +1344,13 @@ impl Server {
                 IndexingMode::LazyNonBlockingBackground => {
                     let state = self.state.dupe();
                     let priority_events_sender = self.priority_events_sender.dupe();
+                    let progress = self.start_progress("Pyrefly: indexing project files");
                     std::thread::spawn(move || {
                         Self::populate_all_project_files_in_config(
                             config,
                             state,
                             priority_events_sender,
+                            progress,
                         );
                     });
                 }
@@ -1173,6 +1359,7 @@ impl Server {
                         config,
                         self.state.dupe(),
                         self.priority_events_sender.dupe(),
+                        self.start_progress("Pyrefly: indexing project files"),
                     );
                 }
             }
@@ -1185,8 +1372,15 @@ impl Server {
         let state = self.state.dupe();
         let priority_events_sender = self.priority_events_sender.dupe();
         let cancellation_handles = self.cancellation_handles.dupe();
+        let progress = self.start_progress("Pyrefly: rechecking");
         std::thread::spawn(move || {
-            let mut transaction = state.new_committable_transaction(Require::Indexing, None);
+            let subscriber = progress.as_ref().map(|progress| progress.subscriber());
+            let mut transaction = state.new_committable_transaction(
+                Require::Indexing,
+                subscriber
+                    .clone()
+                    .map(|x| Box::new(x) as Box<dyn Subscriber>),
+            );
             f(transaction.as_mut());
             // Commit will be blocked until there are no ongoing reads.
             // If we have some long running read jobs that can be cancelled, we should cancel them
@@ -1196,6 +1390,11 @@ impl Server {
             }
             // we have to run, not just commit to process updates
             state.run_with_committing_transaction(transaction, &[]);
+            if let Some(progress) = progress
+                && let Some(subscriber) = &subscriber
+            {
+                progress.end(subscriber);
+            }
             // After we finished a recheck asynchronously, we immediately send `RecheckFinished` to
             // the main event loop of the server. As a result, the server can do a revalidation of
             // all the in-memory files based on the fresh main State as soon as possible.
@@ -1218,11 +1417,18 @@ impl Server {
         config: ArcId<ConfigFile>,
         state: Arc<State>,
         priority_events_sender: Arc<Sender<ServerEvent>>,
+        progress: Option<LspProgress>,
     ) {
         let unknown = ModuleName::unknown();
 
         eprintln!("Populating all files in the config ({:?}).", config.root);
-        let mut transaction = state.new_committable_transaction(Require::Indexing, None);
+        let subscriber = progress.as_ref().map(|progress| progress.subscriber());
+        let mut transaction = state.new_committable_transaction(
+            Require::Indexing,
+            subscriber
+                .clone()
+                .map(|x| Box::new(x) as Box<dyn Subscriber>),
+        );
 
         let project_path_blobs = config.get_filtered_globs(None);
         let paths = project_path_blobs.files().unwrap_or_default();
@@ -1244,6 +1450,11 @@ impl Server {
         eprintln!("Prepare to check {} files.", handles.len());
         transaction.as_mut().run(&handles);
         state.commit_transaction(transaction);
+        if let Some(progress) = progress
+            && let Some(subscriber) = &subscriber
+        {
+            progress.end(subscriber);
+        }
         // After we finished a recheck asynchronously, we immediately send `RecheckFinished` to
         // the main event loop of the server. As a result, the server can do a revalidation of
         // all the in-memory files based on the fresh main State as soon as possible.
@@ -1493,8 +1704,9 @@ impl Server {
             position_to_text_size(&module_info, params.range.start),
             position_to_text_size(&module_info, params.range.end),
         );
-        let code_actions = transaction
-            .local_quickfix_code_actions(&handle, range)?
+        let errors = transaction.get_errors(once(&handle)).collect_errors().shown;
+        let mut code_actions = transaction
+            .local_quickfix_code_actions(&handle, range, &errors)?
             .into_map(|(title, range, insert_text)| {
                 CodeActionOrCommand::CodeAction(CodeAction {
                     title,
@@ -1512,6 +1724,23 @@ impl Server {
                     ..Default::default()
                 })
             });
+        if let Some(edits) = transaction.remove_unused_imports_edits(&handle, &errors) {
+            code_actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: "Remove all unused imports".to_owned(),
+                kind: Some(CodeActionKind::SOURCE_FIX_ALL),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(HashMap::from([(
+                        uri.clone(),
+                        edits.into_map(|(range, new_text)| TextEdit {
+                            range: source_range_to_range(&range),
+                            new_text,
+                        }),
+                    )])),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }));
+        }
         Some(code_actions)
     }
 
@@ -1886,6 +2115,14 @@ impl Server {
         request: &Request,
         response: &Response,
     ) -> anyhow::Result<()> {
+        if let Some(params) = as_request::<WorkDoneProgressCreate>(request) {
+            if let ProgressToken::String(token) = &params.token
+                && let Some(progress) = self.pending_progress.lock().remove(token)
+            {
+                progress.created(response.error.is_none());
+            }
+            return Ok(());
+        }
         if let Some((request, response)) =
             as_request_response_pair::<WorkspaceConfiguration>(request, response)
         {
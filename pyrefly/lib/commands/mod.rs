@@ -11,11 +11,15 @@ pub mod check;
 pub mod config_finder;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod config_migration;
+#[cfg(unix)]
+pub mod daemon;
+pub mod deps;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod init;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod lsp;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod run;
+pub mod shard;
 pub mod suppress;
 pub mod util;
@@ -17,8 +17,15 @@ use pyrefly_util::trace::init_tracing;
 pub use crate::commands::autotype::Args as AutotypeArgs;
 pub use crate::commands::buck_check::Args as BuckCheckArgs;
 pub use crate::commands::check::Args as CheckArgs;
+pub use crate::commands::check::ConfigOverrideArgs;
+#[cfg(unix)]
+pub use crate::commands::daemon::default_socket as default_daemon_socket;
+#[cfg(unix)]
+pub use crate::commands::daemon::project_root as daemon_project_root;
+pub use crate::commands::deps::Args as DepsArgs;
 pub use crate::commands::init::Args as InitArgs;
 pub use crate::commands::lsp::Args as LspArgs;
+pub use crate::commands::shard::MergeShardsArgs;
 
 #[derive(Debug, Parser, Clone)]
 pub struct CommonGlobalArgs {
@@ -0,0 +1,391 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Split `pyrefly check` across several machines with `--shard i/N`, and combine what each
+//! shard reports with `pyrefly merge-shards`.
+
+use std::fmt;
+use std::fmt::Display;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anstream::stdout;
+use anyhow::Context as _;
+use clap::Parser;
+use pyrefly_util::display::number_thousands;
+use pyrefly_util::fs_anyhow;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::commands::run::CommandExitStatus;
+use crate::config::finder::ConfigError;
+use crate::error::error::Error;
+use crate::error::kind::Severity;
+use crate::error::legacy::LegacyError;
+use crate::module::module_name::ModuleName;
+use crate::state::handle::Handle;
+
+/// One of `count` partitions of the files to check, where `index` is 1-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shard {
+    index: usize,
+    count: usize,
+}
+
+impl FromStr for Shard {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let (index, count) = s
+            .split_once('/')
+            .with_context(|| format!("Expected a shard of the form `i/N`, got `{s}`"))?;
+        let index = index
+            .trim()
+            .parse::<usize>()
+            .with_context(|| format!("Invalid shard index in `{s}`"))?;
+        let count = count
+            .trim()
+            .parse::<usize>()
+            .with_context(|| format!("Invalid shard count in `{s}`"))?;
+        if index == 0 || index > count {
+            return Err(anyhow::anyhow!(
+                "Shard index must be between 1 and the shard count, got `{s}`"
+            ));
+        }
+        Ok(Self { index, count })
+    }
+}
+
+impl Display for Shard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.index, self.count)
+    }
+}
+
+/// FNV-1a, used rather than the standard library hasher because every machine must agree on
+/// the partition, and `DefaultHasher` is not guaranteed to be stable across Rust releases.
+fn stable_hash(x: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for b in x.as_bytes() {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+impl Shard {
+    /// Whether this shard is responsible for checking `handle`. Files are assigned by a hash of
+    /// their module name, which doesn't depend on where the project is checked out. Files we
+    /// couldn't find a module name for are assigned by their path relative to `project_root`,
+    /// or to the current directory if they don't belong to a project.
+    pub fn contains(&self, handle: &Handle, project_root: Option<&Path>) -> bool {
+        let key = if handle.module() == ModuleName::unknown() {
+            let path = handle.path().as_path();
+            let root = project_root
+                .map(Path::to_path_buf)
+                .or_else(|| std::env::current_dir().ok());
+            let path = root
+                .as_deref()
+                .and_then(|root| path.strip_prefix(root).ok())
+                .unwrap_or(path);
+            path.to_string_lossy().into_owned()
+        } else {
+            handle.module().as_str().to_owned()
+        };
+        (stable_hash(&key) % self.count as u64) as usize == self.index - 1
+    }
+}
+
+/// What a single shard writes as its output, to be combined by `pyrefly merge-shards`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShardReport {
+    /// The 1-based index of the shard.
+    pub shard: usize,
+    /// The total number of shards.
+    pub shards: usize,
+    /// How many of `errors` have error severity, which along with `config_errors` is what
+    /// decides whether the shard failed.
+    pub shown: usize,
+    /// The number of errors that were ignored or suppressed.
+    pub ignored: usize,
+    /// The number of modules checked by this shard.
+    pub modules: usize,
+    /// The errors, in the same format as `--output-format=json`.
+    pub errors: Vec<serde_json::Value>,
+    /// The problems with the config files. Every shard reads the same configs, so they are only
+    /// counted once when merging.
+    pub config_errors: Vec<String>,
+}
+
+impl ShardReport {
+    pub fn new(
+        shard: Shard,
+        errors: &[Error],
+        config_errors: &[ConfigError],
+        shown: usize,
+        ignored: usize,
+        modules: usize,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            shard: shard.index,
+            shards: shard.count,
+            shown,
+            ignored,
+            modules,
+            errors: errors
+                .iter()
+                .map(|e| serde_json::to_value(LegacyError::from_error(e)))
+                .collect::<Result<_, _>>()?,
+            config_errors: config_errors.iter().map(|e| e.msg()).collect(),
+        })
+    }
+
+    pub fn write(&self, output: Option<&PathBuf>) -> anyhow::Result<()> {
+        let json = serde_json::to_vec_pretty(self)?;
+        match output {
+            Some(path) => fs_anyhow::write(path, &json),
+            None => {
+                let mut out = stdout();
+                out.write_all(&json)?;
+                out.flush()?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Combine the reports of every shard of a run into one.
+    fn merge(mut reports: Vec<ShardReport>) -> anyhow::Result<ShardReport> {
+        let shards = match reports.first() {
+            Some(x) => x.shards,
+            None => return Err(anyhow::anyhow!("No shard reports to merge")),
+        };
+        if let Some(x) = reports.iter().find(|x| x.shards != shards) {
+            return Err(anyhow::anyhow!(
+                "Cannot merge reports from runs split into {shards} and {} shards",
+                x.shards
+            ));
+        }
+        reports.sort_by_key(|x| x.shard);
+        for (i, x) in reports.iter().enumerate() {
+            if x.shard != i + 1 {
+                return Err(anyhow::anyhow!(
+                    "Expected one report for each of the {shards} shards, but shard {} is {}",
+                    i + 1,
+                    if x.shard <= i {
+                        "duplicated"
+                    } else {
+                        "missing"
+                    }
+                ));
+            }
+        }
+        if reports.len() != shards {
+            return Err(anyhow::anyhow!(
+                "Expected one report for each of the {shards} shards, but shard {} is missing",
+                reports.len() + 1
+            ));
+        }
+        let mut res = ShardReport {
+            shard: 1,
+            shards: 1,
+            shown: 0,
+            ignored: 0,
+            modules: 0,
+            errors: Vec::new(),
+            config_errors: Vec::new(),
+        };
+        for x in reports {
+            res.shown += x.shown;
+            res.ignored += x.ignored;
+            res.modules += x.modules;
+            res.errors.extend(x.errors);
+            for e in x.config_errors {
+                if !res.config_errors.contains(&e) {
+                    res.config_errors.push(e);
+                }
+            }
+        }
+        res.shown += res.config_errors.len();
+        // Each file is only checked by one shard, so a stable sort by path keeps the errors of
+        // each file together, in order, regardless of which shard checked it.
+        res.errors
+            .sort_by(|a, b| a["path"].as_str().cmp(&b["path"].as_str()));
+        Ok(res)
+    }
+}
+
+/// Combine the outputs of `pyrefly check --shard` into a single report.
+#[derive(Debug, Parser, Clone)]
+pub struct MergeShardsArgs {
+    /// The files written by each shard.
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+    /// Write the merged errors to a file, instead of printing them.
+    #[arg(long, short = 'o', value_name = "OUTPUT_FILE")]
+    output: Option<PathBuf>,
+    /// Omit the summary in the last line of the output.
+    #[arg(long)]
+    no_summary: bool,
+}
+
+impl MergeShardsArgs {
+    pub fn run(&self) -> anyhow::Result<CommandExitStatus> {
+        let reports = self
+            .files
+            .iter()
+            .map(|path| {
+                serde_json::from_slice::<ShardReport>(&fs_anyhow::read(path)?)
+                    .with_context(|| format!("while reading shard report `{}`", path.display()))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let shards = reports.len();
+        let merged = ShardReport::merge(reports)?;
+        for e in &merged.config_errors {
+            anstream::eprintln!("{e}");
+        }
+        let json = serde_json::to_vec_pretty(&serde_json::json!({ "errors": merged.errors }))?;
+        match &self.output {
+            Some(path) => fs_anyhow::write(path, &json)?,
+            None => {
+                let mut out = stdout();
+                out.write_all(&json)?;
+                out.flush()?;
+            }
+        }
+        if !self.no_summary {
+            anstream::eprintln!(
+                "{} errors shown: {}, errors ignored: {}, modules: {}, shards: {}",
+                Severity::Info.painted(),
+                number_thousands(merged.shown),
+                number_thousands(merged.ignored),
+                number_thousands(merged.modules),
+                shards,
+            );
+        }
+        if merged.shown > 0 {
+            Ok(CommandExitStatus::UserError)
+        } else {
+            Ok(CommandExitStatus::Success)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use pyrefly_util::globs::FilteredGlobs;
+    use pyrefly_util::globs::Globs;
+    use serde_json::json;
+
+    use super::*;
+    use crate::commands::check::Args;
+    use crate::test::util::TestEnv;
+
+    #[test]
+    fn test_parse_shard() {
+        assert_eq!(
+            "2/3".parse::<Shard>().unwrap(),
+            Shard { index: 2, count: 3 }
+        );
+        assert_eq!("2/3".parse::<Shard>().unwrap().to_string(), "2/3");
+        assert!("0/3".parse::<Shard>().is_err());
+        assert!("4/3".parse::<Shard>().is_err());
+        assert!("3".parse::<Shard>().is_err());
+        assert!("a/3".parse::<Shard>().is_err());
+    }
+
+    fn report(shard: usize, shards: usize, paths: &[&str]) -> ShardReport {
+        ShardReport {
+            shard,
+            shards,
+            shown: paths.len(),
+            ignored: 1,
+            modules: 10,
+            errors: paths.iter().map(|x| json!({ "path": x })).collect(),
+            config_errors: vec!["bad config".to_owned()],
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let merged = ShardReport::merge(vec![
+            report(2, 2, &["b.py", "d.py"]),
+            report(1, 2, &["a.py", "c.py"]),
+        ])
+        .unwrap();
+        // The config error both shards report is only counted once.
+        assert_eq!(merged.shown, 5);
+        assert_eq!(merged.config_errors, ["bad config"]);
+        assert_eq!(merged.ignored, 2);
+        assert_eq!(merged.modules, 20);
+        assert_eq!(
+            merged.errors,
+            ["a.py", "b.py", "c.py", "d.py"].map(|x| json!({ "path": x }))
+        );
+
+        assert!(ShardReport::merge(vec![report(1, 2, &[])]).is_err());
+        assert!(ShardReport::merge(vec![report(1, 2, &[]), report(1, 2, &[])]).is_err());
+        assert!(ShardReport::merge(vec![report(1, 2, &[]), report(2, 3, &[])]).is_err());
+    }
+
+    #[test]
+    fn test_shards_split_files() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        let mut env = TestEnv::new();
+        let mut expected = Vec::new();
+        for i in 0..10 {
+            let path = root.join(format!("m{i}.py"));
+            fs::write(&path, "x: int = ''\n").unwrap();
+            env.add_real_path(&format!("m{i}"), path.clone());
+            expected.push(path.to_string_lossy().into_owned());
+        }
+        expected.sort();
+
+        let reports = (1..=3)
+            .map(|i| {
+                let output = root.join(format!("shard{i}.json"));
+                let files = FilteredGlobs::new(
+                    Globs::new(vec![format!("{}/**/*.py", root.display())]),
+                    Globs::new(vec![]),
+                );
+                Args::parse_from([
+                    "check",
+                    "--no-summary",
+                    "--shard",
+                    &format!("{i}/3"),
+                    "--output",
+                    output.to_str().unwrap(),
+                ])
+                .run_once(files, env.config_finder(), false)
+                .unwrap();
+                serde_json::from_slice::<ShardReport>(&fs::read(&output).unwrap()).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        // Every file is checked by exactly one shard.
+        assert_eq!(reports.iter().map(|x| x.modules).sum::<usize>(), 10);
+        let merged = ShardReport::merge(reports).unwrap();
+        let paths = merged
+            .errors
+            .iter()
+            .map(|x| x["path"].as_str().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, expected);
+        assert_eq!(merged.shown, 10);
+    }
+
+    #[test]
+    fn test_shard_rejects_output_format() {
+        assert!(
+            Args::try_parse_from(["check", "--shard", "1/2", "--output-format", "json"]).is_err()
+        );
+    }
+}
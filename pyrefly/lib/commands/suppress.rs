@@ -10,36 +10,33 @@ use std::path::PathBuf;
 
 use anyhow::anyhow;
 use pyrefly_util::fs_anyhow;
-use regex::Regex;
 use ruff_source_file::OneIndexed;
 use starlark_map::small_map::SmallMap;
-use starlark_map::small_set::SmallSet;
 use tracing::error;
 
 use crate::error::error::Error;
+use crate::module::ignore::Ignore;
+use crate::module::ignore::UnusedIgnore;
 use crate::module::module_info::GENERATED_TOKEN;
 use crate::ruff::ast::Ast;
 
 /// Combines all errors that affect one line into a single entry.
-// The current format is: `# pyrefly: ignore  # error1, error2, ...`
+// The current format is: `# pyrefly: ignore[error1, error2, ...]`
 fn dedup_errors(errors: &[Error]) -> SmallMap<usize, String> {
-    let mut deduped_errors = SmallMap::new();
+    let mut deduped_errors: SmallMap<usize, Vec<&str>> = SmallMap::new();
     for error in errors {
-        let e: &mut String = deduped_errors
+        let e = deduped_errors
             .entry(error.source_range().start.line.to_zero_indexed())
             .or_default();
-        let contains_error = e.contains(error.error_kind().to_name());
-        if e.is_empty() {
-            e.push_str("# pyrefly: ignore  # ");
-        } else if !contains_error {
-            e.push_str(", ");
-        }
-
-        if !contains_error {
-            e.push_str(error.error_kind().to_name());
+        let name = error.error_kind().to_name();
+        if !e.contains(&name) {
+            e.push(name);
         }
     }
     deduped_errors
+        .into_iter()
+        .map(|(line, names)| (line, format!("# pyrefly: ignore[{}]", names.join(", "))))
+        .collect()
 }
 
 // TODO: In future have this return an ast as well as the string for comparison
@@ -120,53 +117,48 @@ pub fn suppress_errors(path_errors: &SmallMap<PathBuf, Vec<Error>>) {
     }
 }
 
-pub fn find_unused_ignores<'a>(
-    all_ignores: SmallMap<&'a PathBuf, SmallSet<OneIndexed>>,
-    suppressed_errors: SmallMap<&PathBuf, SmallSet<OneIndexed>>,
-) -> SmallMap<&'a PathBuf, SmallSet<OneIndexed>> {
-    let mut all_unused_ignores: SmallMap<&PathBuf, SmallSet<OneIndexed>> = SmallMap::new();
-    let one = OneIndexed::new(1).unwrap();
-    let default_set: SmallSet<OneIndexed> = SmallSet::new();
-    // Loop over each path only save the ignores that are not in use
-    for (path, ignores) in all_ignores {
-        let errors = suppressed_errors.get(path).unwrap_or(&default_set);
-        let mut unused_ignores = SmallSet::new();
-        for ignore in ignores {
-            if let Some(location) = ignore.checked_add(one)
-                && !errors.contains(&location)
-                && !errors.contains(&ignore)
-            {
-                unused_ignores.insert(ignore);
-            }
-        }
-
-        all_unused_ignores.insert(path, unused_ignores);
-    }
-    all_unused_ignores
-}
-
-pub fn remove_unused_ignores(path_ignores: SmallMap<&PathBuf, SmallSet<OneIndexed>>) {
-    let regex = Regex::new(r"# pyrefly: ignore.*$").unwrap();
+pub fn remove_unused_ignores(path_ignores: SmallMap<&PathBuf, SmallMap<OneIndexed, UnusedIgnore>>) {
     let mut removed_ignores: SmallMap<&PathBuf, usize> = SmallMap::new();
     for (path, ignores) in path_ignores {
         let mut unused_ignore_count = 0;
-        let zero_index_ignores: SmallSet<usize> =
-            ignores.iter().map(|i| i.to_zero_indexed()).collect();
+        let zero_index_ignores: SmallMap<usize, UnusedIgnore> = ignores
+            .into_iter()
+            .map(|(i, x)| (i.to_zero_indexed(), x))
+            .collect();
         if let Ok(file) = read_and_validate_file(path) {
             let mut buf = String::with_capacity(file.len());
             let lines = file.lines();
             for (idx, line) in lines.enumerate() {
-                if zero_index_ignores.contains(&idx) {
-                    unused_ignore_count += 1;
-                    // TODO: Expand support of what we remove and thoroughly test
-                    let new_string = regex.replace_all(line, "");
-                    if !new_string.trim().is_empty() {
-                        buf.push_str(new_string.trim_end());
+                match zero_index_ignores.get(&idx) {
+                    Some(UnusedIgnore::All) => {
+                        unused_ignore_count += 1;
+                        // TODO: Expand support of what we remove and thoroughly test
+                        let new_string = match Ignore::find_pyrefly_ignore(line) {
+                            Some((range, _)) => &line[..range.start().to_usize()],
+                            None => line,
+                        };
+                        if !new_string.trim().is_empty() {
+                            buf.push_str(new_string.trim_end());
+                        }
+                    }
+                    Some(UnusedIgnore::Codes(codes)) => {
+                        unused_ignore_count += codes.len();
+                        match Ignore::find_pyrefly_ignore(line) {
+                            Some((range, found)) => {
+                                let kept = found
+                                    .iter()
+                                    .map(|(x, _)| x.as_str())
+                                    .filter(|x| !codes.iter().any(|c| c == *x))
+                                    .collect::<Vec<_>>();
+                                buf.push_str(&line[..range.start().to_usize()]);
+                                buf.push_str(&format!("# pyrefly: ignore[{}]", kept.join(", ")));
+                                buf.push_str(&line[range.end().to_usize()..]);
+                            }
+                            None => buf.push_str(line),
+                        }
                     }
-                    buf.push('\n');
-                    continue;
+                    None => buf.push_str(line),
                 }
-                buf.push_str(line);
                 buf.push('\n');
             }
             if let Err(e) = fs_anyhow::write(path, buf.as_bytes()) {
@@ -190,6 +182,7 @@ mod tests {
     use pretty_assertions::assert_str_eq;
     use ruff_source_file::LineColumn;
     use ruff_source_file::OneIndexed;
+    use starlark_map::small_set::SmallSet;
     use tempfile;
     use vec1::Vec1;
 
@@ -224,10 +217,22 @@ mod tests {
     }
 
     fn test_remove_suppressions(lines: SmallSet<OneIndexed>, input: &str, want: &str) {
+        test_remove_unused_ignores(
+            lines.into_iter().map(|x| (x, UnusedIgnore::All)).collect(),
+            input,
+            want,
+        );
+    }
+
+    fn test_remove_unused_ignores(
+        ignores: SmallMap<OneIndexed, UnusedIgnore>,
+        input: &str,
+        want: &str,
+    ) {
         let tdir = tempfile::tempdir().unwrap();
         let path = tdir.path().join("test.py");
         fs_anyhow::write(&path, input.as_bytes()).unwrap();
-        let map = SmallMap::from_iter([(&path, lines)]);
+        let map = SmallMap::from_iter([(&path, ignores)]);
         remove_unused_ignores(map);
         let got_file = fs_anyhow::read_to_string(&path).unwrap();
         assert_str_eq!(want, got_file);
@@ -305,19 +310,19 @@ def f(y: int) -> None:
 f(x)
 
 "#,
-            r#"# pyrefly: ignore  # bad-assignment
+            r#"# pyrefly: ignore[bad-assignment]
 x: str = 1
 
 
 def f(y: int) -> None:
     """Doc comment"""
-    # pyrefly: ignore  # bad-argument-type
+    # pyrefly: ignore[bad-argument-type]
     x = "one" + y
-    # pyrefly: ignore  # bad-return
+    # pyrefly: ignore[bad-return]
     return x
 
 
-# pyrefly: ignore  # bad-argument-type
+# pyrefly: ignore[bad-argument-type]
 f(x)
 
 "#,
@@ -333,7 +338,7 @@ def foo() -> None: pass
 "#,
             r#"
 # comment
-# pyrefly: ignore  # bad-assignment
+# pyrefly: ignore[bad-assignment]
 def foo() -> None: pass
 "#,
         );
@@ -352,7 +357,7 @@ def foo() -> None: pass
 "#,
             r#"
 # comment
-# pyrefly: ignore  # bad-assignment
+# pyrefly: ignore[bad-assignment]
 def foo() -> None: pass
 "#,
         );
@@ -371,7 +376,7 @@ def foo() -> None: pass
 "#,
             r#"
 # comment
-# pyrefly: ignore  # bad-assignment, type-alias-error
+# pyrefly: ignore[bad-assignment, type-alias-error]
 def foo() -> None: pass
 "#,
         );
@@ -500,4 +505,56 @@ def f() -> int:
         );
         test_remove_suppressions(lines, &input, &input);
     }
+
+    #[test]
+    fn test_remove_unused_codes() {
+        let line = |x| OneIndexed::new(x).unwrap();
+        let ignores = SmallMap::from_iter([
+            (
+                line(3),
+                UnusedIgnore::Codes(vec!["bad-assignment".to_owned()]),
+            ),
+            (
+                line(5),
+                UnusedIgnore::Codes(vec!["bad-return".to_owned(), "unknown".to_owned()]),
+            ),
+        ]);
+        let input = r#"
+def g() -> str:
+    return "hello" # pyrefly: ignore[bad-return, bad-assignment]
+def f() -> int:
+    # pyrefly: ignore[bad-return, unknown, bad-argument-type]
+    return 1
+"#;
+        let want = r#"
+def g() -> str:
+    return "hello" # pyrefly: ignore[bad-return]
+def f() -> int:
+    # pyrefly: ignore[bad-argument-type]
+    return 1
+"#;
+        test_remove_unused_ignores(ignores, input, want);
+    }
+
+    #[test]
+    fn test_remove_unused_spacing() {
+        let line = |x| OneIndexed::new(x).unwrap();
+        let ignores = SmallMap::from_iter([
+            (line(3), UnusedIgnore::All),
+            (line(5), UnusedIgnore::Codes(vec!["bad-return".to_owned()])),
+        ]);
+        let input = r#"
+def g() -> str:
+    return "hello" # pyrefly:ignore
+def f() -> int:
+    return 1 # pyrefly:   ignore[bad-return, bad-assignment] # why
+"#;
+        let want = r#"
+def g() -> str:
+    return "hello"
+def f() -> int:
+    return 1 # pyrefly: ignore[bad-assignment] # why
+"#;
+        test_remove_unused_ignores(ignores, input, want);
+    }
 }
@@ -8,6 +8,9 @@
 use std::path::Component;
 use std::path::Path;
 use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::Context as _;
 
 use crate::dunder;
 use crate::module::module_name::ModuleName;
@@ -48,6 +51,99 @@ pub fn module_from_path<'a>(
     None
 }
 
+/// Run `git` with `args` in the current directory, returning its stdout.
+fn git(args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .with_context(|| format!("while running `git {}`", args.join(" ")))?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// The root of the git repository the current directory is in.
+pub fn git_toplevel() -> anyhow::Result<PathBuf> {
+    Ok(PathBuf::from(
+        git(&["rev-parse", "--show-toplevel"])?.trim(),
+    ))
+}
+
+fn is_python_file(path: &str) -> bool {
+    path.ends_with(".py") || path.ends_with(".pyi")
+}
+
+/// Parse a NUL-separated list of paths relative to `root`, as printed by git with `-z`, keeping
+/// only Python files.
+fn python_files_in(root: &Path, stdout: &str) -> Vec<PathBuf> {
+    stdout
+        .split('\0')
+        .filter(|x| is_python_file(x))
+        .map(|x| root.join(x))
+        .collect()
+}
+
+/// The Python files that git reports as changed relative to some revision.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct GitChanges {
+    /// Files that were added or modified, including the new names of renamed files and files
+    /// that are not tracked yet.
+    pub changed: Vec<PathBuf>,
+    /// Files that were deleted, including the old names of renamed files.
+    pub removed: Vec<PathBuf>,
+}
+
+impl GitChanges {
+    /// Parse the output of `git diff -z --name-status`, with paths relative to `root`. Each entry
+    /// is a status, followed by one path, or by two for renames and copies.
+    fn parse_name_status(root: &Path, stdout: &str) -> Self {
+        let mut res = Self::default();
+        let mut fields = stdout.split('\0').filter(|x| !x.is_empty());
+        while let Some(status) = fields.next() {
+            let (removed, changed) = match status.as_bytes().first() {
+                Some(b'R') => (fields.next(), fields.next()),
+                Some(b'C') => (None, fields.nth(1)),
+                Some(b'D') => (fields.next(), None),
+                _ => (None, fields.next()),
+            };
+            res.removed
+                .extend(removed.filter(|x| is_python_file(x)).map(|x| root.join(x)));
+            res.changed
+                .extend(changed.filter(|x| is_python_file(x)).map(|x| root.join(x)));
+        }
+        res
+    }
+}
+
+/// The Python files that git reports as changed relative to `rev`, including files that are
+/// not tracked yet.
+pub fn git_changed_files(rev: &str) -> anyhow::Result<GitChanges> {
+    let root = git_toplevel()?;
+    let mut res = GitChanges::parse_name_status(
+        &root,
+        &git(&["diff", "-z", "--name-status", "-M", rev, "--"])?,
+    );
+    // Untracked files are listed relative to the current directory, so ask for them from the root.
+    let root_arg = root.to_string_lossy();
+    res.changed.extend(python_files_in(
+        &root,
+        &git(&[
+            "-C",
+            &root_arg,
+            "ls-files",
+            "-z",
+            "--others",
+            "--exclude-standard",
+        ])?,
+    ));
+    Ok(res)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,4 +176,38 @@ mod tests {
             None
         );
     }
+
+    #[test]
+    fn test_python_files_in() {
+        assert_eq!(
+            python_files_in(
+                Path::new("/repo"),
+                "foo/bar.py\0README.md\0baz.pyi\0with space\nand newline.py\0setup.cfg\0"
+            ),
+            vec![
+                PathBuf::from("/repo/foo/bar.py"),
+                PathBuf::from("/repo/baz.pyi"),
+                PathBuf::from("/repo/with space\nand newline.py"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_name_status() {
+        assert_eq!(
+            GitChanges::parse_name_status(
+                Path::new("/repo"),
+                "M\0a.py\0D\0b.pyi\0R087\0c.py\0d.py\0C100\0e.py\0f.py\0A\0README.md\0R100\0g.txt\0h.py\0"
+            ),
+            GitChanges {
+                changed: vec![
+                    PathBuf::from("/repo/a.py"),
+                    PathBuf::from("/repo/d.py"),
+                    PathBuf::from("/repo/f.py"),
+                    PathBuf::from("/repo/h.py"),
+                ],
+                removed: vec![PathBuf::from("/repo/b.pyi"), PathBuf::from("/repo/c.py")],
+            }
+        );
+    }
 }
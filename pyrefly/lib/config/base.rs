@@ -62,6 +62,22 @@ pub struct ConfigBase {
     )]
     pub ignore_errors_in_generated_code: Option<bool>,
 
+    /// How long, in milliseconds, solving a single module may take. Once exceeded, the remaining
+    /// bindings in the module are treated as `Any`. By default there is no limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solve_budget_ms: Option<u64>,
+
+    /// Whether to report the error kinds that are off by default, such as implicit `Any`s and
+    /// missing return annotations. Any of them can still be disabled in `errors`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+
+    /// Whether a name imported into a module is only re-exported when marked explicitly, with
+    /// `import x as x`, `from m import x as x` or `__all__`. When unset, this is only enforced for
+    /// stubs; `true` also enforces it for packages with a `py.typed` file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explicit_reexport: Option<bool>,
+
     /// Any unknown config items
     #[serde(default, flatten)]
     pub extras: ExtraConfigs,
@@ -83,4 +99,16 @@ impl ConfigBase {
     pub fn get_ignore_errors_in_generated_code(base: &Self) -> Option<bool> {
         base.ignore_errors_in_generated_code
     }
+
+    pub fn get_solve_budget_ms(base: &Self) -> Option<u64> {
+        base.solve_budget_ms
+    }
+
+    pub fn get_strict(base: &Self) -> Option<bool> {
+        base.strict
+    }
+
+    pub fn get_explicit_reexport(base: &Self) -> Option<bool> {
+        base.explicit_reexport
+    }
 }
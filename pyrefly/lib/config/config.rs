@@ -10,6 +10,7 @@ use std::fmt;
 use std::fmt::Display;
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use anyhow::Context;
 use anyhow::anyhow;
@@ -28,6 +29,7 @@ use tracing::warn;
 
 use crate::config::base::ConfigBase;
 use crate::config::base::UntypedDefBehavior;
+use crate::config::environment::conda::Conda;
 use crate::config::environment::environment::PythonEnvironment;
 use crate::config::environment::environment::SitePackagePathSource;
 use crate::config::error::ErrorConfig;
@@ -37,8 +39,11 @@ use crate::module::bundled::typeshed;
 use crate::module::finder::find_module_in_search_path;
 use crate::module::finder::find_module_in_site_package_path;
 use crate::module::finder::find_module_prefixes;
+use crate::module::finder::is_py_typed_site_package_module;
+use crate::module::finder::is_untyped_site_package_module;
 use crate::module::module_name::ModuleName;
 use crate::module::module_path::ModulePath;
+use crate::module::module_path::ModulePathDetails;
 use crate::module::wildcard::ModuleWildcard;
 use crate::state::loader::FindError;
 use crate::sys_info::PythonPlatform;
@@ -236,6 +241,11 @@ pub struct ConfigFile {
     )]
     pub python_interpreter: Option<PathBuf>,
 
+    /// The name of a conda environment whose interpreter will be queried for
+    /// `python_version`, `python_platform`, or `site_package_path` if any of the values are missing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conda_environment: Option<String>,
+
     /// Values representing the environment of the Python interpreter
     /// (which platform, Python version, ...). When we parse, these values
     /// are default to false so we know to query the `python_interpreter` before falling
@@ -293,6 +303,7 @@ impl Default for ConfigFile {
             project_includes: Default::default(),
             project_excludes: Default::default(),
             python_interpreter: None,
+            conda_environment: None,
             search_path_from_args: Vec::new(),
             search_path_from_file: Vec::new(),
             import_root: None,
@@ -382,6 +393,38 @@ impl ConfigFile {
         }
     }
 
+    /// Whether `module`, found at `path`, comes from a package on the site package path that has
+    /// neither stubs nor a `py.typed` file.
+    pub fn is_untyped_import(&self, module: ModuleName, path: &ModulePath) -> bool {
+        match path.details() {
+            ModulePathDetails::FileSystem(path) => {
+                is_untyped_site_package_module(module, path, self.site_package_path())
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether `module`, found at `path`, only re-exports the names it imports when they are
+    /// marked explicitly, given the `explicit-reexport` setting of the importing file.
+    pub fn requires_explicit_reexport(
+        &self,
+        module: ModuleName,
+        path: &ModulePath,
+        setting: Option<bool>,
+    ) -> bool {
+        match setting {
+            Some(false) => false,
+            _ if path.is_interface() => true,
+            Some(true) => match path.details() {
+                ModulePathDetails::FileSystem(path) => {
+                    is_py_typed_site_package_module(module, path, self.site_package_path())
+                }
+                _ => false,
+            },
+            None => false,
+        }
+    }
+
     /// Find all legitimate imports that start with `module`
     pub fn find_import_prefixes(&self, module: ModuleName) -> Vec<ModuleName> {
         find_module_prefixes(module, self.search_path().chain(self.site_package_path()))
@@ -458,6 +501,26 @@ impl ConfigFile {
         SysInfo::new(self.python_version(), self.python_platform().clone())
     }
 
+    /// Every combination of the Python versions and platforms to check against, which is just
+    /// [`Self::get_sys_info`] unless `python-version` or `python-platform` is a list.
+    pub fn get_sys_info_matrix(&self) -> Vec<SysInfo> {
+        let env = &self.python_environment;
+        let versions = if env.python_version_matrix.is_empty() {
+            vec![self.python_version()]
+        } else {
+            env.python_version_matrix.clone()
+        };
+        let platforms = if env.python_platform_matrix.is_empty() {
+            vec![self.python_platform().clone()]
+        } else {
+            env.python_platform_matrix.clone()
+        };
+        versions
+            .iter()
+            .flat_map(|v| platforms.iter().map(|p| SysInfo::new(*v, p.clone())))
+            .collect()
+    }
+
     pub fn errors(&self, path: &Path) -> &ErrorDisplayConfig {
         self.get_from_sub_configs(ConfigBase::get_errors, path)
             .unwrap_or_else(||
@@ -484,6 +547,12 @@ impl ConfigFile {
                 self.root.untyped_def_behavior.unwrap())
     }
 
+    pub fn solve_budget(&self, path: &Path) -> Option<Duration> {
+        self.get_from_sub_configs(ConfigBase::get_solve_budget_ms, path)
+            .or(self.root.solve_budget_ms)
+            .map(Duration::from_millis)
+    }
+
     fn ignore_errors_in_generated_code(&self, path: &Path) -> bool {
         self.get_from_sub_configs(ConfigBase::get_ignore_errors_in_generated_code, path)
             .unwrap_or_else(||
@@ -492,10 +561,22 @@ impl ConfigFile {
                 self.root.ignore_errors_in_generated_code.unwrap())
     }
 
+    pub fn explicit_reexport(&self, path: &Path) -> Option<bool> {
+        self.get_from_sub_configs(ConfigBase::get_explicit_reexport, path)
+            .or(self.root.explicit_reexport)
+    }
+
+    fn strict(&self, path: &Path) -> bool {
+        self.get_from_sub_configs(ConfigBase::get_strict, path)
+            .or(self.root.strict)
+            .unwrap_or_default()
+    }
+
     pub fn get_error_config(&self, path: &Path) -> ErrorConfig {
         ErrorConfig::new(
             self.errors(path),
             self.ignore_errors_in_generated_code(path),
+            self.strict(path),
         )
     }
 
@@ -515,16 +596,36 @@ impl ConfigFile {
         })
     }
 
+    /// The interpreter of the configured `conda_environment`, if there is one and it can be found.
+    fn conda_environment_interpreter(&self) -> Option<PathBuf> {
+        let name = self.conda_environment.as_deref()?;
+        let interpreter = Conda::find_named(name);
+        if interpreter.is_none() {
+            warn!("Unable to find an interpreter for conda environment `{name}`");
+        }
+        interpreter
+    }
+
     /// Configures values that must be updated *after* overwriting with CLI flag values,
     /// which should probably be everything except for `PathBuf` or `Globs` types.
     pub fn configure(&mut self) {
         if self.python_environment.any_empty() {
+            let discovered = self.python_interpreter.is_none();
             if let Some(interpreter) = self
                 .python_interpreter
                 .clone()
+                .or_else(|| self.conda_environment_interpreter())
                 .or_else(|| PythonEnvironment::find_interpreter(self.source.root()))
             {
-                let system_env = PythonEnvironment::get_interpreter_env(&interpreter);
+                let mut system_env = PythonEnvironment::get_interpreter_env(&interpreter);
+                if discovered
+                    && system_env.site_package_path_source
+                        == SitePackagePathSource::Interpreter(interpreter.clone())
+                    && let Some(prefix) = Conda::prefix_of(&interpreter)
+                {
+                    system_env.site_package_path_source =
+                        SitePackagePathSource::CondaEnvironment(prefix.to_path_buf());
+                }
                 self.python_environment.override_empty(system_env);
                 self.python_interpreter = Some(interpreter);
             } else {
@@ -710,12 +811,13 @@ impl Display for ConfigFile {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{source: {:?}, project_includes: {}, project_excludes: {}, search_path: [{}], python_interpreter: {:?}, python_environment: {}, replace_imports_with_any: [{}]}}",
+            "{{source: {:?}, project_includes: {}, project_excludes: {}, search_path: [{}], python_interpreter: {:?}, conda_environment: {:?}, python_environment: {}, replace_imports_with_any: [{}]}}",
             self.source,
             self.project_includes,
             self.project_excludes,
             self.search_path().map(|p| p.display()).join(", "),
             self.python_interpreter,
+            self.conda_environment,
             self.python_environment,
             self.root
                 .replace_imports_with_any
@@ -782,6 +884,9 @@ mod tests {
             untyped-def-behavior = "check-and-infer-return-any"
             replace-imports-with-any = []
             ignore-errors-in-generated-code = false
+            solve-budget-ms = 2000
+            strict = true
+            explicit-reexport = true
             [sub-config.errors]
             assert-type = false
             invalid-yield = false
@@ -807,8 +912,10 @@ mod tests {
                         "venv/lib/python1.2.3/site-packages"
                     )]),
                     site_package_path_source: SitePackagePathSource::ConfigFile,
+                    ..Default::default()
                 },
                 python_interpreter: Some(PathBuf::from("venv/my/python")),
+                conda_environment: None,
                 root: ConfigBase {
                     extras: Default::default(),
                     errors: Some(ErrorDisplayConfig::new(HashMap::from_iter([
@@ -818,6 +925,9 @@ mod tests {
                     ignore_errors_in_generated_code: Some(true),
                     replace_imports_with_any: Some(vec![ModuleWildcard::new("fibonacci").unwrap()]),
                     untyped_def_behavior: Some(UntypedDefBehavior::CheckAndInferReturnType),
+                    solve_budget_ms: None,
+                    strict: None,
+                    explicit_reexport: None,
                 },
                 custom_module_paths: Default::default(),
                 sub_configs: vec![SubConfig {
@@ -831,6 +941,9 @@ mod tests {
                         ignore_errors_in_generated_code: Some(false),
                         replace_imports_with_any: Some(Vec::new()),
                         untyped_def_behavior: Some(UntypedDefBehavior::CheckAndInferReturnAny),
+                        solve_budget_ms: Some(2000),
+                        strict: Some(true),
+                        explicit_reexport: Some(true),
                     }
                 }],
                 use_untyped_imports: true,
@@ -939,6 +1052,7 @@ mod tests {
                     python_version: Some(PythonVersion::new(1, 2, 3)),
                     site_package_path: None,
                     site_package_path_source: SitePackagePathSource::ConfigFile,
+                    ..Default::default()
                 },
                 ..Default::default()
             }
@@ -977,6 +1091,7 @@ mod tests {
                     site_package_path: None,
                     // this won't be set until after `configure()`
                     site_package_path_source: SitePackagePathSource::ConfigFile,
+                    ..Default::default()
                 },
                 ..Default::default()
             }
@@ -1037,6 +1152,7 @@ mod tests {
             fallback_search_path: Vec::new(),
             python_environment: python_environment.clone(),
             python_interpreter: Some(PathBuf::from(interpreter.clone())),
+            conda_environment: None,
             root: Default::default(),
             custom_module_paths: Default::default(),
             sub_configs: vec![SubConfig {
@@ -1068,6 +1184,7 @@ mod tests {
             project_includes: Globs::new(project_includes_vec),
             project_excludes: Globs::new(project_excludes_vec),
             python_interpreter: Some(test_path.join(interpreter)),
+            conda_environment: None,
             search_path_from_args: Vec::new(),
             search_path_from_file: search_path,
             import_root: None,
@@ -1174,6 +1291,9 @@ mod tests {
                 replace_imports_with_any: Some(vec![ModuleWildcard::new("root").unwrap()]),
                 untyped_def_behavior: Some(UntypedDefBehavior::CheckAndInferReturnType),
                 ignore_errors_in_generated_code: Some(false),
+                solve_budget_ms: None,
+                strict: None,
+                explicit_reexport: Some(false),
                 extras: Default::default(),
             },
             sub_configs: vec![
@@ -1194,6 +1314,9 @@ mod tests {
                             ModuleWildcard::new("second").unwrap(),
                         ]),
                         ignore_errors_in_generated_code: Some(true),
+                        solve_budget_ms: Some(100),
+                        strict: Some(true),
+                        explicit_reexport: Some(true),
                         ..Default::default()
                     },
                 },
@@ -1222,6 +1345,26 @@ mod tests {
             &[ModuleWildcard::new("root").unwrap()],
         );
 
+        // test a value only set in a sub config
+        assert_eq!(
+            config.solve_budget(Path::new("this/is/second/priority")),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            config.solve_budget(Path::new("this/does/not/match/any")),
+            None
+        );
+        assert!(config.strict(Path::new("this/is/highest/priority")));
+        assert!(!config.strict(Path::new("this/does/not/match/any")));
+        assert_eq!(
+            config.explicit_reexport(Path::new("this/is/highest/priority")),
+            Some(true)
+        );
+        assert_eq!(
+            config.explicit_reexport(Path::new("this/does/not/match/any")),
+            Some(false)
+        );
+
         // test replace_imports_with_any special case None path
         assert_eq!(
             config.replace_imports_with_any(None),
@@ -1382,4 +1525,113 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_python_version_matrix() {
+        let mut config = ConfigFile::parse_config(
+            r#"
+            python-version = ["3.9", "3.12"]
+            python-platform = ["linux", "win32"]
+            site-package-path = []
+            "#,
+        )
+        .unwrap();
+        config.configure();
+        assert_eq!(config.python_version(), PythonVersion::new(3, 9, 0));
+        assert_eq!(config.python_platform(), &PythonPlatform::linux());
+        assert_eq!(
+            config.get_sys_info_matrix(),
+            vec![
+                SysInfo::new(PythonVersion::new(3, 9, 0), PythonPlatform::linux()),
+                SysInfo::new(PythonVersion::new(3, 9, 0), PythonPlatform::windows()),
+                SysInfo::new(PythonVersion::new(3, 12, 0), PythonPlatform::linux()),
+                SysInfo::new(PythonVersion::new(3, 12, 0), PythonPlatform::windows()),
+            ]
+        );
+        assert!(
+            toml::to_string(&config)
+                .unwrap()
+                .contains("python-version = [")
+        );
+
+        let config = ConfigFile::parse_config("python-version = \"3.12\"").unwrap();
+        assert!(config.python_environment.python_version_matrix.is_empty());
+        assert!(ConfigFile::parse_config("python-version = []").is_err());
+    }
+
+    #[test]
+    fn test_conda_environment() {
+        let mut config = ConfigFile::parse_config(
+            r#"
+            conda-environment = "myenv"
+            python-version = "3.12"
+            python-platform = "linux"
+            site-package-path = []
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.conda_environment.as_deref(), Some("myenv"));
+        // Nothing is missing from the environment, so no interpreter is looked up.
+        config.configure();
+        assert_eq!(config.python_interpreter, None);
+        assert!(
+            toml::to_string(&config)
+                .unwrap()
+                .contains("conda-environment = \"myenv\"")
+        );
+    }
+
+    /// Set up a conda environment at `prefix`, whose interpreter is a script that reports a fixed
+    /// environment rather than a real Python.
+    #[cfg(unix)]
+    fn fake_conda_prefix(prefix: &Path) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::create_dir_all(prefix.join("conda-meta")).unwrap();
+        fs::create_dir_all(prefix.join("bin")).unwrap();
+        let interpreter = prefix.join("bin/python3");
+        fs::write(
+            &interpreter,
+            r#"#!/bin/sh
+echo '{"python_platform": "linux", "python_version": "3.11.0", "site_package_path": ["/site"]}'
+"#,
+        )
+        .unwrap();
+        fs::set_permissions(&interpreter, fs::Permissions::from_mode(0o755)).unwrap();
+        interpreter
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_configure_conda_project_prefix() {
+        let tempdir = TempDir::new().unwrap();
+        let root = tempdir.path();
+        let interpreter = fake_conda_prefix(&root.join(".conda"));
+
+        let mut config = ConfigFile {
+            source: ConfigSource::File(root.join("pyrefly.toml")),
+            ..Default::default()
+        };
+        config.configure();
+        assert_eq!(config.python_interpreter, Some(interpreter.clone()));
+        assert_eq!(
+            config.python_environment.site_package_path,
+            Some(vec![PathBuf::from("/site")])
+        );
+        assert_eq!(
+            config.python_environment.site_package_path_source,
+            SitePackagePathSource::CondaEnvironment(root.join(".conda"))
+        );
+
+        // An interpreter that was asked for is reported as such, even if it's in a conda environment.
+        let mut config = ConfigFile {
+            python_interpreter: Some(interpreter.clone()),
+            ..Default::default()
+        };
+        config.configure();
+        assert_eq!(
+            config.python_environment.site_package_path_source,
+            SitePackagePathSource::Interpreter(interpreter)
+        );
+    }
 }
@@ -22,7 +22,8 @@ impl ActiveEnvironment {
             return Some(env);
         }
 
-        // TODO(connernilsen): also handle $CONDA_PREFIX
+        // `$CONDA_PREFIX` is only checked after the project's own environments, since conda often
+        // activates its `base` environment in every shell. See `PythonEnvironment::find_interpreter`.
 
         None
     }
@@ -0,0 +1,300 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::env;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Finds conda environments, either by name, from a project's `environment.yml`,
+/// or from a project-local `.conda/` prefix.
+pub struct Conda {}
+
+impl Conda {
+    /// Every conda environment prefix contains this directory.
+    const META_DIR: &str = "conda-meta";
+    /// The prefix created by `conda create --prefix .conda`, which some tools use by default.
+    const PROJECT_PREFIX: &str = ".conda";
+    const ENVIRONMENT_FILES: &[&str] = &["environment.yml", "environment.yaml"];
+    /// Directories conda is commonly installed into, relative to the user's home directory.
+    const HOME_INSTALLS: &[&str] = &["miniconda3", "anaconda3", "miniforge3", "mambaforge"];
+
+    /// Is `prefix` the root of a conda environment?
+    pub fn is_prefix(prefix: &Path) -> bool {
+        prefix.join(Self::META_DIR).is_dir()
+    }
+
+    /// The Python interpreter installed in the conda environment at `prefix`, if any.
+    pub fn interpreter(prefix: &Path) -> Option<PathBuf> {
+        let candidates = if cfg!(windows) {
+            vec![prefix.join("python.exe")]
+        } else {
+            vec![prefix.join("bin/python3"), prefix.join("bin/python")]
+        };
+        candidates.into_iter().find(|p| p.is_file())
+    }
+
+    /// The prefix of the conda environment `interpreter` is installed in, if it is in one.
+    pub fn prefix_of(interpreter: &Path) -> Option<&Path> {
+        let parent = interpreter.parent()?;
+        let prefix = if cfg!(windows) {
+            parent
+        } else {
+            parent.parent()?
+        };
+        Self::is_prefix(prefix).then_some(prefix)
+    }
+
+    /// Find the interpreter of the activated conda environment, at `$CONDA_PREFIX`.
+    pub fn find_active() -> Option<PathBuf> {
+        Self::find_at(Path::new(&env::var_os("CONDA_PREFIX")?))
+    }
+
+    /// The interpreter of the conda environment at `prefix`, if it is one.
+    fn find_at(prefix: &Path) -> Option<PathBuf> {
+        if !Self::is_prefix(prefix) {
+            return None;
+        }
+        Self::interpreter(prefix)
+    }
+
+    /// Find the interpreter of the conda environment named `name`.
+    pub fn find_named(name: &str) -> Option<PathBuf> {
+        Self::find_named_in(name, &Self::roots(), &Self::envs_dirs())
+    }
+
+    /// Find the interpreter of a conda environment belonging to the project at `project_path`:
+    /// a `.conda/` prefix, or else the environment named in its `environment.yml`.
+    pub fn find(project_path: &Path) -> Option<PathBuf> {
+        Self::find_at(&project_path.join(Self::PROJECT_PREFIX))
+            .or_else(|| Self::find_named(&Self::environment_file_name(project_path)?))
+    }
+
+    fn find_named_in(name: &str, roots: &[PathBuf], envs_dirs: &[PathBuf]) -> Option<PathBuf> {
+        let prefixes = if name == "base" {
+            roots.to_vec()
+        } else {
+            envs_dirs.iter().map(|dir| dir.join(name)).collect()
+        };
+        prefixes
+            .into_iter()
+            .filter(|prefix| Self::is_prefix(prefix))
+            .find_map(|prefix| Self::interpreter(&prefix))
+    }
+
+    /// The `name` of the environment declared in the project's `environment.yml`.
+    fn environment_file_name(project_path: &Path) -> Option<String> {
+        Self::ENVIRONMENT_FILES.iter().find_map(|file| {
+            let contents = std::fs::read_to_string(project_path.join(file)).ok()?;
+            Self::parse_environment_name(&contents)
+        })
+    }
+
+    /// Read the top-level `name:` key of an `environment.yml`, without a full YAML parser.
+    fn parse_environment_name(contents: &str) -> Option<String> {
+        contents.lines().find_map(|line| {
+            let value = line.strip_prefix("name:")?;
+            let value = value.split(" #").next().unwrap_or_default().trim();
+            let value = value.trim_matches(|c| c == '"' || c == '\'');
+            (!value.is_empty()).then(|| value.to_owned())
+        })
+    }
+
+    fn home() -> Option<PathBuf> {
+        env::var_os("HOME")
+            .or_else(|| env::var_os("USERPROFILE"))
+            .map(PathBuf::from)
+    }
+
+    /// Possible conda installation roots, which are also the prefix of the `base` environment.
+    fn roots() -> Vec<PathBuf> {
+        let mut roots = Vec::new();
+        if let Some(root) = env::var_os("CONDA_ROOT") {
+            roots.push(PathBuf::from(root));
+        }
+        // `$CONDA_EXE` is `<root>/bin/conda`, or `<root>\Scripts\conda.exe` on Windows.
+        if let Some(exe) = env::var_os("CONDA_EXE")
+            && let Some(root) = Path::new(&exe).parent().and_then(|p| p.parent())
+        {
+            roots.push(root.to_path_buf());
+        }
+        if let Some(prefix) = env::var_os("CONDA_PREFIX") {
+            roots.push(Self::root_of(Path::new(&prefix)).to_path_buf());
+        }
+        if let Some(home) = Self::home() {
+            roots.extend(Self::HOME_INSTALLS.iter().map(|dir| home.join(dir)));
+        }
+        roots
+    }
+
+    /// The conda installation an activated environment at `prefix` belongs to. A named
+    /// environment lives in `<root>/envs/<name>`, while `base` is the root itself.
+    fn root_of(prefix: &Path) -> &Path {
+        match prefix.parent() {
+            Some(envs) if envs.file_name().is_some_and(|n| n == "envs") => {
+                envs.parent().unwrap_or(prefix)
+            }
+            _ => prefix,
+        }
+    }
+
+    /// Directories that named conda environments are created in.
+    fn envs_dirs() -> Vec<PathBuf> {
+        let mut dirs = Vec::new();
+        for var in ["CONDA_ENVS_PATH", "CONDA_ENVS_DIRS"] {
+            if let Some(paths) = env::var_os(var) {
+                dirs.extend(env::split_paths(&paths));
+            }
+        }
+        dirs.extend(Self::roots().into_iter().map(|root| root.join("envs")));
+        if let Some(home) = Self::home() {
+            dirs.push(home.join(".conda/envs"));
+        }
+        dirs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pyrefly_util::test_path::TestPath;
+
+    use super::*;
+
+    fn conda_prefix(name: &str) -> TestPath {
+        let interpreter = if cfg!(windows) {
+            TestPath::file("python.exe")
+        } else {
+            TestPath::dir("bin", vec![TestPath::file("python3")])
+        };
+        TestPath::dir(name, vec![TestPath::dir("conda-meta", vec![]), interpreter])
+    }
+
+    fn interpreter_in(prefix: &Path) -> PathBuf {
+        if cfg!(windows) {
+            prefix.join("python.exe")
+        } else {
+            prefix.join("bin/python3")
+        }
+    }
+
+    #[test]
+    fn test_find_project_prefix() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        TestPath::setup_test_directory(
+            root,
+            vec![TestPath::file("pyrefly.toml"), conda_prefix(".conda")],
+        );
+
+        let interpreter = Conda::find(root);
+        assert_eq!(interpreter, Some(interpreter_in(&root.join(".conda"))));
+        assert_eq!(
+            Conda::prefix_of(interpreter.as_deref().unwrap()),
+            Some(root.join(".conda").as_path())
+        );
+    }
+
+    #[test]
+    fn test_find_not_a_conda_prefix() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        TestPath::setup_test_directory(
+            root,
+            vec![TestPath::dir(
+                ".conda",
+                vec![TestPath::dir("bin", vec![TestPath::file("python3")])],
+            )],
+        );
+
+        assert_eq!(Conda::find(root), None);
+        assert_eq!(
+            Conda::prefix_of(&interpreter_in(&root.join(".conda"))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_at() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        TestPath::setup_test_directory(
+            root,
+            vec![
+                conda_prefix("myenv"),
+                TestPath::dir(
+                    "venv",
+                    vec![TestPath::dir("bin", vec![TestPath::file("python3")])],
+                ),
+            ],
+        );
+
+        assert_eq!(
+            Conda::find_at(&root.join("myenv")),
+            Some(interpreter_in(&root.join("myenv")))
+        );
+        assert_eq!(Conda::find_at(&root.join("venv")), None);
+        assert_eq!(Conda::find_at(&root.join("missing")), None);
+    }
+
+    #[test]
+    fn test_root_of() {
+        assert_eq!(
+            Conda::root_of(Path::new("/opt/miniconda3/envs/myenv")),
+            Path::new("/opt/miniconda3")
+        );
+        assert_eq!(
+            Conda::root_of(Path::new("/opt/miniconda3")),
+            Path::new("/opt/miniconda3")
+        );
+        assert_eq!(
+            Conda::root_of(Path::new("/work/.conda")),
+            Path::new("/work/.conda")
+        );
+    }
+
+    #[test]
+    fn test_find_named() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        TestPath::setup_test_directory(
+            root,
+            vec![TestPath::dir(
+                "miniconda3",
+                vec![
+                    TestPath::dir("conda-meta", vec![]),
+                    TestPath::dir("envs", vec![conda_prefix("myenv")]),
+                ],
+            )],
+        );
+        let roots = vec![root.join("missing"), root.join("miniconda3")];
+        let envs_dirs = roots.iter().map(|r| r.join("envs")).collect::<Vec<_>>();
+
+        assert_eq!(
+            Conda::find_named_in("myenv", &roots, &envs_dirs),
+            Some(interpreter_in(&root.join("miniconda3/envs/myenv")))
+        );
+        assert_eq!(Conda::find_named_in("other", &roots, &envs_dirs), None);
+        // `base` has no interpreter in this layout.
+        assert_eq!(Conda::find_named_in("base", &roots, &envs_dirs), None);
+    }
+
+    #[test]
+    fn test_parse_environment_name() {
+        assert_eq!(
+            Conda::parse_environment_name("name: myenv\nchannels:\n  - conda-forge\n"),
+            Some("myenv".to_owned())
+        );
+        assert_eq!(
+            Conda::parse_environment_name("# comment\nname: \"quoted\" # trailing\n"),
+            Some("quoted".to_owned())
+        );
+        assert_eq!(
+            Conda::parse_environment_name("dependencies:\n  - name: nested\n"),
+            None
+        );
+        assert_eq!(Conda::parse_environment_name("name:\n"), None);
+    }
+}
@@ -25,6 +25,7 @@ use tracing::warn;
 use which::which;
 
 use crate::config::environment::active_environment::ActiveEnvironment;
+use crate::config::environment::conda::Conda;
 use crate::config::environment::venv::Venv;
 use crate::sys_info::PythonPlatform;
 use crate::sys_info::PythonVersion;
@@ -38,6 +39,8 @@ pub enum SitePackagePathSource {
     ConfigFile,
     CommandLine,
     Interpreter(PathBuf),
+    /// Queried from the interpreter of the conda environment at this prefix.
+    CondaEnvironment(PathBuf),
 }
 
 impl Display for SitePackagePathSource {
@@ -48,6 +51,13 @@ impl Display for SitePackagePathSource {
             Self::Interpreter(path) => {
                 write!(f, "queried from interpreter at `{}`", path.display())
             }
+            Self::CondaEnvironment(prefix) => {
+                write!(
+                    f,
+                    "queried from conda environment at `{}`",
+                    prefix.display()
+                )
+            }
         }
     }
 }
@@ -60,9 +70,65 @@ impl Display for SitePackagePathSource {
 /// other than the first available on the path should be used (i.e.
 /// should we always look at a venv/conda environment instead?)
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone, Default)]
-#[serde(rename_all = "kebab-case")]
+#[serde(try_from = "RawPythonEnvironment", into = "RawPythonEnvironment")]
 pub struct PythonEnvironment {
     /// The platform any `sys.platform` check should evaluate against.
+    pub python_platform: Option<PythonPlatform>,
+
+    /// The platform any `sys.version` check should evaluate against.
+    pub python_version: Option<PythonVersion>,
+
+    /// Directories containing third-party package imports, searched
+    /// after first checking `search_path` and `typeshed`.
+    pub site_package_path: Option<Vec<PathBuf>>,
+
+    /// Is the `site_package_path` here one we got from
+    /// querying an interpreter?
+    pub site_package_path_source: SitePackagePathSource,
+
+    /// Every platform to check against, when `python-platform` is a list of more than one.
+    /// `python_platform` is the first of them, and is used wherever only one is needed.
+    pub python_platform_matrix: Vec<PythonPlatform>,
+
+    /// Every version to check against, when `python-version` is a list of more than one.
+    /// `python_version` is the first of them, and is used wherever only one is needed.
+    pub python_version_matrix: Vec<PythonVersion>,
+}
+
+/// A value that can be given on its own, or as a list of values to check against in turn.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T: Clone> OneOrMany<T> {
+    fn new(first: Option<T>, matrix: Vec<T>) -> Option<Self> {
+        if matrix.is_empty() {
+            first.map(Self::One)
+        } else {
+            Some(Self::Many(matrix))
+        }
+    }
+
+    /// Split into the first value and, if there is more than one, all of them.
+    fn split(self, name: &str) -> anyhow::Result<(T, Vec<T>)> {
+        match self {
+            Self::One(x) => Ok((x, Vec::new())),
+            Self::Many(xs) => match xs.as_slice() {
+                [] => Err(anyhow!("`{name}` must not be an empty list")),
+                [x] => Ok((x.clone(), Vec::new())),
+                [x, ..] => Ok((x.clone(), xs)),
+            },
+        }
+    }
+}
+
+/// The serialized form of [`PythonEnvironment`].
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawPythonEnvironment {
     #[serde(
         default,
         skip_serializing_if = "Option::is_none",
@@ -70,9 +136,8 @@ pub struct PythonEnvironment {
         // alias while we migrate existing fields from snake case to kebab case.
         alias = "python_platform"
     )]
-    pub python_platform: Option<PythonPlatform>,
+    python_platform: Option<OneOrMany<PythonPlatform>>,
 
-    /// The platform any `sys.version` check should evaluate against.
     #[serde(
         default,
         skip_serializing_if = "Option::is_none",
@@ -80,10 +145,8 @@ pub struct PythonEnvironment {
         // alias while we migrate existing fields from snake case to kebab case.
         alias = "python_version"
     )]
-    pub python_version: Option<PythonVersion>,
+    python_version: Option<OneOrMany<PythonVersion>>,
 
-    /// Directories containing third-party package imports, searched
-    /// after first checking `search_path` and `typeshed`.
     #[serde(
         default,
         skip_serializing_if = "Option::is_none",
@@ -91,12 +154,46 @@ pub struct PythonEnvironment {
         // alias while we migrate existing fields from snake case to kebab case.
         alias = "site_package_path"
     )]
-    pub site_package_path: Option<Vec<PathBuf>>,
+    site_package_path: Option<Vec<PathBuf>>,
+}
 
-    /// Is the `site_package_path` here one we got from
-    /// querying an interpreter?
-    #[serde(skip, default)]
-    pub site_package_path_source: SitePackagePathSource,
+impl TryFrom<RawPythonEnvironment> for PythonEnvironment {
+    type Error = anyhow::Error;
+
+    fn try_from(raw: RawPythonEnvironment) -> anyhow::Result<Self> {
+        let (python_platform, python_platform_matrix) = match raw.python_platform {
+            Some(x) => {
+                let (first, matrix) = x.split("python-platform")?;
+                (Some(first), matrix)
+            }
+            None => (None, Vec::new()),
+        };
+        let (python_version, python_version_matrix) = match raw.python_version {
+            Some(x) => {
+                let (first, matrix) = x.split("python-version")?;
+                (Some(first), matrix)
+            }
+            None => (None, Vec::new()),
+        };
+        Ok(Self {
+            python_platform,
+            python_version,
+            site_package_path: raw.site_package_path,
+            site_package_path_source: SitePackagePathSource::default(),
+            python_platform_matrix,
+            python_version_matrix,
+        })
+    }
+}
+
+impl From<PythonEnvironment> for RawPythonEnvironment {
+    fn from(env: PythonEnvironment) -> Self {
+        Self {
+            python_platform: OneOrMany::new(env.python_platform, env.python_platform_matrix),
+            python_version: OneOrMany::new(env.python_version, env.python_version_matrix),
+            site_package_path: env.site_package_path,
+        }
+    }
 }
 
 impl PythonEnvironment {
@@ -108,6 +205,26 @@ impl PythonEnvironment {
         env
     }
 
+    /// Set the platforms to check against, where the first is used wherever only one is needed.
+    pub fn set_python_platforms(&mut self, platforms: Vec<PythonPlatform>) {
+        self.python_platform = platforms.first().cloned();
+        self.python_platform_matrix = if platforms.len() > 1 {
+            platforms
+        } else {
+            Vec::new()
+        };
+    }
+
+    /// Set the versions to check against, where the first is used wherever only one is needed.
+    pub fn set_python_versions(&mut self, versions: Vec<PythonVersion>) {
+        self.python_version = versions.first().copied();
+        self.python_version_matrix = if versions.len() > 1 {
+            versions
+        } else {
+            Vec::new()
+        };
+    }
+
     /// Are any Python environment values `None`?
     pub fn any_empty(&self) -> bool {
         self.python_platform.is_none()
@@ -268,6 +385,13 @@ print(json.dumps({'python_platform': platform, 'python_version': version, 'site_
             if venv.is_some() {
                 return venv;
             }
+            let conda = Conda::find(start_path);
+            if conda.is_some() {
+                return conda;
+            }
+        }
+        if let Some(active_conda) = Conda::find_active() {
+            return Some(active_conda);
         }
         Self::get_default_interpreter().map(|p| p.to_path_buf())
     }
@@ -9,27 +9,111 @@ use std::collections::HashMap;
 
 use serde::Deserialize;
 use serde::Serialize;
+use starlark_map::small_set::SmallSet;
 
+use crate::config::base::ConfigBase;
 use crate::error::kind::ErrorKind;
+use crate::error::kind::Severity;
+
+/// The severity an error kind is reported at, as set in the `errors` table.
+// Listed from least to most severe, so that settings can be combined by taking the maximum.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Deserialize,
+    Serialize
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorSeverity {
+    /// Don't report the error.
+    Ignore,
+    Info,
+    Warn,
+    Error,
+}
+
+impl ErrorSeverity {
+    /// The severity to report at, or `None` if the error shouldn't be reported.
+    pub fn to_severity(self) -> Option<Severity> {
+        match self {
+            Self::Ignore => None,
+            Self::Info => Some(Severity::Info),
+            Self::Warn => Some(Severity::Warn),
+            Self::Error => Some(Severity::Error),
+        }
+    }
+}
+
+/// The setting for an error kind in the `errors` table. Either a boolean, where `true` reports the
+/// error at its default severity and `false` doesn't report it, or a severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ErrorSetting {
+    Enabled(bool),
+    Severity(ErrorSeverity),
+}
+
+impl ErrorSetting {
+    /// The severity to report errors of `kind` at, or `None` if they shouldn't be reported.
+    pub fn severity(self, kind: ErrorKind) -> Option<Severity> {
+        match self {
+            Self::Enabled(true) => Some(kind.severity()),
+            Self::Enabled(false) => None,
+            Self::Severity(x) => x.to_severity(),
+        }
+    }
+}
+
+impl From<bool> for ErrorSetting {
+    fn from(value: bool) -> Self {
+        Self::Enabled(value)
+    }
+}
+
+impl From<ErrorSeverity> for ErrorSetting {
+    fn from(value: ErrorSeverity) -> Self {
+        Self::Severity(value)
+    }
+}
 
 /// Represents overrides for errors to emit when collecting/printing errors.
-/// The boolean in the map represents whether the error is enabled or disabled
-/// (true = show error, false = don't show error).
+/// Each error kind may be enabled or disabled (`true` = show error, `false` = don't show error),
+/// or given a severity of `"error"`, `"warn"`, `"info"` or `"ignore"`.
 /// Not all error kinds are required to be defined in this map. Any that are missing
 /// will be treated as `<error-kind> = true`.
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone, Default)]
 #[serde(transparent)]
-pub struct ErrorDisplayConfig(HashMap<ErrorKind, bool>);
+pub struct ErrorDisplayConfig(HashMap<ErrorKind, ErrorSetting>);
 
 impl ErrorDisplayConfig {
-    pub fn new(config: HashMap<ErrorKind, bool>) -> Self {
-        Self(config)
+    pub fn new<T: Into<ErrorSetting>>(config: HashMap<ErrorKind, T>) -> Self {
+        Self(config.into_iter().map(|(k, v)| (k, v.into())).collect())
+    }
+
+    /// Gets the setting for the given `ErrorKind`, if there is one.
+    pub fn get(&self, kind: ErrorKind) -> Option<ErrorSetting> {
+        self.0.get(&kind).copied()
+    }
+
+    /// Gets the severity the given `ErrorKind` is reported at, or `None` if it is disabled.
+    /// If the value isn't found, then use the default severity of the kind.
+    pub fn severity(&self, kind: ErrorKind) -> Option<Severity> {
+        match self.get(kind) {
+            None => Some(kind.severity()),
+            Some(x) => x.severity(kind),
+        }
     }
 
     /// Gets whether the given `ErrorKind` is enabled. If the value isn't
     /// found, then assume it should be enabled.
     pub fn is_enabled(&self, kind: ErrorKind) -> bool {
-        self.0.get(&kind) != Some(&false)
+        self.severity(kind).is_some()
     }
 }
 
@@ -37,16 +121,112 @@ impl ErrorDisplayConfig {
 pub struct ErrorConfig<'a> {
     pub display_config: &'a ErrorDisplayConfig,
     pub ignore_errors_in_generated_code: bool,
+    /// Whether to report the error kinds that are only enabled in strict mode.
+    pub strict: bool,
 }
 
 impl<'a> ErrorConfig<'a> {
     pub fn new(
         display_config: &'a ErrorDisplayConfig,
         ignore_errors_in_generated_code: bool,
+        strict: bool,
     ) -> Self {
         Self {
             display_config,
             ignore_errors_in_generated_code,
+            strict,
         }
     }
 }
+
+/// The opt-in error kinds (see `ErrorKind::is_opt_in`) that are reported for a module, taking its
+/// inline config into account. We don't look for opt-in errors that nobody will see.
+#[derive(Debug, Clone, Default)]
+pub struct OptInErrors(SmallSet<ErrorKind>);
+
+impl OptInErrors {
+    pub fn new(error_config: &ErrorConfig, inline_config: &ConfigBase) -> Self {
+        let strict = inline_config.strict.unwrap_or(error_config.strict);
+        Self(
+            enum_iterator::all::<ErrorKind>()
+                .filter(|kind| {
+                    if !kind.is_opt_in() {
+                        return false;
+                    }
+                    let setting = inline_config
+                        .errors
+                        .as_ref()
+                        .and_then(|x| x.get(*kind))
+                        .or_else(|| error_config.display_config.get(*kind));
+                    match setting {
+                        Some(x) => x.severity(*kind).is_some(),
+                        None => strict && kind.is_strict(),
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Whether we should look for errors of the given `ErrorKind`. Always true for kinds that
+    /// aren't opt-in.
+    pub fn is_enabled(&self, kind: ErrorKind) -> bool {
+        !kind.is_opt_in() || self.0.contains(&kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_display_config_severity() {
+        let config: ErrorDisplayConfig = toml::from_str(
+            r#"
+            bad-return = "warn"
+            bad-assignment = "ignore"
+            reveal-type = "error"
+            import-error = false
+            deprecated = true
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.severity(ErrorKind::BadReturn), Some(Severity::Warn));
+        assert_eq!(config.severity(ErrorKind::BadAssignment), None);
+        assert_eq!(
+            config.severity(ErrorKind::RevealType),
+            Some(Severity::Error)
+        );
+        assert_eq!(config.severity(ErrorKind::ImportError), None);
+        assert_eq!(config.severity(ErrorKind::Deprecated), Some(Severity::Warn));
+        assert_eq!(
+            config.severity(ErrorKind::BadOverride),
+            Some(Severity::Error)
+        );
+        assert!(!config.is_enabled(ErrorKind::BadAssignment));
+        assert!(config.is_enabled(ErrorKind::BadReturn));
+        assert!(toml::from_str::<ErrorDisplayConfig>("bad-return = \"fatal\"").is_err());
+    }
+
+    #[test]
+    fn test_opt_in_errors() {
+        let display_config = ErrorDisplayConfig::new(HashMap::from([
+            (ErrorKind::ImplicitAny, false),
+            (ErrorKind::UnusedImport, true),
+        ]));
+        let strict = ErrorConfig::new(&display_config, false, true);
+        let opt_in = OptInErrors::new(&strict, &ConfigBase::default());
+        assert!(opt_in.is_enabled(ErrorKind::BadReturn));
+        assert!(opt_in.is_enabled(ErrorKind::UntypedImport));
+        assert!(opt_in.is_enabled(ErrorKind::UnusedImport));
+        assert!(!opt_in.is_enabled(ErrorKind::ImplicitAny));
+        assert!(!opt_in.is_enabled(ErrorKind::UnusedVariable));
+
+        let inline_config = ConfigBase {
+            strict: Some(false),
+            ..Default::default()
+        };
+        let opt_in = OptInErrors::new(&strict, &inline_config);
+        assert!(!opt_in.is_enabled(ErrorKind::UntypedImport));
+        assert!(opt_in.is_enabled(ErrorKind::UnusedImport));
+    }
+}
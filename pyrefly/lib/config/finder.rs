@@ -70,6 +70,10 @@ impl ConfigError {
         }
     }
 
+    pub fn msg(&self) -> String {
+        format!("{:#}", self.msg)
+    }
+
     pub fn severity(&self) -> Severity {
         self.severity
     }
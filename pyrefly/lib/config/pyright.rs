@@ -18,6 +18,7 @@ use crate::config::base::ConfigBase;
 use crate::config::config::ConfigFile;
 use crate::config::config::SubConfig;
 use crate::config::error::ErrorDisplayConfig;
+use crate::config::error::ErrorSeverity;
 use crate::error::kind::ErrorKind;
 use crate::sys_info::PythonPlatform;
 use crate::sys_info::PythonVersion;
@@ -105,20 +106,16 @@ pub enum DiagnosticLevel {
 }
 
 impl DiagnosticLevel {
-    fn to_bool(&self) -> bool {
+    fn to_severity(&self) -> ErrorSeverity {
         match self {
-            Self::None => false,
-            _ => true,
+            Self::None => ErrorSeverity::Ignore,
+            Self::Information => ErrorSeverity::Info,
+            Self::Warning => ErrorSeverity::Warn,
+            Self::Error => ErrorSeverity::Error,
         }
     }
 }
 
-impl From<DiagnosticLevel> for bool {
-    fn from(value: DiagnosticLevel) -> Self {
-        value.to_bool()
-    }
-}
-
 #[derive(Deserialize)]
 #[serde(untagged)]
 pub enum DiagnosticLevelOrBool {
@@ -127,17 +124,19 @@ pub enum DiagnosticLevelOrBool {
 }
 
 impl DiagnosticLevelOrBool {
-    fn to_bool(&self) -> bool {
+    /// In pyright, `true` is the same as `"error"` and `false` is the same as `"none"`.
+    fn to_severity(&self) -> ErrorSeverity {
         match self {
-            Self::DiagnosticLevel(dl) => dl.to_bool(),
-            Self::Bool(b) => *b,
+            Self::DiagnosticLevel(dl) => dl.to_severity(),
+            Self::Bool(true) => ErrorSeverity::Error,
+            Self::Bool(false) => ErrorSeverity::Ignore,
         }
     }
 }
 
-impl From<DiagnosticLevelOrBool> for bool {
+impl From<DiagnosticLevelOrBool> for ErrorSeverity {
     fn from(value: DiagnosticLevelOrBool) -> Self {
-        value.to_bool()
+        value.to_severity()
     }
 }
 
@@ -149,10 +148,10 @@ impl From<DiagnosticLevelOrBool> for bool {
 pub struct RuleOverrides {
     #[serde_as(as = "Option<FromInto<DiagnosticLevelOrBool>>")]
     #[serde(default)]
-    report_missing_imports: Option<bool>,
+    report_missing_imports: Option<ErrorSeverity>,
     #[serde_as(as = "Option<FromInto<DiagnosticLevelOrBool>>")]
     #[serde(default)]
-    report_missing_module_source: Option<bool>,
+    report_missing_module_source: Option<ErrorSeverity>,
 }
 
 impl RuleOverrides {
@@ -161,14 +160,14 @@ impl RuleOverrides {
         let mut map = HashMap::new();
         // For each ErrorKind, there are one or more RuleOverrides fields.
         // The ErrorDisplayConfig map has an entry for an ErrorKind if at least one of the RuleOverrides for that ErrorKind is present.
-        // The value of that ErrorKind's entry is found by or'ing together the present RuleOverrides.
+        // The value of that ErrorKind's entry is the most severe of the present RuleOverrides.
         if let Some(import_error) = [
             self.report_missing_imports,
             self.report_missing_module_source,
         ]
         .into_iter()
         .flatten()
-        .reduce(|acc, x| acc | x)
+        .max()
         {
             map.insert(ErrorKind::ImportError, import_error);
         }
@@ -210,6 +209,7 @@ mod tests {
     use super::*;
     use crate::config::environment::environment::PythonEnvironment;
     use crate::config::environment::environment::SitePackagePathSource;
+    use crate::error::kind::Severity;
 
     #[test]
     fn test_convert_pyright_config() -> anyhow::Result<()> {
@@ -245,6 +245,7 @@ mod tests {
                     python_version: Some(PythonVersion::new(3, 10, 0)),
                     site_package_path: None,
                     site_package_path_source: SitePackagePathSource::ConfigFile,
+                    ..Default::default()
                 },
                 ..Default::default()
             }
@@ -277,6 +278,7 @@ mod tests {
                     python_platform: None,
                     site_package_path: None,
                     site_package_path_source: SitePackagePathSource::ConfigFile,
+                    ..Default::default()
                 },
                 ..Default::default()
             }
@@ -330,6 +332,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_report_diagnostics_severity() -> anyhow::Result<()> {
+        let raw_file = r#"
+            {
+                "reportMissingImports": "information",
+                "reportMissingModuleSource": "warning"
+            }
+            "#;
+        let pyr = serde_json::from_str::<PyrightConfig>(raw_file)?;
+        let config = pyr.convert();
+        assert_eq!(
+            config.root.errors.unwrap().severity(ErrorKind::ImportError),
+            Some(Severity::Warn)
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_exec_env() -> anyhow::Result<()> {
         let raw_file = r#"
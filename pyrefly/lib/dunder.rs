@@ -17,6 +17,7 @@ pub const DATACLASS_FIELDS: Name = Name::new_static("__dataclass_fields__");
 pub const DEBUG: Name = Name::new_static("__debug__");
 pub const DELATTR: Name = Name::new_static("__delattr__");
 pub const DELITEM: Name = Name::new_static("__delitem__");
+pub const DICT: Name = Name::new_static("__dict__");
 pub const DOC: Name = Name::new_static("__doc__");
 pub const ENTER: Name = Name::new_static("__enter__");
 pub const EQ: Name = Name::new_static("__eq__");
@@ -45,10 +46,16 @@ pub const POS: Name = Name::new_static("__pos__");
 pub const SET: Name = Name::new_static("__set__");
 pub const SETATTR: Name = Name::new_static("__setattr__");
 pub const SETITEM: Name = Name::new_static("__setitem__");
+pub const SLOTS: Name = Name::new_static("__slots__");
 pub const BOOL: Name = Name::new_static("__bool__");
+pub const WEAKREF: Name = Name::new_static("__weakref__");
 
 pub const RICH_CMPS: &[Name] = &[LT, LE, EQ, NE, GT, GE];
 
+/// The ordering methods that `functools.total_ordering` can fill in, in the order of preference
+/// used at runtime when picking the method to derive the others from.
+pub const RICH_CMPS_TOTAL_ORDERING: &[Name] = &[LT, LE, GT, GE];
+
 /// Returns the associated dunder if `op` corresponds to a "rich comparison method":
 /// https://docs.python.org/3/reference/datamodel.html#object.__lt__.
 pub fn rich_comparison_dunder(op: CmpOp) -> Option<Name> {
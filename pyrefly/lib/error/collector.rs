@@ -10,16 +10,24 @@ use std::mem;
 
 use dupe::Dupe;
 use pyrefly_util::lock::Mutex;
+use ruff_source_file::OneIndexed;
 use ruff_text_size::TextRange;
+use starlark_map::small_map::SmallMap;
 use vec1::Vec1;
+use vec1::vec1;
 
 use crate::config::error::ErrorConfig;
+use crate::config::error::OptInErrors;
 use crate::error::context::ErrorContext;
 use crate::error::error::Error;
 use crate::error::kind::ErrorKind;
+use crate::error::payload::ErrorPayload;
 use crate::error::style::ErrorStyle;
+use crate::module::ignore::SuppressionKind;
+use crate::module::ignore::UnusedIgnore;
 use crate::module::module_info::ModuleInfo;
 use crate::module::module_info::SourceRange;
+use crate::module::module_info::TextRangeWithModuleInfo;
 
 #[derive(Debug, Default, Clone)]
 struct ModuleErrors {
@@ -118,21 +126,46 @@ impl ErrorCollector {
     }
 
     pub fn add(
+        &self,
+        range: TextRange,
+        kind: ErrorKind,
+        context: Option<&dyn Fn() -> ErrorContext>,
+        msg: Vec1<String>,
+    ) {
+        self.add_detailed(
+            range,
+            kind,
+            context,
+            msg,
+            Vec::new(),
+            ErrorPayload::default(),
+        );
+    }
+
+    /// Like `add`, but with secondary lines explaining why the error happened, e.g. the protocol
+    /// member that made a type not assignable to a protocol, and machine-readable data about it.
+    pub fn add_detailed(
         &self,
         range: TextRange,
         kind: ErrorKind,
         context: Option<&dyn Fn() -> ErrorContext>,
         mut msg: Vec1<String>,
+        because: Vec<(String, Option<TextRangeWithModuleInfo>)>,
+        mut payload: ErrorPayload,
     ) {
         if self.style == ErrorStyle::Never {
             return;
         }
         let source_range = self.module_info.source_range(range);
-        let is_ignored = self.module_info.is_ignored(&source_range);
+        let is_ignored = self.module_info.is_ignored(&source_range, kind);
         if let Some(ctx) = context {
-            msg.insert(0, ctx().format());
+            let ctx = ctx();
+            msg.insert(0, ctx.format());
+            payload.add_context(&ctx);
         }
-        let err = Error::new(self.module_info.dupe(), source_range, msg, is_ignored, kind);
+        let err = Error::new(self.module_info.dupe(), source_range, msg, is_ignored, kind)
+            .with_because(because)
+            .with_payload(payload);
         self.errors.lock().push(err);
     }
 
@@ -152,16 +185,109 @@ impl ErrorCollector {
         self.errors.lock().len()
     }
 
+    /// The parts of the `# pyrefly: ignore` comments in this module that don't suppress any of
+    /// `errors`.
+    fn unused_ignores_in<'a>(
+        &self,
+        errors: impl Iterator<Item = &'a Error>,
+        opt_in_errors: &OptInErrors,
+    ) -> SmallMap<OneIndexed, UnusedIgnore> {
+        self.module_info.ignore().unused(
+            errors.map(|err| {
+                let (start, end) = self.module_info.ignore_range(err.source_range());
+                (start, end, err.error_kind())
+            }),
+            |kind| opt_in_errors.is_enabled(kind),
+        )
+    }
+
+    /// The parts of the `# pyrefly: ignore` comments in this module that don't suppress any error.
+    pub fn unused_ignores(&self, error_config: &ErrorConfig) -> SmallMap<OneIndexed, UnusedIgnore> {
+        let opt_in_errors =
+            OptInErrors::new(error_config, self.module_info.inline_config().config());
+        self.unused_ignores_in(self.errors.lock().iter(), &opt_in_errors)
+    }
+
+    /// Warnings for the codes in `# pyrefly: ignore[...]` comments that don't suppress anything,
+    /// either because they aren't error kinds, or because there is no error of that kind on the
+    /// lines the comment covers.
+    fn unused_ignore_codes<'a>(
+        &self,
+        errors: impl Iterator<Item = &'a Error>,
+        opt_in_errors: &OptInErrors,
+    ) -> Vec<Error> {
+        let ignore = self.module_info.ignore();
+        let suppressions = ignore.get_suppressions(SuppressionKind::Pyrefly);
+        if self.style == ErrorStyle::Never || suppressions.values().all(|x| x.codes().is_empty()) {
+            return Vec::new();
+        }
+        let mut res = Vec::new();
+        for (line, unused) in self.unused_ignores_in(errors, opt_in_errors) {
+            let suppression = suppressions[&line];
+            let line_start = self.module_info.line_start(line);
+            for (code, range) in suppression.codes().iter().zip(suppression.code_ranges()) {
+                if let UnusedIgnore::Codes(unused) = &unused
+                    && !unused.contains(code)
+                {
+                    continue;
+                }
+                let msg = match ErrorKind::from_name(code) {
+                    Some(_) => format!("There is no `{code}` error here to ignore"),
+                    None => format!("`{code}` is not an error kind, so it doesn't ignore anything"),
+                };
+                let source_range = self.module_info.source_range(*range + line_start);
+                let is_ignored = self
+                    .module_info
+                    .is_ignored(&source_range, ErrorKind::UnusedIgnore);
+                res.push(Error::new(
+                    self.module_info.dupe(),
+                    source_range,
+                    vec1![msg],
+                    is_ignored,
+                    ErrorKind::UnusedIgnore,
+                ));
+            }
+        }
+        res
+    }
+
     pub fn collect_into(&self, error_config: &ErrorConfig, result: &mut CollectedErrors) {
         let mut errors = self.errors.lock();
-        if !(self.module_info.is_generated() && error_config.ignore_errors_in_generated_code) {
-            for err in errors.iter() {
+        // Settings from comments at the top of the file take precedence over the config file.
+        let inline_config = self.module_info.inline_config().config();
+        let ignore_errors_in_generated_code = inline_config
+            .ignore_errors_in_generated_code
+            .unwrap_or(error_config.ignore_errors_in_generated_code);
+        let strict = inline_config.strict.unwrap_or(error_config.strict);
+        let setting = |kind| {
+            inline_config
+                .errors
+                .as_ref()
+                .and_then(|x| x.get(kind))
+                .or_else(|| error_config.display_config.get(kind))
+        };
+        if !(self.module_info.is_generated() && ignore_errors_in_generated_code) {
+            let opt_in_errors = OptInErrors::new(error_config, inline_config);
+            let unused = self.unused_ignore_codes(errors.iter(), &opt_in_errors);
+            let mut errors = errors.iter().chain(&unused).collect::<Vec<_>>();
+            if !unused.is_empty() {
+                errors.sort_by(|a, b| a.source_range().cmp(b.source_range()));
+            }
+            for err in errors {
+                let kind = err.error_kind();
+                let setting = setting(kind);
+                if setting.is_none() && kind.is_opt_in() && !(strict && kind.is_strict()) {
+                    // Opt-in errors weren't asked for, so they don't count as disabled either.
+                    continue;
+                }
                 if err.is_ignored() {
                     result.suppressed.push(err.clone());
-                } else if !error_config.display_config.is_enabled(err.error_kind()) {
-                    result.disabled.push(err.clone());
+                } else if let Some(severity) =
+                    setting.map_or(Some(kind.severity()), |x| x.severity(kind))
+                {
+                    result.shown.push(err.clone().with_severity(severity));
                 } else {
-                    result.shown.push(err.clone());
+                    result.disabled.push(err.clone());
                 }
             }
         }
@@ -188,6 +314,8 @@ mod tests {
 
     use super::*;
     use crate::config::error::ErrorDisplayConfig;
+    use crate::config::error::ErrorSeverity;
+    use crate::error::kind::Severity;
     use crate::module::module_name::ModuleName;
     use crate::module::module_path::ModulePath;
 
@@ -235,7 +363,11 @@ mod tests {
         );
         assert_eq!(
             errors
-                .collect(&ErrorConfig::new(&ErrorDisplayConfig::default(), false))
+                .collect(&ErrorConfig::new(
+                    &ErrorDisplayConfig::default(),
+                    false,
+                    false
+                ))
                 .shown
                 .map(|x| x.msg()),
             vec!["b", "a", "a"]
@@ -286,7 +418,7 @@ mod tests {
             (ErrorKind::BadAssignment, false),
             (ErrorKind::NotIterable, false),
         ]));
-        let config = ErrorConfig::new(&display_config, false);
+        let config = ErrorConfig::new(&display_config, false, false);
 
         assert_eq!(
             errors.collect(&config).shown.map(|x| x.msg()),
@@ -294,6 +426,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_error_collector_severity() {
+        let mi = ModuleInfo::new(
+            ModuleName::from_name(&Name::new_static("main")),
+            ModulePath::filesystem(Path::new("main.py").to_owned()),
+            Arc::new("contents".to_owned()),
+        );
+        let errors = ErrorCollector::new(mi.dupe(), ErrorStyle::Delayed);
+        add(
+            &errors,
+            TextRange::new(TextSize::new(1), TextSize::new(3)),
+            ErrorKind::BadReturn,
+            "a".to_owned(),
+        );
+        add(
+            &errors,
+            TextRange::new(TextSize::new(2), TextSize::new(3)),
+            ErrorKind::RevealType,
+            "b".to_owned(),
+        );
+        add(
+            &errors,
+            TextRange::new(TextSize::new(3), TextSize::new(4)),
+            ErrorKind::BadAssignment,
+            "c".to_owned(),
+        );
+
+        let display_config = ErrorDisplayConfig::new(HashMap::from([
+            (ErrorKind::BadReturn, ErrorSeverity::Warn),
+            (ErrorKind::RevealType, ErrorSeverity::Error),
+            (ErrorKind::BadAssignment, ErrorSeverity::Ignore),
+        ]));
+        let config = ErrorConfig::new(&display_config, false, false);
+        let collected = errors.collect(&config);
+
+        assert_eq!(
+            collected.shown.map(|x| (x.msg(), x.severity())),
+            vec![
+                ("a".to_owned(), Severity::Warn),
+                ("b".to_owned(), Severity::Error)
+            ]
+        );
+        assert_eq!(collected.disabled.map(|x| x.msg()), vec!["c"]);
+    }
+
     #[test]
     fn test_error_collector_generated_code() {
         let mi = ModuleInfo::new(
@@ -310,13 +487,56 @@ mod tests {
         );
 
         let display_config = ErrorDisplayConfig::default();
-        let config0 = ErrorConfig::new(&display_config, false);
+        let config0 = ErrorConfig::new(&display_config, false, false);
         assert_eq!(errors.collect(&config0).shown.map(|x| x.msg()), vec!["a"]);
 
-        let config1 = ErrorConfig::new(&display_config, true);
+        let config1 = ErrorConfig::new(&display_config, true, false);
         assert!(errors.collect(&config1).shown.map(|x| x.msg()).is_empty());
     }
 
+    #[test]
+    fn test_error_collector_strict() {
+        let mi = ModuleInfo::new(
+            ModuleName::from_name(&Name::new_static("main")),
+            ModulePath::filesystem(Path::new("main.py").to_owned()),
+            Arc::new("contents".to_owned()),
+        );
+        let errors = ErrorCollector::new(mi.dupe(), ErrorStyle::Delayed);
+        add(
+            &errors,
+            TextRange::new(TextSize::new(1), TextSize::new(3)),
+            ErrorKind::ImplicitAny,
+            "a".to_owned(),
+        );
+        add(
+            &errors,
+            TextRange::new(TextSize::new(2), TextSize::new(3)),
+            ErrorKind::MissingReturnAnnotation,
+            "b".to_owned(),
+        );
+
+        let display_config = ErrorDisplayConfig::default();
+        let config = ErrorConfig::new(&display_config, false, false);
+        let collected = errors.collect(&config);
+        assert!(collected.shown.is_empty());
+        assert!(collected.disabled.is_empty());
+        let config = ErrorConfig::new(&display_config, false, true);
+        assert_eq!(
+            errors.collect(&config).shown.map(|x| x.msg()),
+            vec!["a", "b"]
+        );
+
+        // Explicit settings win over strict mode, either way.
+        let display_config = ErrorDisplayConfig::new(HashMap::from([
+            (ErrorKind::ImplicitAny, true),
+            (ErrorKind::MissingReturnAnnotation, false),
+        ]));
+        let config = ErrorConfig::new(&display_config, false, false);
+        assert_eq!(errors.collect(&config).shown.map(|x| x.msg()), vec!["a"]);
+        let config = ErrorConfig::new(&display_config, false, true);
+        assert_eq!(errors.collect(&config).shown.map(|x| x.msg()), vec!["a"]);
+    }
+
     #[test]
     fn test_errors_not_sorted() {
         let mi = ModuleInfo::new(
@@ -339,7 +559,11 @@ mod tests {
         );
         assert_eq!(
             errors
-                .collect(&ErrorConfig::new(&ErrorDisplayConfig::default(), false))
+                .collect(&ErrorConfig::new(
+                    &ErrorDisplayConfig::default(),
+                    false,
+                    false
+                ))
                 .shown
                 .map(|x| x.msg()),
             vec!["Overload", "A specific error"]
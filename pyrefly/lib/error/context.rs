@@ -9,8 +9,10 @@ use ruff_python_ast::name::Name;
 
 use crate::binding::binding::AnnotationTarget;
 use crate::error::kind::ErrorKind;
+use crate::module::module_info::TextRangeWithModuleInfo;
 use crate::module::module_name::ModuleName;
 use crate::types::callable::FuncId;
+use crate::types::type_var::Variance;
 use crate::types::types::Type;
 
 /// General context for an error. For many errors, the root cause is some steps removed from what
@@ -48,6 +50,45 @@ pub enum ErrorContext {
     ImportNotFound(ModuleName),
 }
 
+impl ErrorContext {
+    /// The name of the variant, for machine-readable output.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::BadContextManager(..) => "BadContextManager",
+            Self::UnaryOp(..) => "UnaryOp",
+            Self::BinaryOp(..) => "BinaryOp",
+            Self::InplaceBinaryOp(..) => "InplaceBinaryOp",
+            Self::Iteration(..) => "Iteration",
+            Self::AsyncIteration(..) => "AsyncIteration",
+            Self::Await(..) => "Await",
+            Self::Index(..) => "Index",
+            Self::SetItem(..) => "SetItem",
+            Self::DelItem(..) => "DelItem",
+            Self::MatchPositional(..) => "MatchPositional",
+            Self::ImportNotFound(..) => "ImportNotFound",
+        }
+    }
+
+    /// The types involved in the context, e.g. the operands of a binary operation.
+    pub fn types(&self) -> Vec<&Type> {
+        match self {
+            Self::BadContextManager(ty)
+            | Self::UnaryOp(_, ty)
+            | Self::Iteration(ty)
+            | Self::AsyncIteration(ty)
+            | Self::Await(ty)
+            | Self::Index(ty)
+            | Self::SetItem(ty)
+            | Self::DelItem(ty)
+            | Self::MatchPositional(ty) => vec![ty],
+            Self::BinaryOp(_, left, right) | Self::InplaceBinaryOp(_, left, right) => {
+                vec![left, right]
+            }
+            Self::ImportNotFound(_) => Vec::new(),
+        }
+    }
+}
+
 /// The context in which a got <: want type check occurs. This differs from ErrorContext in that
 /// TypeCheckContext applies specifically to type mismatches. For example:
 ///   class C:
@@ -138,6 +179,34 @@ impl TypeCheckKind {
         }
     }
 
+    /// The name of the variant, for machine-readable output.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::MagicMethodReturn(..) => "MagicMethodReturn",
+            Self::AugmentedAssignment => "AugmentedAssignment",
+            Self::ImplicitFunctionReturn(..) => "ImplicitFunctionReturn",
+            Self::ExplicitFunctionReturn => "ExplicitFunctionReturn",
+            Self::TypeGuardReturn => "TypeGuardReturn",
+            Self::CallArgument(..) => "CallArgument",
+            Self::CallVarArgs(..) => "CallVarArgs",
+            Self::CallKwArgs(..) => "CallKwArgs",
+            Self::CallUnpackKwArg(..) => "CallUnpackKwArg",
+            Self::FunctionParameterDefault(..) => "FunctionParameterDefault",
+            Self::TypedDictKey(..) => "TypedDictKey",
+            Self::TypedDictUnpacking => "TypedDictUnpacking",
+            Self::Attribute(..) => "Attribute",
+            Self::AnnotatedName(..) => "AnnotatedName",
+            Self::IterationVariableMismatch(..) => "IterationVariableMismatch",
+            Self::AnnAssign => "AnnAssign",
+            Self::UnpackedAssign => "UnpackedAssign",
+            Self::CycleBreaking => "CycleBreaking",
+            Self::ExceptionClass => "ExceptionClass",
+            Self::YieldValue => "YieldValue",
+            Self::YieldFrom => "YieldFrom",
+            Self::UnexpectedBareYield => "UnexpectedBareYield",
+        }
+    }
+
     pub fn as_error_kind(&self) -> ErrorKind {
         match self {
             Self::MagicMethodReturn(..) => ErrorKind::BadReturn,
@@ -165,3 +234,41 @@ impl TypeCheckKind {
         }
     }
 }
+
+/// Why a `got <: want` check failed, recorded by the solver so that we can explain big types,
+/// such as protocols, callables and TypedDicts, instead of just saying they are not assignable.
+#[derive(Debug, Clone)]
+pub struct SubsetFailure {
+    pub reason: SubsetReason,
+    /// Where the thing the failure is about is defined, e.g. the protocol member or TypedDict key,
+    /// if we know. Nested failures without a location of their own are about the same thing.
+    pub location: Option<TextRangeWithModuleInfo>,
+    /// The failures that caused this one, e.g. the parameter that made a protocol method incompatible.
+    pub nested: Vec<SubsetFailure>,
+}
+
+#[derive(Debug, Clone)]
+pub enum SubsetReason {
+    /// The protocol member is missing from `got`.
+    MissingProtocolMember(Name),
+    /// The protocol member exists on `got`, but has an incompatible type.
+    ProtocolMember(Name),
+    /// A parameter of the `got` callable, with the given name if it has one, doesn't accept the
+    /// type of the corresponding `want` parameter, as (name, got, want).
+    Param(Option<Name>, Type, Type),
+    /// The `want` callable has a keyword parameter that the `got` callable doesn't accept.
+    MissingParam(Name),
+    /// The return type of the `got` callable isn't assignable to that of `want`, as (got, want).
+    ReturnType(Type, Type),
+    /// The TypedDict key is missing from `got`.
+    MissingTypedDictKey(Name),
+    /// The TypedDict key is `ReadOnly` in `got`, but not in `want`.
+    ReadOnlyTypedDictKey(Name),
+    /// The TypedDict key is required in one of `got` and `want`, but not in the other. The bool
+    /// indicates whether it is required in `got`.
+    TypedDictKeyRequired(Name, bool),
+    /// The TypedDict key has incompatible value types, as (key, got, want).
+    TypedDictKey(Name, Type, Type),
+    /// The type arguments for a type parameter are incompatible, as (parameter, variance, got, want).
+    TypeArgument(Name, Variance, Type, Type),
+}
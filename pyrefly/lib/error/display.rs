@@ -6,6 +6,7 @@
  */
 
 use crate::error::context::ErrorContext;
+use crate::error::context::SubsetReason;
 use crate::error::context::TypeCheckKind;
 use crate::module::module_name::ModuleName;
 use crate::types::callable::FuncId;
@@ -230,6 +231,62 @@ impl TypeCheckKind {
     }
 }
 
+impl SubsetReason {
+    /// Format the reason, first converting the types it mentions with `for_display`.
+    pub fn format(&self, for_display: &dyn Fn(&Type) -> Type) -> String {
+        let pair = |got: &Type, want: &Type| {
+            let got = for_display(got);
+            let want = for_display(want);
+            let ctx = TypeDisplayContext::new(&[&got, &want]);
+            (
+                ctx.display(&got).to_string(),
+                ctx.display(&want).to_string(),
+            )
+        };
+        match self {
+            Self::MissingProtocolMember(name) => format!("Protocol member `{name}` is missing"),
+            Self::ProtocolMember(name) => {
+                format!("Protocol member `{name}` has an incompatible type")
+            }
+            Self::Param(name, got, want) => {
+                let (got, want) = pair(got, want);
+                let param_desc = match name {
+                    Some(name) => format!("Parameter `{name}`"),
+                    None => "Positional parameter".to_owned(),
+                };
+                format!("{param_desc} has type `{got}`, which does not accept `{want}`")
+            }
+            Self::MissingParam(name) => format!("Keyword parameter `{name}` is missing"),
+            Self::ReturnType(got, want) => {
+                let (got, want) = pair(got, want);
+                format!("Return type `{got}` is not assignable to `{want}`")
+            }
+            Self::MissingTypedDictKey(key) => format!("TypedDict key `{key}` is missing"),
+            Self::ReadOnlyTypedDictKey(key) => {
+                format!("TypedDict key `{key}` is read-only, but is expected to be mutable")
+            }
+            Self::TypedDictKeyRequired(key, true) => {
+                format!("TypedDict key `{key}` is required, but is expected to be non-required")
+            }
+            Self::TypedDictKeyRequired(key, false) => {
+                format!("TypedDict key `{key}` is non-required, but is expected to be required")
+            }
+            Self::TypedDictKey(key, got, want) => {
+                let (got, want) = pair(got, want);
+                format!(
+                    "TypedDict key `{key}` has type `{got}`, which is not compatible with `{want}`"
+                )
+            }
+            Self::TypeArgument(param, variance, got, want) => {
+                let (got, want) = pair(got, want);
+                format!(
+                    "Type argument `{got}` is not compatible with `{want}` for {variance} type parameter `{param}`"
+                )
+            }
+        }
+    }
+}
+
 pub fn function_suffix(func_id: Option<&FuncId>, current_module: ModuleName) -> String {
     match func_id {
         Some(func) => format!(" in function `{}`", func.format(current_module)),
@@ -22,8 +22,10 @@ use yansi::Paint;
 
 use crate::error::kind::ErrorKind;
 use crate::error::kind::Severity;
+use crate::error::payload::ErrorPayload;
 use crate::module::module_info::ModuleInfo;
 use crate::module::module_info::SourceRange;
+use crate::module::module_info::TextRangeWithModuleInfo;
 use crate::module::module_path::ModulePath;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -36,18 +38,36 @@ pub struct Error {
     /// The rest of the error message after the first line.
     /// Note that this is formatted for pretty-printing, with two spaces at the beginning and after every newline.
     msg_details: Option<Box<str>>,
+    /// Secondary lines explaining why the error happened, most specific last. Nested reasons are
+    /// indented by two spaces per level.
+    because: Box<[ErrorReason]>,
+    /// Machine-readable data about the error, if we have any.
+    payload: Option<Box<ErrorPayload>>,
+    /// The Python versions and platforms the error occurs under, when checking against several,
+    /// or empty if it occurs under all of them.
+    configurations: Box<[Box<str>]>,
+    /// The severity to report the error at, which is the default for its kind unless configured otherwise.
+    severity: Severity,
     is_ignored: bool,
 }
 
+/// A secondary line explaining why an error happened.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ErrorReason {
+    pub msg: Box<str>,
+    /// Where the thing the reason is about is defined, e.g. a protocol member, if we know.
+    pub location: Option<TextRangeWithModuleInfo>,
+}
+
 impl Error {
     pub fn write_line(&self, mut f: impl Write, verbose: bool) -> io::Result<()> {
         if verbose {
             writeln!(
                 f,
-                "{} {} [{}]",
-                self.error_kind().severity().label(),
+                "{} {} {}",
+                self.severity.label(),
                 self.msg_header,
-                self.error_kind.to_name(),
+                self.kind_label(),
             )?;
             let origin = self.lossy_origin();
             let snippet = self.get_source_snippet(&origin);
@@ -56,15 +76,18 @@ impl Error {
             if let Some(details) = &self.msg_details {
                 writeln!(f, "{details}")?;
             }
+            for line in self.because_lines() {
+                writeln!(f, "{line}")?;
+            }
         } else {
             writeln!(
                 f,
-                "{} {}:{}: {} [{}]",
-                self.error_kind().severity().label(),
+                "{} {}:{}: {} {}",
+                self.severity.label(),
                 self.path(),
                 self.range,
                 self.msg_header,
-                self.error_kind.to_name(),
+                self.kind_label(),
             )?;
         }
         Ok(())
@@ -74,9 +97,9 @@ impl Error {
         if verbose {
             anstream::println!(
                 "{} {} {}",
-                self.error_kind().severity().painted(),
+                self.severity.painted(),
                 Paint::new(&*self.msg_header),
-                Paint::dim(format!("[{}]", self.error_kind().to_name()).as_str()),
+                Paint::dim(self.kind_label().as_str()),
             );
             let origin = self.lossy_origin();
             let snippet = self.get_source_snippet(&origin);
@@ -85,18 +108,35 @@ impl Error {
             if let Some(details) = &self.msg_details {
                 anstream::println!("{details}");
             }
+            for line in self.because_lines() {
+                anstream::println!("{}", Paint::dim(&line));
+            }
         } else {
             anstream::println!(
                 "{} {}:{}: {} {}",
-                self.error_kind().severity().painted(),
+                self.severity.painted(),
                 Paint::blue(&self.path().as_path().display()),
                 Paint::dim(self.source_range()),
                 Paint::new(&*self.msg_header),
-                Paint::dim(format!("[{}]", self.error_kind().to_name()).as_str()),
+                Paint::dim(self.kind_label().as_str()),
             );
         }
     }
 
+    /// The error kind in brackets, followed by the configurations the error occurs under, if
+    /// it doesn't occur under all of them.
+    fn kind_label(&self) -> String {
+        if self.configurations.is_empty() {
+            format!("[{}]", self.error_kind.to_name())
+        } else {
+            format!(
+                "[{}] (under {})",
+                self.error_kind.to_name(),
+                self.configurations.join(", ")
+            )
+        }
+    }
+
     fn lossy_origin(&self) -> Cow<'_, str> {
         self.path().as_path().to_string_lossy()
     }
@@ -112,7 +152,7 @@ impl Error {
             .content_in_line_range(range.start.line, range.end.line);
         let line_start = self.module_info.line_start(range.start.line);
 
-        let level = match self.error_kind().severity() {
+        let level = match self.severity {
             Severity::Error => Level::Error,
             Severity::Warn => Level::Warning,
             Severity::Info => Level::Info,
@@ -180,10 +220,51 @@ impl Error {
             error_kind,
             msg_header,
             msg_details,
+            because: Box::new([]),
+            configurations: Box::new([]),
+            payload: None,
+            severity: error_kind.severity(),
             is_ignored,
         }
     }
 
+    pub fn with_configurations(mut self, configurations: Vec<String>) -> Self {
+        self.configurations = configurations
+            .into_iter()
+            .map(String::into_boxed_str)
+            .collect();
+        self
+    }
+
+    pub fn configurations(&self) -> &[Box<str>] {
+        &self.configurations
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    pub fn with_payload(mut self, payload: ErrorPayload) -> Self {
+        self.payload = if payload.is_empty() {
+            None
+        } else {
+            Some(Box::new(payload))
+        };
+        self
+    }
+
+    pub fn with_because(mut self, because: Vec<(String, Option<TextRangeWithModuleInfo>)>) -> Self {
+        self.because = because
+            .into_iter()
+            .map(|(msg, location)| ErrorReason {
+                msg: msg.into_boxed_str(),
+                location,
+            })
+            .collect();
+        self
+    }
+
     pub fn source_range(&self) -> &SourceRange {
         &self.range
     }
@@ -196,7 +277,19 @@ impl Error {
         &self.msg_header
     }
 
+    /// The full error message, including the details and the reasons why it happened.
     pub fn msg(&self) -> String {
+        let mut msg = self.msg_without_because();
+        for line in self.because_lines() {
+            msg.push('\n');
+            msg.push_str(&line);
+        }
+        msg
+    }
+
+    /// The error message without the reasons why it happened, for consumers like the LSP that
+    /// present the reasons separately.
+    pub fn msg_without_because(&self) -> String {
         if let Some(details) = &self.msg_details {
             format!("{}\n{}", self.msg_header, details)
         } else {
@@ -204,6 +297,21 @@ impl Error {
         }
     }
 
+    pub fn because(&self) -> &[ErrorReason] {
+        &self.because
+    }
+
+    /// The reasons why the error happened, formatted like the details.
+    fn because_lines(&self) -> impl Iterator<Item = String> {
+        self.because
+            .iter()
+            .map(|reason| format!("  {}", reason.msg))
+    }
+
+    pub fn payload(&self) -> Option<&ErrorPayload> {
+        self.payload.as_deref()
+    }
+
     pub fn is_ignored(&self) -> bool {
         self.is_ignored
     }
@@ -211,4 +319,8 @@ impl Error {
     pub fn error_kind(&self) -> ErrorKind {
         self.error_kind
     }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
 }
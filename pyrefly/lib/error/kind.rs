@@ -19,6 +19,8 @@ use yansi::Painted;
 
 // IMPORTANT: these cases should be listed in order of severity
 #[derive(Debug, Clone, Dupe, Copy, PartialOrd, Ord, PartialEq, Eq, Hash)]
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Severity {
     Info,
     Warn,
@@ -104,6 +106,12 @@ pub enum ErrorKind {
     DeleteError,
     /// Calling a function marked with `@deprecated`
     Deprecated,
+    /// A parameter or return type that is implicitly `Any` because it is unannotated.
+    /// Only reported in strict mode.
+    ImplicitAny,
+    /// Importing a name from a stub (or a `py.typed` package, if configured) that the module
+    /// imports itself, but doesn't explicitly re-export.
+    ImplicitReexport,
     /// An attribute was implicitly defined by assignment to `self` in a method that we
     /// do not recognize as always executing (we recognize constructors and some test setup
     /// methods).
@@ -123,6 +131,8 @@ pub enum ErrorKind {
     /// An error caused by incorrect inheritance in a class or type definition.
     /// e.g. a metaclass that is not a subclass of `type`.
     InvalidInheritance,
+    /// A `# pyrefly: <key> = <value>` comment at the top of a file that can't be applied.
+    InvalidInlineConfig,
     /// Attempting to use a value that is not a valid kind of Literal.
     InvalidLiteral,
     /// An error caused by incorrect usage of the @overload decorator.
@@ -153,6 +163,8 @@ pub enum ErrorKind {
     MissingAttribute,
     /// Accessing an attribute that does not exist on a module.
     MissingModuleAttribute,
+    /// A public function without a return annotation. Only reported in strict mode.
+    MissingReturnAnnotation,
     /// The attribute exists but does not support this access pattern.
     NoAccess,
     /// Attempting to call an overloaded function, but none of the signatures match.
@@ -170,6 +182,8 @@ pub enum ErrorKind {
     ReadOnly,
     /// Raised by a call to reveal_type().
     RevealType,
+    /// A module took longer than its solve budget, so the rest of it was treated as `Any`.
+    TooComplex,
     /// An error related to type alias usage or definition.
     TypeAliasError,
     /// An error related to TypedDict keys.
@@ -181,10 +195,31 @@ pub enum ErrorKind {
     UnexpectedKeyword,
     /// Attempting to use a name that is not defined.
     UnknownName,
+    /// A `typing.cast` of a value to the type it already has. Only reported in strict mode.
+    UnnecessaryCast,
+    /// A comparison between literals that can never be equal. Only reported in strict mode.
+    UnnecessaryComparison,
+    /// An `isinstance` or `issubclass` call that is always true or always false.
+    /// Only reported in strict mode.
+    UnnecessaryIsinstance,
+    /// Code that can never run, e.g. after a `return` or a call that never returns.
+    /// Only reported when enabled.
+    Unreachable,
     /// Attempting to use a feature that is not yet supported.
     Unsupported,
     /// Attempting to apply an operator to arguments that do not support it.
     UnsupportedOperand,
+    /// Importing a third-party package that has no `py.typed` file or stubs.
+    /// Only reported in strict mode.
+    UntypedImport,
+    /// A private module-level function that is never used. Only reported when enabled.
+    UnusedFunction,
+    /// An error code in a `# pyrefly: ignore[...]` comment that doesn't suppress any error.
+    UnusedIgnore,
+    /// An import that is never used, and isn't a re-export. Only reported when enabled.
+    UnusedImport,
+    /// A local variable that is assigned but never read. Only reported when enabled.
+    UnusedVariable,
 }
 
 /// Computing the error kinds is disturbingly expensive, so cache the results.
@@ -202,13 +237,53 @@ impl ErrorKind {
         ERROR_KIND_CACHE[self as usize].as_str()
     }
 
+    /// The error kind with the given kebab-case name, e.g. `bad-return`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        enum_iterator::all::<ErrorKind>().find(|x| x.to_name() == name)
+    }
+
     pub fn severity(self) -> Severity {
         match self {
             ErrorKind::RevealType => Severity::Info,
+            ErrorKind::TooComplex => Severity::Info,
             ErrorKind::Deprecated => Severity::Warn,
+            ErrorKind::UnusedIgnore
+            | ErrorKind::UnusedFunction
+            | ErrorKind::UnusedImport
+            | ErrorKind::UnusedVariable => Severity::Warn,
             _ => Severity::Error,
         }
     }
+
+    /// Whether this kind is off by default, and only reported when enabled in the `errors` table
+    /// (or in strict mode, for the kinds that are part of it).
+    pub fn is_opt_in(self) -> bool {
+        self.is_strict() || self.is_unnecessary()
+    }
+
+    /// Whether this kind is off by default, and reported in strict mode.
+    pub fn is_strict(self) -> bool {
+        matches!(
+            self,
+            ErrorKind::ImplicitAny
+                | ErrorKind::MissingReturnAnnotation
+                | ErrorKind::UnnecessaryCast
+                | ErrorKind::UnnecessaryComparison
+                | ErrorKind::UnnecessaryIsinstance
+                | ErrorKind::UntypedImport
+        )
+    }
+
+    /// Whether this kind flags code that can be deleted, which editors may grey out.
+    pub fn is_unnecessary(self) -> bool {
+        matches!(
+            self,
+            ErrorKind::Unreachable
+                | ErrorKind::UnusedFunction
+                | ErrorKind::UnusedImport
+                | ErrorKind::UnusedVariable
+        )
+    }
 }
 #[cfg(test)]
 mod tests {
@@ -223,6 +298,11 @@ mod tests {
     fn test_error_kind_name() {
         assert_eq!(ErrorKind::Unsupported.to_name(), "unsupported");
         assert_eq!(ErrorKind::ParseError.to_name(), "parse-error");
+        assert_eq!(
+            ErrorKind::from_name("parse-error"),
+            Some(ErrorKind::ParseError)
+        );
+        assert_eq!(ErrorKind::from_name("parse_error"), None);
     }
 
     #[test]
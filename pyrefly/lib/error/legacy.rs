@@ -8,7 +8,10 @@
 use pyrefly_util::prelude::SliceExt;
 use serde::Serialize;
 
+use crate::error::collector::CollectedErrors;
 use crate::error::error::Error;
+use crate::error::kind::Severity;
+use crate::error::payload::ErrorPayload;
 
 /// Legacy error structure in Pyre1. Needs to be consistent with the following file:
 /// <https://www.internalfb.com/code/fbsource/fbcode/tools/pyre/facebook/arc/lib/error.rs>
@@ -26,6 +29,17 @@ pub struct LegacyError {
     name: &'static str,
     description: String,
     concise_description: String,
+    /// The configured severity of the error: `error`, `warn` or `info`.
+    severity: Severity,
+    /// Whether the error is suppressed by an ignore comment.
+    suppressed: bool,
+    /// The Python versions and platforms the error occurs under, if it doesn't occur under
+    /// all of those checked.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    configurations: Vec<String>,
+    /// Structured data about the error, such as the expected and actual types.
+    #[serde(flatten)]
+    payload: ErrorPayload,
 }
 
 impl LegacyError {
@@ -42,6 +56,14 @@ impl LegacyError {
             name: error.error_kind().to_name(),
             description: error.msg(),
             concise_description: error.msg_header().to_owned(),
+            severity: error.severity(),
+            suppressed: error.is_ignored(),
+            configurations: error
+                .configurations()
+                .iter()
+                .map(|x| x.to_string())
+                .collect(),
+            payload: error.payload().cloned().unwrap_or_default(),
         }
     }
 }
@@ -57,4 +79,21 @@ impl LegacyErrors {
             errors: errors.map(LegacyError::from_error),
         }
     }
+
+    /// The shown errors, followed by those suppressed by ignore comments if `include_suppressed`.
+    pub fn from_collected(errors: &CollectedErrors, include_suppressed: bool) -> Self {
+        let suppressed: &[Error] = if include_suppressed {
+            &errors.suppressed
+        } else {
+            &[]
+        };
+        Self {
+            errors: errors
+                .shown
+                .iter()
+                .chain(suppressed)
+                .map(LegacyError::from_error)
+                .collect(),
+        }
+    }
 }
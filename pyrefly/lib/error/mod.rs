@@ -12,5 +12,6 @@ pub mod error;
 pub mod expectation;
 pub mod kind;
 pub mod legacy;
+pub mod payload;
 pub mod style;
 pub mod summarise;
@@ -0,0 +1,81 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Machine-readable data about an error, so that tools consuming our JSON output can group
+//! errors by what went wrong instead of parsing the rendered messages.
+
+use serde::Serialize;
+
+use crate::error::context::ErrorContext;
+use crate::error::context::TypeCheckKind;
+use crate::types::display::TypeDisplayContext;
+use crate::types::types::Type;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize)]
+pub struct ErrorPayload {
+    /// The kind of `got <: want` check that failed, e.g. `CallArgument`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_check: Option<&'static str>,
+    /// The context the error was reported in, e.g. `BinaryOp`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<&'static str>,
+    /// The type that was expected by a failed type check.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<PayloadType>,
+    /// The type that was actually found by a failed type check.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<PayloadType>,
+    /// Other types involved in the error, such as the operands of a binary operation.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<PayloadType>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct PayloadType {
+    /// The type as it is displayed in error messages.
+    pub rendered: String,
+    /// The type with every class qualified by the name of its module.
+    pub qualified: String,
+}
+
+impl PayloadType {
+    /// Render some types together, so that they are disambiguated the same way as in messages.
+    fn of_types(types: &[&Type]) -> Vec<Self> {
+        let ctx = TypeDisplayContext::new(types);
+        let mut qualified_ctx = ctx.clone();
+        qualified_ctx.always_display_module_name();
+        types
+            .iter()
+            .map(|ty| Self {
+                rendered: ctx.display(ty).to_string(),
+                qualified: qualified_ctx.display(ty).to_string(),
+            })
+            .collect()
+    }
+}
+
+impl ErrorPayload {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// The payload for a failed `got <: want` check.
+    pub fn type_check(kind: &TypeCheckKind, got: &Type, want: &Type) -> Self {
+        let mut types = PayloadType::of_types(&[got, want]).into_iter();
+        Self {
+            type_check: Some(kind.name()),
+            actual: types.next(),
+            expected: types.next(),
+            ..Self::default()
+        }
+    }
+
+    pub fn add_context(&mut self, context: &ErrorContext) {
+        self.context = Some(context.name());
+        self.types.extend(PayloadType::of_types(&context.types()));
+    }
+}
@@ -137,7 +137,7 @@ struct DefinitionsBuilder<'a> {
     inner: Definitions,
 }
 
-fn is_private_name(name: &Name) -> bool {
+pub fn is_private_name(name: &Name) -> bool {
     name.starts_with('_') && !name.starts_with("__")
 }
 
@@ -18,6 +18,8 @@ use starlark_map::small_map::SmallMap;
 use starlark_map::small_set::SmallSet;
 
 use crate::common::symbol_kind::SymbolKind;
+use crate::dunder;
+use crate::export::definitions::Definition;
 use crate::export::definitions::DefinitionStyle;
 use crate::export::definitions::Definitions;
 use crate::export::definitions::DocString;
@@ -32,6 +34,14 @@ use crate::sys_info::SysInfo;
 pub trait LookupExport {
     /// Get the exports of a given module, or an error if the module is not available.
     fn get(&self, module: ModuleName) -> Result<Exports, FindError>;
+
+    /// Whether the given module comes from a package without a `py.typed` file or stubs.
+    fn is_untyped(&self, module: ModuleName) -> bool;
+
+    /// Whether the given module only re-exports the names it imports when they are marked
+    /// explicitly, e.g. because it is a stub. The `setting` is the importing file's own
+    /// `explicit-reexport` setting, which takes precedence over the config file.
+    fn requires_explicit_reexport(&self, module: ModuleName, setting: Option<bool>) -> bool;
 }
 
 #[derive(Debug, Clone)]
@@ -133,6 +143,50 @@ impl Exports {
         self.0.docstring.as_ref()
     }
 
+    /// Is this name explicitly marked as a re-export, either by being listed in an explicit
+    /// `__all__`, or by being imported with a redundant alias (`from m import x as x`)?
+    pub fn is_explicit_export(&self, name: &Name, lookup: &dyn LookupExport) -> bool {
+        let definitions = &self.0.definitions;
+        matches!(
+            definitions.definitions.get(name),
+            Some(Definition {
+                style: DefinitionStyle::ImportAsEq(_),
+                ..
+            })
+        ) || (definitions.definitions.contains_key(&dunder::ALL)
+            && self.wildcard(lookup).contains(name))
+    }
+
+    /// If this name is imported from another module and explicitly re-exported, where it is
+    /// imported. Names re-exported with `from m import *` have no location, so return `None`.
+    pub fn explicit_reexport(&self, name: &Name, lookup: &dyn LookupExport) -> Option<Export> {
+        let definition = self.0.definitions.definitions.get(name)?;
+        if matches!(definition.style, DefinitionStyle::Local(_))
+            || !self.is_explicit_export(name, lookup)
+        {
+            return None;
+        }
+        Some(Export {
+            location: definition.range,
+            symbol_kind: None,
+            docstring: definition.docstring.clone(),
+        })
+    }
+
+    /// Is this name imported from another module without being explicitly re-exported?
+    /// In stubs, such names are private to the module (see PEP 484).
+    pub fn is_implicit_reexport(&self, name: &Name, lookup: &dyn LookupExport) -> bool {
+        matches!(
+            self.0.definitions.definitions.get(name),
+            Some(Definition {
+                style: DefinitionStyle::Import(_)
+                    | DefinitionStyle::ImportAs(_)
+                    | DefinitionStyle::ImportModule(_),
+                ..
+            })
+        ) && !self.is_explicit_export(name, lookup)
+    }
+
     pub fn is_submodule_imported_implicitly(&self, name: &Name) -> bool {
         self.0
             .definitions
@@ -196,6 +250,14 @@ mod tests {
                 None => Err(FindError::not_found(anyhow!("Error"), module)),
             }
         }
+
+        fn is_untyped(&self, _module: ModuleName) -> bool {
+            false
+        }
+
+        fn requires_explicit_reexport(&self, _module: ModuleName, _setting: Option<bool>) -> bool {
+            false
+        }
     }
 
     fn mk_exports(contents: &str, style: ModuleStyle) -> Exports {
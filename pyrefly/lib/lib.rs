@@ -42,6 +42,7 @@ mod export;
 mod graph;
 mod module;
 pub mod playground;
+mod plugin;
 pub mod query;
 mod report;
 mod ruff;
@@ -61,6 +62,8 @@ pub mod library {
     pub mod library {
         pub mod library {
             pub mod library {
+                pub use crate::alt::types::class_metadata::ClassSynthesizedField;
+                pub use crate::alt::types::class_metadata::ClassSynthesizedFields;
                 #[cfg(not(target_arch = "wasm32"))]
                 pub use crate::commands::config_finder::standard_config_finder;
                 #[cfg(not(target_arch = "wasm32"))]
@@ -75,8 +78,23 @@ pub mod library {
                 pub use crate::module::module_info::SourceRange;
                 pub use crate::module::module_name::ModuleName;
                 pub use crate::module::module_path::ModulePath;
+                pub use crate::plugin::Plugin;
+                pub use crate::plugin::PluginContext;
+                pub use crate::plugin::PluginKey;
+                pub use crate::plugin::Plugins;
+                pub use crate::state::state::State;
                 pub use crate::sys_info::PythonPlatform;
                 pub use crate::sys_info::PythonVersion;
+                pub use crate::types::callable::Callable;
+                pub use crate::types::callable::FuncMetadata;
+                pub use crate::types::callable::Function;
+                pub use crate::types::callable::Param;
+                pub use crate::types::callable::ParamList;
+                pub use crate::types::callable::Required;
+                pub use crate::types::class::Class;
+                pub use crate::types::class::ClassType;
+                pub use crate::types::stdlib::Stdlib;
+                pub use crate::types::types::Type;
             }
         }
     }
@@ -312,6 +312,39 @@ pub fn find_module_in_site_package_path(
     Ok(None)
 }
 
+/// Whether `path`, where `module` was found, is a source file from a package in `include` that
+/// has neither stubs nor a `py.typed` file.
+pub fn is_untyped_site_package_module(
+    module: ModuleName,
+    path: &Path,
+    include: &[PathBuf],
+) -> bool {
+    !path.extension().is_some_and(|x| x == "pyi")
+        && site_package_py_typed(module, path, include) == Some(PyTyped::Missing)
+}
+
+/// Whether `path`, where `module` was found, is a source file from a package in `include` that
+/// has a `py.typed` file.
+pub fn is_py_typed_site_package_module(
+    module: ModuleName,
+    path: &Path,
+    include: &[PathBuf],
+) -> bool {
+    site_package_py_typed(module, path, include).is_some_and(|x| x != PyTyped::Missing)
+}
+
+/// The `py.typed` status of the package containing `module`, if it was found at `path` in `include`.
+fn site_package_py_typed(module: ModuleName, path: &Path, include: &[PathBuf]) -> Option<PyTyped> {
+    let first = &module.components()[0];
+    include.iter().find_map(|root| {
+        if path.starts_with(root) {
+            find_one_part(first, iter::once(root)).map(|x| x.py_typed())
+        } else {
+            None
+        }
+    })
+}
+
 pub fn find_module_prefixes<'a>(
     prefix: ModuleName,
     include: impl Iterator<Item = &'a PathBuf>,
@@ -572,6 +605,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_is_untyped_site_package_module() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        TestPath::setup_test_directory(
+            root,
+            vec![
+                TestPath::dir("untyped", vec![TestPath::file("__init__.py")]),
+                TestPath::dir(
+                    "typed",
+                    vec![TestPath::file("__init__.py"), TestPath::file("py.typed")],
+                ),
+                TestPath::dir("stubbed", vec![TestPath::file("__init__.pyi")]),
+            ],
+        );
+        let include = [root.to_path_buf()];
+        let is_untyped = |module, path: &str| {
+            is_untyped_site_package_module(ModuleName::from_str(module), &root.join(path), &include)
+        };
+        assert!(is_untyped("untyped", "untyped/__init__.py"));
+        assert!(!is_untyped("typed", "typed/__init__.py"));
+        assert!(!is_untyped("stubbed", "stubbed/__init__.pyi"));
+        let is_py_typed = |module, path: &str| {
+            is_py_typed_site_package_module(
+                ModuleName::from_str(module),
+                &root.join(path),
+                &include,
+            )
+        };
+        assert!(!is_py_typed("untyped", "untyped/__init__.py"));
+        assert!(is_py_typed("typed", "typed/__init__.py"));
+        // Not from the site package path at all.
+        assert!(!is_untyped_site_package_module(
+            ModuleName::from_str("untyped"),
+            Path::new("elsewhere/untyped/__init__.py"),
+            &include,
+        ));
+    }
+
     #[test]
     fn test_find_site_package_path_no_py_typed() {
         let tempdir = tempfile::tempdir().unwrap();
@@ -8,9 +8,13 @@
 use dupe::Dupe;
 use itertools::Itertools;
 use ruff_source_file::OneIndexed;
+use ruff_text_size::TextRange;
+use ruff_text_size::TextSize;
 use starlark_map::small_map::SmallMap;
 use starlark_map::small_set::SmallSet;
 
+use crate::error::kind::ErrorKind;
+
 #[derive(PartialEq, Debug, Clone, Hash, Eq, Dupe, Copy)]
 pub enum SuppressionKind {
     Ignore,
@@ -18,21 +22,59 @@ pub enum SuppressionKind {
     Pyrefly,
 }
 
-/// Record the position of `# type: ignore[valid-type]` statements.
-/// For now we don't record the content of the ignore, but we could.
+/// A suppression comment, e.g. `# pyrefly: ignore[bad-return]`.
+#[derive(PartialEq, Debug, Clone, Hash, Eq)]
+pub struct Suppression {
+    kind: SuppressionKind,
+    /// The error kinds in brackets, which the suppression is limited to if non-empty.
+    /// We only record them for `# pyrefly: ignore`, as other tools use different names.
+    codes: Vec<String>,
+    /// Where each of the `codes` is, relative to the start of the line.
+    code_ranges: Vec<TextRange>,
+}
+
+impl Suppression {
+    pub fn kind(&self) -> SuppressionKind {
+        self.kind
+    }
+
+    pub fn codes(&self) -> &[String] {
+        &self.codes
+    }
+
+    pub fn code_ranges(&self) -> &[TextRange] {
+        &self.code_ranges
+    }
+
+    fn suppresses(&self, kind: ErrorKind) -> bool {
+        self.codes.is_empty() || self.codes.iter().any(|x| x == kind.to_name())
+    }
+}
+
+/// The part of a `# pyrefly: ignore` comment that doesn't suppress any error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnusedIgnore {
+    /// The whole comment.
+    All,
+    /// Some of the error kinds in brackets, while the others are still used.
+    Codes(Vec<String>),
+}
+
+/// Record the position of `# type: ignore[valid-type]` statements, along with the error kinds
+/// of `# pyrefly: ignore[bad-return]` statements.
 #[derive(Debug, Clone, Default)]
 pub struct Ignore {
-    ignores: SmallMap<OneIndexed, Vec<SuppressionKind>>,
+    ignores: SmallMap<OneIndexed, Vec<Suppression>>,
     ignore_all: bool,
 }
 
 impl Ignore {
     pub fn new(code: &str) -> Self {
         // process line level comments
-        let mut ignores: SmallMap<OneIndexed, Vec<SuppressionKind>> = SmallMap::new();
+        let mut ignores: SmallMap<OneIndexed, Vec<Suppression>> = SmallMap::new();
         for (line, line_str) in code.lines().enumerate() {
-            if let Some(kind) = Self::get_suppression_kind(line_str) {
-                ignores.insert(OneIndexed::from_zero_indexed(line), [kind].to_vec());
+            if let Some(suppression) = Self::get_suppression(line_str) {
+                ignores.insert(OneIndexed::from_zero_indexed(line), vec![suppression]);
             }
         }
         Self {
@@ -70,47 +112,173 @@ impl Ignore {
         false
     }
 
-    pub fn get_suppression_kind(line: &str) -> Option<SuppressionKind> {
-        fn match_pyrefly_ignore(line: &str) -> bool {
-            let mut words = line.split_whitespace();
-            if let Some("pyrefly:") = words.next() {
-                words.next() == Some("ignore")
-            } else {
-                false
+    /// Parse `pyrefly: ignore` or `pyrefly: ignore[code1, code2]`, returning how long it is,
+    /// and the codes and where they are relative to the start of `comment`.
+    fn parse_pyrefly_ignore(comment: &str) -> Option<(usize, Vec<(String, TextRange)>)> {
+        let rest = comment.strip_prefix("pyrefly:")?.trim_start();
+        let rest = rest.strip_prefix("ignore")?;
+        if let Some(rest) = rest.strip_prefix('[') {
+            let (codes, _) = rest.split_once(']')?;
+            let len = comment.len() - rest.len() + codes.len() + 1;
+            let mut offset = comment.len() - rest.len();
+            let mut res = Vec::new();
+            for code in codes.split(',') {
+                let trimmed = code.trim();
+                if !trimmed.is_empty() {
+                    let start = offset + code.len() - code.trim_start().len();
+                    res.push((
+                        trimmed.to_owned(),
+                        TextRange::at(
+                            TextSize::try_from(start).unwrap(),
+                            TextSize::try_from(trimmed.len()).unwrap(),
+                        ),
+                    ));
+                }
+                offset += code.len() + 1;
             }
+            Some((len, res))
+        } else if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+            Some((comment.len() - rest.len(), Vec::new()))
+        } else {
+            None
         }
+    }
+
+    /// Find a `# pyrefly: ignore` comment in `line`, in any of the forms `get_suppression`
+    /// accepts, returning where it is, up to the end of its codes, and the codes and where they
+    /// are, all relative to the start of `line`.
+    pub fn find_pyrefly_ignore(line: &str) -> Option<(TextRange, Vec<(String, TextRange)>)> {
+        line.match_indices("# ").find_map(|(i, _)| {
+            let (len, codes) = Self::parse_pyrefly_ignore(&line[i + 2..])?;
+            let start = TextSize::try_from(i + 2).unwrap();
+            Some((
+                TextRange::at(
+                    TextSize::try_from(i).unwrap(),
+                    TextSize::try_from(len + 2).unwrap(),
+                ),
+                codes.into_iter().map(|(x, r)| (x, r + start)).collect(),
+            ))
+        })
+    }
 
-        for l in line.split("# ").skip(1) {
+    pub fn get_suppression(line: &str) -> Option<Suppression> {
+        for (i, _) in line.match_indices("# ") {
+            let start = i + 2;
+            let l = &line[start..];
             if l.starts_with("type: ignore") {
-                return Some(SuppressionKind::Ignore);
-            } else if match_pyrefly_ignore(l) {
-                return Some(SuppressionKind::Pyrefly);
+                return Some(Suppression {
+                    kind: SuppressionKind::Ignore,
+                    codes: Vec::new(),
+                    code_ranges: Vec::new(),
+                });
+            } else if let Some((_, codes)) = Self::parse_pyrefly_ignore(l) {
+                let start = TextSize::try_from(start).unwrap();
+                let (codes, code_ranges) = codes.into_iter().map(|(x, r)| (x, r + start)).unzip();
+                return Some(Suppression {
+                    kind: SuppressionKind::Pyrefly,
+                    codes,
+                    code_ranges,
+                });
             } else if l.starts_with("pyre-ignore") || l.starts_with("pyre-fixme") {
-                return Some(SuppressionKind::Pyre);
+                return Some(Suppression {
+                    kind: SuppressionKind::Pyre,
+                    codes: Vec::new(),
+                    code_ranges: Vec::new(),
+                });
             }
         }
         None
     }
 
-    pub fn is_ignored(&self, start_line: OneIndexed, end_line: OneIndexed) -> bool {
+    pub fn get_suppression_kind(line: &str) -> Option<SuppressionKind> {
+        Self::get_suppression(line).map(|x| x.kind)
+    }
+
+    pub fn is_ignored(
+        &self,
+        start_line: OneIndexed,
+        end_line: OneIndexed,
+        kind: ErrorKind,
+    ) -> bool {
         if self.ignore_all {
             true
         } else {
-            // We allow an ignore the line before the range, or on any line within the range.
-            // We convert to/from zero-indexed because OneIndexed does not implement Step.
-            (start_line.to_zero_indexed().saturating_sub(1)..=end_line.to_zero_indexed())
-                .any(|x| self.ignores.contains_key(&OneIndexed::from_zero_indexed(x)))
+            Self::covering_lines(start_line, end_line).any(|x| {
+                self.ignores
+                    .get(&x)
+                    .is_some_and(|xs| xs.iter().any(|x| x.suppresses(kind)))
+            })
+        }
+    }
+
+    /// The lines an ignore comment can be on to suppress an error from `start_line` to `end_line`.
+    /// We allow an ignore the line before the range, or on any line within the range.
+    fn covering_lines(
+        start_line: OneIndexed,
+        end_line: OneIndexed,
+    ) -> impl Iterator<Item = OneIndexed> {
+        // We convert to/from zero-indexed because OneIndexed does not implement Step.
+        (start_line.to_zero_indexed().saturating_sub(1)..=end_line.to_zero_indexed())
+            .map(OneIndexed::from_zero_indexed)
+    }
+
+    /// The parts of the `# pyrefly: ignore` comments that don't suppress any of `errors`, given
+    /// as the lines each error could be ignored from (see `ModuleInfo::ignore_range`) and its kind.
+    /// Codes for kinds that `is_checked` rejects are never unused, since we didn't look for them.
+    pub fn unused(
+        &self,
+        errors: impl IntoIterator<Item = (OneIndexed, OneIndexed, ErrorKind)>,
+        is_checked: impl Fn(ErrorKind) -> bool,
+    ) -> SmallMap<OneIndexed, UnusedIgnore> {
+        let suppressions = self.get_suppressions(SuppressionKind::Pyrefly);
+        if self.ignore_all || suppressions.is_empty() {
+            return SmallMap::new();
+        }
+        let mut used = SmallSet::new();
+        for (start_line, end_line, kind) in errors {
+            for line in Self::covering_lines(start_line, end_line) {
+                if suppressions.get(&line).is_some_and(|x| x.suppresses(kind)) {
+                    used.insert((line, kind));
+                }
+            }
+        }
+        let mut res = SmallMap::new();
+        for (line, suppression) in suppressions {
+            if suppression.codes.is_empty() {
+                if !used.iter().any(|(x, _)| *x == line) {
+                    res.insert(line, UnusedIgnore::All);
+                }
+                continue;
+            }
+            let unused = suppression
+                .codes
+                .iter()
+                .filter(|code| {
+                    ErrorKind::from_name(code)
+                        .is_none_or(|kind| is_checked(kind) && !used.contains(&(line, kind)))
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            if unused.len() == suppression.codes.len() {
+                res.insert(line, UnusedIgnore::All);
+            } else if !unused.is_empty() {
+                res.insert(line, UnusedIgnore::Codes(unused));
+            }
         }
+        res
     }
 
-    /// Get all the ignores of a given kind.
-    pub fn get_ignores(&self, kind: SuppressionKind) -> SmallSet<OneIndexed> {
+    /// Get all the suppressions of a given kind, by line.
+    pub fn get_suppressions(&self, kind: SuppressionKind) -> SmallMap<OneIndexed, &Suppression> {
         self.ignores
             .iter()
-            .filter(|ignore| ignore.1.contains(&kind))
-            .map(|(line, _)| *line)
+            .filter_map(|(line, xs)| Some((*line, xs.iter().find(|x| x.kind == kind)?)))
             .collect()
     }
+
+    pub fn is_ignore_all(&self) -> bool {
+        self.ignore_all
+    }
 }
 
 #[cfg(test)]
@@ -124,6 +292,125 @@ mod tests {
         assert!(Ignore::get_suppression_kind("# ignore: pyrefly").is_none());
         assert!(Ignore::get_suppression_kind(" pyrefly: ignore").is_none());
         assert!(Ignore::get_suppression_kind("normal line").is_none());
+        assert!(Ignore::get_suppression_kind("# pyrefly: ignore-all-errors").is_none());
+    }
+
+    #[test]
+    fn test_get_suppression_codes() {
+        let codes = |x: &str| Ignore::get_suppression(x).map(|x| x.codes);
+        assert_eq!(codes("x # pyrefly: ignore"), Some(Vec::new()));
+        assert_eq!(codes("x # pyrefly: ignore # because"), Some(Vec::new()));
+        assert_eq!(
+            codes("x # pyrefly: ignore[bad-return, bad-assignment]"),
+            Some(vec!["bad-return".to_owned(), "bad-assignment".to_owned()])
+        );
+        assert_eq!(codes("x # pyrefly: ignore[bad-return"), None);
+        assert_eq!(codes("x # type: ignore[valid-type]"), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_get_suppression_code_ranges() {
+        let line = "ignore = 1  # pyrefly: ignore[ignore,  bad-return]";
+        let suppression = Ignore::get_suppression(line).unwrap();
+        let found = suppression
+            .code_ranges()
+            .iter()
+            .map(|r| &line[r.start().to_usize()..r.end().to_usize()])
+            .collect::<Vec<_>>();
+        assert_eq!(found, vec!["ignore", "bad-return"]);
+        assert_eq!(suppression.code_ranges()[0].start().to_usize(), 30);
+    }
+
+    #[test]
+    fn test_find_pyrefly_ignore() {
+        let find = |line: &str| {
+            Ignore::find_pyrefly_ignore(line)
+                .map(|(r, _)| line[r.start().to_usize()..r.end().to_usize()].to_owned())
+        };
+        assert_eq!(
+            find("x = 1 # pyrefly:ignore"),
+            Some("# pyrefly:ignore".to_owned())
+        );
+        assert_eq!(
+            find("x = 1 # pyrefly:  ignore[a, b] # why"),
+            Some("# pyrefly:  ignore[a, b]".to_owned())
+        );
+        assert_eq!(find("x = 1 # pyrefly: ignored"), None);
+        let (_, codes) = Ignore::find_pyrefly_ignore("x # pyrefly:ignore[bad-return]").unwrap();
+        assert_eq!(codes[0].0, "bad-return");
+        assert_eq!(codes[0].1.start().to_usize(), 19);
+    }
+
+    #[test]
+    fn test_unused_line_before() {
+        let ignore = Ignore::new(
+            "# pyrefly: ignore\nx = 1\n# pyrefly: ignore\nx = 1\n# pyrefly: ignore[bad-return, bad-assignment]\nx = 1\n# pyrefly: ignore[bad-assignment]\nx = 1\n",
+        );
+        let line = |x| OneIndexed::new(x).unwrap();
+        let unused = ignore.unused(
+            [2, 6, 8].map(|x| (line(x), line(x), ErrorKind::BadReturn)),
+            |_| true,
+        );
+        assert_eq!(
+            unused,
+            SmallMap::from_iter([
+                (line(3), UnusedIgnore::All),
+                (
+                    line(5),
+                    UnusedIgnore::Codes(vec!["bad-assignment".to_owned()])
+                ),
+                (line(7), UnusedIgnore::All),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_unused() {
+        let ignore = Ignore::new(
+            "x = 1  # pyrefly: ignore[bad-return, bad-assignment]\n# pyrefly: ignore\ny = 2\nz = 3  # pyrefly: ignore\n",
+        );
+        let line = |x| OneIndexed::new(x).unwrap();
+        let unused = ignore.unused(
+            [
+                (line(1), line(1), ErrorKind::BadReturn),
+                (line(3), line(3), ErrorKind::BadAssignment),
+            ],
+            |_| true,
+        );
+        assert_eq!(
+            unused.into_iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    line(1),
+                    UnusedIgnore::Codes(vec!["bad-assignment".to_owned()])
+                ),
+                (line(4), UnusedIgnore::All),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unused_unchecked() {
+        let ignore = Ignore::new(
+            "x = 1  # pyrefly: ignore[implicit-any, bad-return]
+y = 2  # pyrefly: ignore[implicit-any]
+",
+        );
+        let line = |x| OneIndexed::new(x).unwrap();
+        let unused = ignore.unused([], |kind| kind != ErrorKind::ImplicitAny);
+        assert_eq!(
+            unused.into_iter().collect::<Vec<_>>(),
+            vec![(line(1), UnusedIgnore::Codes(vec!["bad-return".to_owned()]))]
+        );
+    }
+
+    #[test]
+    fn test_is_ignored_codes() {
+        let ignore = Ignore::new("x = 1  # pyrefly: ignore[bad-return]\ny = 2  # pyrefly: ignore");
+        let line = |x| OneIndexed::new(x).unwrap();
+        assert!(ignore.is_ignored(line(1), line(1), ErrorKind::BadReturn));
+        assert!(!ignore.is_ignored(line(1), line(1), ErrorKind::BadAssignment));
+        assert!(ignore.is_ignored(line(2), line(2), ErrorKind::BadAssignment));
     }
 
     #[test]
@@ -0,0 +1,188 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Per-file configuration, given by `# pyrefly: <key> = <value>` comments at the top of a file,
+//! which override the matching `ConfigBase` settings for that file only. `# pyrefly: strict` is
+//! accepted as a shorthand for `# pyrefly: strict = true`.
+
+use ruff_text_size::TextRange;
+use ruff_text_size::TextSize;
+use toml::Table;
+use toml::Value;
+
+use crate::config::base::ConfigBase;
+
+/// The settings from the `# pyrefly: <key> = <value>` comments at the top of a file.
+#[derive(Debug, Clone, Default)]
+pub struct InlineConfig {
+    config: ConfigBase,
+    /// The comments we couldn't apply, with why.
+    errors: Vec<(TextRange, String)>,
+}
+
+impl InlineConfig {
+    pub fn new(code: &str) -> Self {
+        let mut res = Self::default();
+        let mut settings = Table::new();
+        let mut offset = TextSize::default();
+        // Like `# pyrefly: ignore-all-errors`, these must come before any code.
+        for line in code.split_inclusive('\n') {
+            let start = offset + TextSize::of(&line[..line.len() - line.trim_start().len()]);
+            offset += TextSize::of(line);
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let Some(comment) = line.strip_prefix('#') else {
+                break;
+            };
+            let Some(directive) = comment.trim_start().strip_prefix("pyrefly:") else {
+                continue;
+            };
+            let directive = directive.trim_start();
+            let (key, rest) = directive.split_at(
+                directive
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+                    .unwrap_or(directive.len()),
+            );
+            let rest = rest.trim_start();
+            let setting = if let Some(value) = rest.strip_prefix('=') {
+                Self::parse(key, value.trim())
+            } else if key == "strict" && rest.is_empty() {
+                // A bare `# pyrefly: strict` is short for `# pyrefly: strict = true`.
+                Ok((key.to_owned(), Value::Boolean(true)))
+            } else {
+                // Other directives, such as `# pyrefly: ignore`, aren't settings.
+                continue;
+            };
+            match setting {
+                Ok((key, value)) => match (settings.get_mut(&key), value) {
+                    // Let several comments each configure some error kinds.
+                    (Some(Value::Table(old)), Value::Table(new)) => old.extend(new),
+                    (_, value) => {
+                        settings.insert(key, value);
+                    }
+                },
+                Err(msg) => res
+                    .errors
+                    .push((TextRange::at(start, TextSize::of(line)), msg)),
+            }
+        }
+        if !settings.is_empty() {
+            // Each setting was checked on its own, so together they must be valid too.
+            res.config = Value::Table(settings).try_into().unwrap_or_default();
+        }
+        res
+    }
+
+    /// Parse a single `<key> = <value>` setting, where string values may be left unquoted.
+    fn parse(key: &str, value: &str) -> Result<(String, Value), String> {
+        if key == "replace-imports-with-any" {
+            return Err(format!(
+                "`{key}` can only be set in a config file, not for a single file"
+            ));
+        }
+        let value = toml::from_str::<Table>(&format!("x = {value}"))
+            .ok()
+            .and_then(|mut x| x.remove("x"))
+            .unwrap_or_else(|| Value::String(value.to_owned()));
+        let table = Table::from_iter([(key.to_owned(), value.clone())]);
+        match Value::Table(table).try_into::<ConfigBase>() {
+            Ok(config) if config.extras.0.is_empty() => Ok((key.to_owned(), value)),
+            Ok(_) => Err(format!("`{key}` is not a setting that can be configured")),
+            Err(e) => Err(format!("Invalid value for `{key}`: {}", e.message())),
+        }
+    }
+
+    pub fn config(&self) -> &ConfigBase {
+        &self.config
+    }
+
+    /// The comments we couldn't apply, with the reason why.
+    pub fn errors(&self) -> &[(TextRange, String)] {
+        &self.errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::base::UntypedDefBehavior;
+    use crate::error::kind::ErrorKind;
+
+    #[test]
+    fn test_inline_config() {
+        let config = InlineConfig::new(
+            r#"#!/usr/bin/env python3
+# pyrefly: errors = {bad-override = false}
+# pyrefly: untyped-def-behavior = check-and-infer-return-any
+
+#   pyrefly: errors = {bad-return = "warn"}
+# pyrefly: ignore-errors-in-generated-code = true
+x = 1
+# pyrefly: solve-budget-ms = 10
+"#,
+        );
+        assert!(config.errors().is_empty());
+        let errors = config.config().errors.as_ref().unwrap();
+        assert!(!errors.is_enabled(ErrorKind::BadOverride));
+        assert!(errors.is_enabled(ErrorKind::BadReturn));
+        assert!(errors.is_enabled(ErrorKind::BadAssignment));
+        assert_eq!(
+            config.config().untyped_def_behavior,
+            Some(UntypedDefBehavior::CheckAndInferReturnAny)
+        );
+        assert_eq!(config.config().ignore_errors_in_generated_code, Some(true));
+        assert_eq!(config.config().solve_budget_ms, None);
+    }
+
+    #[test]
+    fn test_inline_config_errors() {
+        let code = r#"
+# pyrefly: errors = {not-a-kind = false}
+# pyrefly: untyped-def-behavior = sometimes
+# pyrefly: search-path = ["foo"]
+# pyrefly: replace-imports-with-any = ["foo"]
+# pyrefly: solve-budget-ms = 10
+"#;
+        let config = InlineConfig::new(code);
+        let errors = config
+            .errors()
+            .iter()
+            .map(|(range, _)| &code[*range])
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![
+                "# pyrefly: errors = {not-a-kind = false}",
+                "# pyrefly: untyped-def-behavior = sometimes",
+                "# pyrefly: search-path = [\"foo\"]",
+                "# pyrefly: replace-imports-with-any = [\"foo\"]",
+            ]
+        );
+        assert_eq!(config.config().solve_budget_ms, Some(10));
+    }
+
+    #[test]
+    fn test_inline_config_strict() {
+        let config = InlineConfig::new("# pyrefly: strict\nx = 1\n");
+        assert!(config.errors().is_empty());
+        assert_eq!(config.config().strict, Some(true));
+
+        let config = InlineConfig::new("# pyrefly: strict = false\nx = 1\n");
+        assert_eq!(config.config().strict, Some(false));
+    }
+
+    #[test]
+    fn test_inline_config_other_directives() {
+        let config = InlineConfig::new(
+            "# pyrefly: ignore # a=b\n# pyrefly: ignore-all-errors\n# pyrefly: ignore[bad-return] # x = 1\nx = 1\n",
+        );
+        assert!(config.errors().is_empty());
+        assert!(config.config().strict.is_none());
+    }
+}
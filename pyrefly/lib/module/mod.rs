@@ -9,6 +9,7 @@ pub mod bundled;
 pub mod display;
 pub mod finder;
 pub mod ignore;
+pub mod inline_config;
 pub mod module_info;
 pub mod module_name;
 pub mod module_path;
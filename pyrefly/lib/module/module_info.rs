@@ -26,6 +26,7 @@ use vec1::vec1;
 use crate::error::collector::ErrorCollector;
 use crate::error::kind::ErrorKind;
 use crate::module::ignore::Ignore;
+use crate::module::inline_config::InlineConfig;
 use crate::module::module_name::ModuleName;
 use crate::module::module_path::ModulePath;
 use crate::ruff::ast::Ast;
@@ -86,6 +87,7 @@ struct ModuleInfoInner {
     path: ModulePath,
     index: LineIndex,
     ignore: Ignore,
+    inline_config: InlineConfig,
     is_generated: bool,
     contents: Arc<String>,
 }
@@ -95,12 +97,14 @@ impl ModuleInfo {
     pub fn new(name: ModuleName, path: ModulePath, contents: Arc<String>) -> Self {
         let index = LineIndex::from_source_text(&contents);
         let ignore = Ignore::new(&contents);
+        let inline_config = InlineConfig::new(&contents);
         let is_generated = contents.contains(GENERATED_TOKEN);
         Self(ArcId::new(ModuleInfoInner {
             name,
             path,
             index,
             ignore,
+            inline_config,
             is_generated,
             contents,
         }))
@@ -225,9 +229,10 @@ impl ModuleInfo {
         self.0.index.line_start(line, &self.0.contents)
     }
 
-    pub fn is_ignored(&self, source_range: &SourceRange) -> bool {
-        // Extend the range of the error to include comment lines before it.
-        // This makes it so that the preceding ignore could "see through" comments.
+    /// The lines an ignore comment could be on to suppress an error at `source_range`.
+    /// Extends the range of the error to include comment lines before it.
+    /// This makes it so that the preceding ignore could "see through" comments.
+    pub fn ignore_range(&self, source_range: &SourceRange) -> (OneIndexed, OneIndexed) {
         let start_line = {
             let mut start_line = source_range.start.line;
             while let Some(earlier_line) = start_line.checked_sub(OneIndexed::MIN) {
@@ -244,7 +249,16 @@ impl ModuleInfo {
             }
             start_line
         };
-        self.0.ignore.is_ignored(start_line, source_range.end.line)
+        (start_line, source_range.end.line)
+    }
+
+    pub fn is_ignored(&self, source_range: &SourceRange, kind: ErrorKind) -> bool {
+        let (start_line, end_line) = self.ignore_range(source_range);
+        self.0.ignore.is_ignored(start_line, end_line, kind)
+    }
+
+    pub fn inline_config(&self) -> &InlineConfig {
+        &self.0.inline_config
     }
 
     pub fn ignore(&self) -> &Ignore {
@@ -252,7 +266,7 @@ impl ModuleInfo {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TextRangeWithModuleInfo {
     pub module_info: ModuleInfo,
     pub range: TextRange,
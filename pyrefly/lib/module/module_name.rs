@@ -130,10 +130,30 @@ impl ModuleName {
         Self::from_str("abc")
     }
 
+    pub fn attr() -> Self {
+        Self::from_str("attr")
+    }
+
+    pub fn pydantic_fields() -> Self {
+        Self::from_str("pydantic.fields")
+    }
+
+    pub fn pydantic_functional_validators() -> Self {
+        Self::from_str("pydantic.functional_validators")
+    }
+
     pub fn dataclasses() -> Self {
         Self::from_str("dataclasses")
     }
 
+    pub fn functools() -> Self {
+        Self::from_str("functools")
+    }
+
+    pub fn future() -> Self {
+        Self::from_str("__future__")
+    }
+
     pub fn type_checker_internals() -> Self {
         Self::from_str("_typeshed._type_checker_internals")
     }
@@ -0,0 +1,170 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Extension points for teaching the type checker about frameworks whose behavior can't be
+//! expressed in stubs, such as ORMs that turn class attributes into constructor parameters.
+//!
+//! A [`Plugin`] is registered on a [`Plugins`] registry under the qualified name of a class or
+//! function, and the registry is handed to `State`. Plugins registered under a class name are
+//! consulted for every class that inherits from it, and plugins registered under a decorator
+//! name for every class decorated with it. Plugins registered under a function or class name
+//! are also consulted whenever it is called.
+
+use std::fmt;
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::sync::Arc;
+
+use pyrefly_derive::TypeEq;
+use ruff_python_ast::name::Name;
+use starlark_map::small_map::SmallMap;
+
+use crate::alt::types::class_metadata::ClassSynthesizedFields;
+use crate::module::module_name::ModuleName;
+use crate::types::callable::FuncId;
+use crate::types::class::Class;
+use crate::types::stdlib::Stdlib;
+use crate::types::types::Type;
+
+/// The qualified name of the class or function a plugin is registered for. Methods are named
+/// `Class.method`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, TypeEq)]
+pub struct PluginKey {
+    pub module: ModuleName,
+    pub name: Name,
+}
+
+impl PluginKey {
+    pub fn new(module: ModuleName, name: Name) -> Self {
+        Self { module, name }
+    }
+
+    pub fn of_func_id(func_id: &FuncId) -> Self {
+        let name = match &func_id.cls {
+            Some(cls) => Name::new(format!("{cls}.{}", func_id.func)),
+            None => func_id.func.clone(),
+        };
+        Self::new(func_id.module, name)
+    }
+}
+
+impl Display for PluginKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.module, self.name)
+    }
+}
+
+/// The view of the type checker that plugins get to see.
+pub trait PluginContext {
+    fn stdlib(&self) -> &Stdlib;
+
+    /// The type of an instance of `cls`.
+    fn instantiate(&self, cls: &Class) -> Type;
+
+    /// The type of the attribute `name` declared in the body of `cls` or one of its ancestors.
+    /// Fields synthesized for `cls` are not visible, since this is called while computing them.
+    fn class_field_type(&self, cls: &Class, name: &Name) -> Option<Type>;
+
+    /// Does `cls` inherit from the class with the given qualified name?
+    fn has_ancestor(&self, cls: &Class, module: &str, name: &str) -> bool;
+}
+
+/// Framework-specific behavior. All the hooks default to doing nothing.
+pub trait Plugin: Send + Sync {
+    /// Synthesize fields, such as `__init__`, for a class that inherits from or is decorated
+    /// with the name the plugin is registered under. Fields the class defines itself take
+    /// precedence over synthesized ones.
+    fn class_synthesized_fields(
+        &self,
+        _ctx: &dyn PluginContext,
+        _cls: &Class,
+    ) -> Option<ClassSynthesizedFields> {
+        None
+    }
+
+    /// Replace the return type of a call to the function or class the plugin is registered
+    /// under. `args` and `keywords` are the types of the arguments, and `ret` is the return type
+    /// we inferred from the signature.
+    fn call_return_type(
+        &self,
+        _ctx: &dyn PluginContext,
+        _args: &[Type],
+        _keywords: &[(Name, Type)],
+        _ret: &Type,
+    ) -> Option<Type> {
+        None
+    }
+}
+
+/// The plugins known to a `State`, keyed by qualified name.
+#[derive(Clone, Default)]
+pub struct Plugins(SmallMap<PluginKey, Arc<dyn Plugin>>);
+
+impl Plugins {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `plugin` under a qualified name, e.g. `django.db.models.Model`. The module is
+    /// everything up to the last dot, except for methods, which can be registered with
+    /// [`Plugins::register_key`].
+    pub fn register(&mut self, qualified_name: &str, plugin: Arc<dyn Plugin>) {
+        let (module, name) = qualified_name
+            .rsplit_once('.')
+            .unwrap_or(("builtins", qualified_name));
+        self.register_key(
+            PluginKey::new(ModuleName::from_str(module), Name::new(name)),
+            plugin,
+        );
+    }
+
+    pub fn register_key(&mut self, key: PluginKey, plugin: Arc<dyn Plugin>) {
+        self.0.insert(key, plugin);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, key: &PluginKey) -> Option<&Arc<dyn Plugin>> {
+        self.0.get(key)
+    }
+
+    /// Look up the plugin for a qualified name without building a key if there are no plugins.
+    pub fn get_by_name(
+        &self,
+        module: ModuleName,
+        name: &Name,
+    ) -> Option<(PluginKey, &Arc<dyn Plugin>)> {
+        if self.is_empty() {
+            return None;
+        }
+        let key = PluginKey::new(module, name.clone());
+        let plugin = self.0.get(&key)?;
+        Some((key, plugin))
+    }
+}
+
+impl Debug for Plugins {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.keys()).finish()
+    }
+}
+
+impl PartialEq for Plugins {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self.0.iter().all(|(key, plugin)| {
+                other
+                    .0
+                    .get(key)
+                    .is_some_and(|other_plugin| Arc::ptr_eq(plugin, other_plugin))
+            })
+    }
+}
+
+impl Eq for Plugins {}
@@ -58,7 +58,26 @@ impl ReportKey {
     }
 }
 
+/// The number of bindings (counting keys and values separately), and the shallow size of them.
+pub fn bindings_size(bindings: &Bindings) -> (usize, usize) {
+    #[allow(clippy::trivially_copy_pass_by_ref)] // required to match the macro signature
+    fn f<K: Keyed>(_: &PhantomData<K>, entry: &Bindings, res: &mut (usize, usize))
+    where
+        BindingTable: TableKeyed<K, Value = BindingEntry<K>>,
+    {
+        for idx in entry.keys::<K>() {
+            res.0 += 2;
+            res.1 += mem::size_of_val(entry.idx_to_key(idx)) + mem::size_of_val(entry.get(idx));
+        }
+    }
+
+    let mut res = (0, 0);
+    table_for_each!(&PhantomTable::default(), |v| f(v, bindings, &mut res));
+    res
+}
+
 /// Report on how many there are of each binding, and how much memory they take up, per module.
+/// Modules whose bindings were evicted by a memory budget are reported as `Evicted`.
 pub fn binding_memory(transaction: &Transaction) -> String {
     #[allow(clippy::trivially_copy_pass_by_ref)] // required to match the macro signature
     fn f<K: Keyed>(
@@ -80,7 +99,9 @@ pub fn binding_memory(transaction: &Transaction) -> String {
     let mut report = SmallMap::new();
     let phantom_table = PhantomTable::default();
     for handle in transaction.handles() {
-        let bindings = transaction.get_bindings(&handle).unwrap();
+        let Some(bindings) = transaction.get_bindings(&handle) else {
+            continue;
+        };
         table_for_each!(&phantom_table, |v| f(
             v,
             handle.module(),
@@ -106,6 +127,19 @@ pub fn binding_memory(transaction: &Transaction) -> String {
         )
         .unwrap();
     }
+    let mut evicted = transaction.evicted();
+    evicted.sort_by_key(|(_, x)| x.binding_shallow_bytes);
+    evicted.reverse();
+    for (handle, x) in evicted {
+        writeln!(
+            res,
+            "{},Evicted,Bindings,{},{}",
+            handle.module(),
+            x.bindings,
+            x.binding_shallow_bytes
+        )
+        .unwrap();
+    }
     res
 }
 
@@ -162,6 +196,8 @@ mod tests {
             decorators: Default::default(),
             is_new_type: false,
             special_base: None,
+            slots: None,
+            model_config: None,
         };
         assert_eq!(
             ReportKey::new(module, &v),
@@ -0,0 +1,271 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! The import graph of a set of modules, as resolved by the checker, along with its cycles.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use dupe::Dupe;
+use pyrefly_util::prelude::SliceExt;
+use serde::Serialize;
+
+use crate::module::module_path::ModulePath;
+use crate::module::module_path::ModulePathDetails;
+use crate::state::handle::Handle;
+use crate::state::state::Transaction;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum ModuleKind {
+    Source,
+    Stub,
+    BundledTypeshed,
+    Namespace,
+}
+
+impl ModuleKind {
+    fn new(path: &ModulePath) -> Self {
+        match path.details() {
+            ModulePathDetails::BundledTypeshed(_) => Self::BundledTypeshed,
+            ModulePathDetails::Namespace(_) => Self::Namespace,
+            _ if path.is_interface() => Self::Stub,
+            _ => Self::Source,
+        }
+    }
+}
+
+/// The modules we checked and everything they import, sorted by name, with the imports
+/// of each one as indices into the modules.
+struct Graph {
+    modules: Vec<Handle>,
+    imports: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    fn new(transaction: &Transaction, handles: &[Handle]) -> Self {
+        let edges = handles.map(|h| (h.dupe(), transaction.get_imports(h)));
+        let mut modules = edges
+            .iter()
+            .flat_map(|(h, imports)| std::iter::once(h).chain(imports))
+            .cloned()
+            .collect::<Vec<_>>();
+        modules.sort_by(|a, b| (a.module(), a.path()).cmp(&(b.module(), b.path())));
+        modules.dedup();
+        let index = modules
+            .iter()
+            .enumerate()
+            .map(|(i, h)| (h.dupe(), i))
+            .collect::<HashMap<_, _>>();
+        let mut imports = vec![Vec::new(); modules.len()];
+        for (h, deps) in edges {
+            let res = &mut imports[index[&h]];
+            res.extend(deps.iter().map(|x| index[x]));
+            res.sort_unstable();
+            res.dedup();
+        }
+        Self { modules, imports }
+    }
+
+    /// The import cycles, as the strongly connected components with more than one module, or a
+    /// module that imports itself. Found with Tarjan's algorithm, without recursion, as import
+    /// chains can be long.
+    fn cycles(&self) -> Vec<Vec<usize>> {
+        let n = self.imports.len();
+        let mut index = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut next = 0;
+        let mut res = Vec::new();
+        for root in 0..n {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            // Each entry is a module, and the position of the next import of it to visit.
+            let mut work = vec![(root, 0)];
+            while let Some((v, i)) = work.pop() {
+                if i == 0 {
+                    index[v] = next;
+                    low[v] = next;
+                    next += 1;
+                    stack.push(v);
+                    on_stack[v] = true;
+                }
+                if let Some(&w) = self.imports[v].get(i) {
+                    work.push((v, i + 1));
+                    if index[w] == usize::MAX {
+                        work.push((w, 0));
+                    } else if on_stack[w] {
+                        low[v] = low[v].min(index[w]);
+                    }
+                    continue;
+                }
+                if low[v] == index[v] {
+                    let mut component = Vec::new();
+                    loop {
+                        let w = stack.pop().unwrap();
+                        on_stack[w] = false;
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    if component.len() > 1 || self.imports[v].contains(&v) {
+                        component.sort_unstable();
+                        res.push(component);
+                    }
+                }
+                if let Some(&(parent, _)) = work.last() {
+                    low[parent] = low[parent].min(low[v]);
+                }
+            }
+        }
+        res.sort();
+        res
+    }
+
+    fn name(&self, i: usize) -> String {
+        self.modules[i].module().to_string()
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct DepsModule {
+    name: String,
+    path: String,
+    kind: ModuleKind,
+}
+
+#[derive(Debug, Serialize)]
+struct DepsEdge {
+    from: String,
+    to: String,
+}
+
+#[derive(Debug, Serialize)]
+struct DepsReport {
+    modules: Vec<DepsModule>,
+    imports: Vec<DepsEdge>,
+    cycles: Vec<Vec<String>>,
+}
+
+/// The number of modules, imports and import cycles among `handles` and the modules they import.
+pub fn deps_summary(transaction: &Transaction, handles: &[Handle]) -> (usize, usize, usize) {
+    let graph = Graph::new(transaction, handles);
+    (
+        graph.modules.len(),
+        graph.imports.iter().map(|x| x.len()).sum(),
+        graph.cycles().len(),
+    )
+}
+
+/// The import graph as JSON, with a list of modules, a list of import edges, and a list of cycles.
+pub fn deps_json(transaction: &Transaction, handles: &[Handle]) -> String {
+    let graph = &Graph::new(transaction, handles);
+    let report = DepsReport {
+        modules: graph.modules.map(|h| DepsModule {
+            name: h.module().to_string(),
+            path: h.path().to_string(),
+            kind: ModuleKind::new(h.path()),
+        }),
+        imports: graph
+            .imports
+            .iter()
+            .enumerate()
+            .flat_map(|(from, tos)| {
+                tos.iter().map(move |to| DepsEdge {
+                    from: graph.name(from),
+                    to: graph.name(*to),
+                })
+            })
+            .collect(),
+        cycles: graph
+            .cycles()
+            .into_iter()
+            .map(|x| x.into_iter().map(|i| graph.name(i)).collect())
+            .collect(),
+    };
+    serde_json::to_string_pretty(&report).unwrap()
+}
+
+/// The import graph in the Graphviz DOT language. Stubs are drawn as boxes, bundled typeshed
+/// modules are greyed out, and imports that are part of a cycle are red.
+pub fn deps_dot(transaction: &Transaction, handles: &[Handle]) -> String {
+    let graph = Graph::new(transaction, handles);
+    let mut cycle = vec![None; graph.modules.len()];
+    for (i, x) in graph.cycles().into_iter().enumerate() {
+        for m in x {
+            cycle[m] = Some(i);
+        }
+    }
+    let mut res = String::new();
+    writeln!(res, "digraph imports {{").unwrap();
+    for (i, h) in graph.modules.iter().enumerate() {
+        let attrs = match ModuleKind::new(h.path()) {
+            ModuleKind::Stub => ", shape=box",
+            ModuleKind::BundledTypeshed => ", shape=box, color=gray, fontcolor=gray",
+            ModuleKind::Namespace => ", style=dashed",
+            ModuleKind::Source => "",
+        };
+        writeln!(
+            res,
+            "  {i} [label={:?}, tooltip={:?}{attrs}];",
+            h.module().as_str(),
+            h.path().to_string()
+        )
+        .unwrap();
+    }
+    for (from, tos) in graph.imports.iter().enumerate() {
+        for to in tos {
+            let attrs = if cycle[from].is_some() && cycle[from] == cycle[*to] {
+                " [color=red]"
+            } else {
+                ""
+            };
+            writeln!(res, "  {from} -> {to}{attrs};").unwrap();
+        }
+    }
+    writeln!(res, "}}").unwrap();
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::util::TestEnv;
+
+    #[test]
+    fn test_deps() {
+        let mut t = TestEnv::new();
+        t.add("a", "import b\nimport c");
+        t.add("b", "from a import *");
+        t.add("c", "import d");
+        t.add("d", "import c\nimport typing");
+        t.add("e", "import e");
+        let (state, handle) = t.to_state();
+        let handles = ["a", "b", "c", "d", "e"].map(handle);
+        let transaction = state.transaction();
+
+        let graph = Graph::new(&transaction, &handles);
+        let cycles = graph
+            .cycles()
+            .into_iter()
+            .map(|x| x.into_iter().map(|i| graph.name(i)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(cycles, vec![vec!["a", "b"], vec!["c", "d"], vec!["e"]]);
+
+        let json = deps_json(&transaction, &handles);
+        assert!(json.contains(r#""from": "a","#));
+        assert!(json.contains(r#""name": "typing","#));
+        assert!(json.contains(r#""kind": "bundled-typeshed""#));
+
+        let dot = deps_dot(&transaction, &handles);
+        assert!(dot.starts_with("digraph imports {"));
+        assert!(dot.contains("[color=red]"));
+    }
+}
@@ -7,5 +7,6 @@
 
 pub mod binding_memory;
 pub mod debug_info;
+pub mod deps;
 pub mod glean;
 pub mod trace;
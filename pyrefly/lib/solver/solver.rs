@@ -24,8 +24,12 @@ use vec1::vec1;
 
 use crate::alt::answers::LookupAnswer;
 use crate::error::collector::ErrorCollector;
+use crate::error::context::SubsetFailure;
+use crate::error::context::SubsetReason;
 use crate::error::context::TypeCheckContext;
 use crate::error::context::TypeCheckKind;
+use crate::error::payload::ErrorPayload;
+use crate::module::module_info::TextRangeWithModuleInfo;
 use crate::solver::type_order::TypeOrder;
 use crate::types::callable::Callable;
 use crate::types::callable::Function;
@@ -392,24 +396,60 @@ impl Solver {
         errors: &ErrorCollector,
         loc: TextRange,
         tcc: &dyn Fn() -> TypeCheckContext,
+        failures: &[SubsetFailure],
     ) {
         let tcc = tcc();
-        let msg = tcc.kind.format_error(
-            &self.for_display(got.clone()),
-            &self.for_display(want.clone()),
-            errors.module_info().name(),
-        );
+        let got = self.for_display(got.clone());
+        let want = self.for_display(want.clone());
+        let msg = tcc
+            .kind
+            .format_error(&got, &want, errors.module_info().name());
         let kind = tcc.kind.as_error_kind();
+        let mut because = Vec::new();
+        self.format_failures(failures, 0, None, &mut because);
+        let payload = ErrorPayload::type_check(&tcc.kind, &got, &want);
         match tcc.context {
             Some(ctx) => {
-                errors.add(loc, kind, Some(&|| ctx.clone()), vec1![msg]);
+                errors.add_detailed(
+                    loc,
+                    kind,
+                    Some(&|| ctx.clone()),
+                    vec1![msg],
+                    because,
+                    payload,
+                );
             }
             None => {
-                errors.add(loc, kind, None, vec1![msg]);
+                errors.add_detailed(loc, kind, None, vec1![msg], because, payload);
             }
         }
     }
 
+    /// Format the reasons a subset check failed as lines, indented by how deeply they are nested,
+    /// each with the location of the nearest failure that has one.
+    fn format_failures(
+        &self,
+        failures: &[SubsetFailure],
+        depth: usize,
+        location: Option<&TextRangeWithModuleInfo>,
+        out: &mut Vec<(String, Option<TextRangeWithModuleInfo>)>,
+    ) {
+        for failure in failures {
+            let location = failure.location.as_ref().or(location);
+            out.push((
+                format!(
+                    "{}{}",
+                    "  ".repeat(depth),
+                    failure
+                        .reason
+                        .format(&|t: &Type| self.for_display(t.clone()))
+                ),
+                location.cloned(),
+            ));
+            self.format_failures(&failure.nested, depth + 1, location, out);
+        }
+    }
+
     /// Union a list of types together. In the process may cause some variables to be forced.
     pub fn unions<Ans: LookupAnswer>(
         &self,
@@ -523,6 +563,22 @@ impl Solver {
         self.is_subset_eq_impl(got, want, type_order, false)
     }
 
+    /// Like `is_subset_eq`, but if the check fails, returns the reasons why.
+    pub fn is_subset_eq_explained<Ans: LookupAnswer>(
+        &self,
+        got: &Type,
+        want: &Type,
+        type_order: TypeOrder<Ans>,
+    ) -> Result<(), Vec<SubsetFailure>> {
+        let mut subset = self.subset(type_order, false);
+        subset.explain = true;
+        if subset.is_subset_eq(got, want) {
+            Ok(())
+        } else {
+            Err(subset.failures)
+        }
+    }
+
     pub fn is_subset_eq_impl<Ans: LookupAnswer>(
         &self,
         got: &Type,
@@ -530,14 +586,23 @@ impl Solver {
         type_order: TypeOrder<Ans>,
         union: bool,
     ) -> bool {
-        let mut subset = Subset {
+        self.subset(type_order, union).is_subset_eq(got, want)
+    }
+
+    fn subset<'a, Ans: LookupAnswer>(
+        &'a self,
+        type_order: TypeOrder<'a, Ans>,
+        union: bool,
+    ) -> Subset<'a, Ans> {
+        Subset {
             solver: self,
             type_order,
             union,
             gas: INITIAL_GAS,
             recursive_assumptions: SmallSet::new(),
-        };
-        subset.is_subset_eq(got, want)
+            explain: false,
+            failures: Vec::new(),
+        }
     }
 }
 
@@ -552,6 +617,11 @@ pub struct Subset<'a, Ans: LookupAnswer> {
     /// Recursive assumptions of pairs of types that is_subset_eq returns true for.
     /// Used for structural typechecking of protocols.
     pub recursive_assumptions: SmallSet<(Type, Type)>,
+    /// Whether to record `failures`, which is only worth it if we are going to explain them.
+    explain: bool,
+    /// Why the checks that failed so far failed, used to explain the error to the user.
+    /// Reasons recorded by a check that eventually succeeds are discarded.
+    pub failures: Vec<SubsetFailure>,
 }
 
 impl<'a, Ans: LookupAnswer> Subset<'a, Ans> {
@@ -568,11 +638,71 @@ impl<'a, Ans: LookupAnswer> Subset<'a, Ans> {
             // We really have no idea. Just give up for now.
             return false;
         }
+        let failures = self.failures.len();
         let res = self.is_subset_eq_var(got, want);
+        if res {
+            self.failures.truncate(failures);
+        }
         self.gas.restore();
         res
     }
 
+    /// Run the check `f`, and if it fails, record `reason` as the cause, with anything `f`
+    /// recorded nested beneath it.
+    pub fn explain(
+        &mut self,
+        reason: impl FnOnce() -> SubsetReason,
+        f: impl FnOnce(&mut Self) -> bool,
+    ) -> bool {
+        self.explain_at(reason, || None, f)
+    }
+
+    /// Like `explain`, but with the location of the thing `reason` is about.
+    pub fn explain_at(
+        &mut self,
+        reason: impl FnOnce() -> SubsetReason,
+        location: impl FnOnce() -> Option<TextRangeWithModuleInfo>,
+        f: impl FnOnce(&mut Self) -> bool,
+    ) -> bool {
+        if !self.explain {
+            return f(self);
+        }
+        let failures = self.failures.len();
+        if f(self) {
+            self.failures.truncate(failures);
+            true
+        } else {
+            let nested = self.failures.split_off(failures);
+            self.failures.push(SubsetFailure {
+                reason: reason(),
+                location: location(),
+                nested,
+            });
+            false
+        }
+    }
+
+    /// Record `reason` as the cause of a failed check, and return `false`.
+    pub fn fail(&mut self, reason: SubsetReason) -> bool {
+        self.fail_at(reason, || None)
+    }
+
+    /// Like `fail`, but with the location of the thing `reason` is about.
+    pub fn fail_at(
+        &mut self,
+        reason: SubsetReason,
+        location: impl FnOnce() -> Option<TextRangeWithModuleInfo>,
+    ) -> bool {
+        if self.explain {
+            self.failures.push(SubsetFailure {
+                reason,
+                location: location(),
+                nested: Vec::new(),
+            });
+        }
+        false
+    }
+
     /// Implementation of Var subset cases, calling onward to solve non-Var cases.
     fn is_subset_eq_var(&mut self, got: &Type, want: &Type) -> bool {
         // This function does two things: it checks that got <: want, and it solves free variables assuming that
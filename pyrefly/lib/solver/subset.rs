@@ -17,6 +17,7 @@ use starlark_map::small_map::SmallMap;
 
 use crate::alt::answers::LookupAnswer;
 use crate::dunder;
+use crate::error::context::SubsetReason;
 use crate::solver::solver::Subset;
 use crate::types::callable::Callable;
 use crate::types::callable::Function;
@@ -49,7 +50,10 @@ impl<'a, Ans: LookupAnswer> Subset<'a, Ans> {
                     Some(Param::PosOnly(_, l, l_req) | Param::Pos(_, l, l_req)),
                     Some(Param::PosOnly(_, u, u_req)),
                 ) if (*u_req == Required::Required || *l_req == Required::Optional) => {
-                    if self.is_subset_eq(u, l) {
+                    if self.explain(
+                        || SubsetReason::Param(None, l.clone(), u.clone()),
+                        |me| me.is_subset_eq(u, l),
+                    ) {
                         l_arg = l_args.next();
                         u_arg = u_args.next();
                     } else {
@@ -60,7 +64,10 @@ impl<'a, Ans: LookupAnswer> Subset<'a, Ans> {
                     if l_name == u_name
                         && (*u_req == Required::Required || *l_req == Required::Optional) =>
                 {
-                    if self.is_subset_eq(u, l) {
+                    if self.explain(
+                        || SubsetReason::Param(Some(l_name.clone()), l.clone(), u.clone()),
+                        |me| me.is_subset_eq(u, l),
+                    ) {
                         l_arg = l_args.next();
                         u_arg = u_args.next();
                     } else {
@@ -288,16 +295,22 @@ impl<'a, Ans: LookupAnswer> Subset<'a, Ans> {
         for (name, (u_ty, u_req)) in u_keywords.iter() {
             if let Some((l_ty, l_req)) = l_keywords.remove(name) {
                 if !(*u_req == Required::Required || l_req == Required::Optional)
-                    || !self.is_subset_eq(u_ty, &l_ty)
+                    || !self.explain(
+                        || SubsetReason::Param(Some(name.clone()), l_ty.clone(), u_ty.clone()),
+                        |me| me.is_subset_eq(u_ty, &l_ty),
+                    )
                 {
                     return false;
                 }
             } else if let Some(l_ty) = &l_kwargs {
-                if !self.is_subset_eq(u_ty, l_ty) {
+                if !self.explain(
+                    || SubsetReason::Param(Some(name.clone()), l_ty.clone(), u_ty.clone()),
+                    |me| me.is_subset_eq(u_ty, l_ty),
+                ) {
                     return false;
                 }
             } else {
-                return false;
+                return self.fail(SubsetReason::MissingParam(name.clone()));
             }
         }
         for (_, l_req) in l_keywords.values() {
@@ -397,13 +410,16 @@ impl<'a, Ans: LookupAnswer> Subset<'a, Ans> {
             ) && name == dunder::CALL
                 && let Some(want) = self.try_lookup_attr_from_class(&protocol, &dunder::CALL)
             {
-                if let Type::BoundMethod(method) = &want
-                    && let Some(want_no_self) = method.to_callable()
-                {
-                    if !self.is_subset_eq(&got, &want_no_self) {
-                        return false;
-                    }
-                } else if !self.is_subset_eq(&got, &want) {
+                let want_no_self = match &want {
+                    Type::BoundMethod(method) => method.to_callable(),
+                    _ => None,
+                };
+                let want = want_no_self.unwrap_or(want);
+                if !self.explain_at(
+                    || SubsetReason::ProtocolMember(name.clone()),
+                    || to.field_definition(protocol.class_object(), &name),
+                    |me| me.is_subset_eq(&got, &want),
+                ) {
                     return false;
                 }
             } else if let got_attrs = to.try_lookup_attr(&got, &name)
@@ -411,14 +427,22 @@ impl<'a, Ans: LookupAnswer> Subset<'a, Ans> {
                 && let Some(want) = to.try_lookup_attr_from_class_type(protocol.clone(), &name)
             {
                 for got in got_attrs {
-                    if !to
-                        .is_attr_subset(&got, &want, &mut |got, want| self.is_subset_eq(got, want))
-                    {
+                    if !self.explain_at(
+                        || SubsetReason::ProtocolMember(name.clone()),
+                        || to.field_definition(protocol.class_object(), &name),
+                        |me| {
+                            to.is_attr_subset(&got, &want, &mut |got, want| {
+                                me.is_subset_eq(got, want)
+                            })
+                        },
+                    ) {
                         return false;
                     }
                 }
             } else {
-                return false;
+                return self.fail_at(SubsetReason::MissingProtocolMember(name.clone()), || {
+                    to.field_definition(protocol.class_object(), &name)
+                });
             }
         }
         true
@@ -718,7 +742,11 @@ impl<'a, Ans: LookupAnswer> Subset<'a, Ans> {
                         self.is_paramspec_subset_of_paramspec(ls, p1, us, p2)
                     }
                 };
-                args_subset && self.is_subset_eq(&l.ret, &u.ret)
+                args_subset
+                    && self.explain(
+                        || SubsetReason::ReturnType(l.ret.clone(), u.ret.clone()),
+                        |me| me.is_subset_eq(&l.ret, &u.ret),
+                    )
             }
             (Type::TypedDict(got), Type::TypedDict(want)) => {
                 // For each key in `want`, `got` has the corresponding key
@@ -728,22 +756,44 @@ impl<'a, Ans: LookupAnswer> Subset<'a, Ans> {
                 let got_fields = self.type_order.typed_dict_fields(got);
                 let want_fields = self.type_order.typed_dict_fields(want);
 
+                let to = self.type_order;
+                let location = |k: &Name| to.field_definition(want.class_object(), k);
                 want_fields.iter().all(|(k, want_v)| {
-                    got_fields.get(k).is_some_and(|got_v| {
-                        match (got_v.read_only, want_v.read_only) {
-                            // ReadOnly cannot be assigned to Non-ReadOnly
-                            (true, false) => false,
-                            // Non-ReadOnly fields are invariant
-                            (false, false) => self.is_equal(&got_v.ty, &want_v.ty),
-                            // ReadOnly `want` fields are covariant
-                            (_, true) => self.is_subset_eq(&got_v.ty, &want_v.ty),
-                        }
+                    let Some(got_v) = got_fields.get(k) else {
+                        return self
+                            .fail_at(SubsetReason::MissingTypedDictKey(k.clone()), || location(k));
+                    };
+                    let reason = || {
+                        SubsetReason::TypedDictKey(k.clone(), got_v.ty.clone(), want_v.ty.clone())
+                    };
+                    match (got_v.read_only, want_v.read_only) {
+                        // ReadOnly cannot be assigned to Non-ReadOnly
+                        (true, false) => self
+                            .fail_at(SubsetReason::ReadOnlyTypedDictKey(k.clone()), || {
+                                location(k)
+                            }),
+                        // Non-ReadOnly fields are invariant
+                        (false, false) => self.explain_at(
+                            reason,
+                            || location(k),
+                            |me| me.is_equal(&got_v.ty, &want_v.ty),
+                        ),
+                        // ReadOnly `want` fields are covariant
+                        (_, true) => self.explain_at(
+                            reason,
+                            || location(k),
+                            |me| me.is_subset_eq(&got_v.ty, &want_v.ty),
+                        ),
+                    }
+                }) && got_fields
+                    .iter()
+                    .all(|(k, got_v)| match want_fields.get(k) {
+                        Some(want_v) if got_v.required != want_v.required => self.fail_at(
+                            SubsetReason::TypedDictKeyRequired(k.clone(), got_v.required),
+                            || location(k),
+                        ),
+                        _ => true,
                     })
-                }) && got_fields.iter().all(|(k, got_v)| {
-                    want_fields
-                        .get(k)
-                        .is_none_or(|want_v| got_v.required == want_v.required)
-                })
             }
             (Type::TypedDict(_), Type::SelfType(cls))
                 if cls == self.type_order.stdlib().typed_dict_fallback() =>
@@ -1024,23 +1074,29 @@ impl<'a, Ans: LookupAnswer> Subset<'a, Ans> {
             .get_variance_from_class(got_class.class_object());
 
         for (got_arg, want_arg, param) in izip!(got, want, params.iter()) {
-            let result = if param.quantified.kind() == QuantifiedKind::TypeVarTuple {
-                self.is_equal(got_arg, want_arg)
+            let variance = if param.quantified.kind() == QuantifiedKind::TypeVarTuple {
+                Variance::Invariant
             } else {
-                let param_name = param.name().as_str();
-
-                let param_variance = match variances.0.get(param_name) {
-                    Some(variance) => *variance,
-                    None => Variance::Invariant,
-                };
-
-                match param_variance {
-                    Variance::Covariant => self.is_subset_eq(got_arg, want_arg),
-                    Variance::Contravariant => self.is_subset_eq(want_arg, got_arg),
-                    Variance::Invariant => self.is_equal(got_arg, want_arg),
-                    Variance::Bivariant => true,
-                }
+                variances
+                    .0
+                    .get(param.name().as_str())
+                    .copied()
+                    .unwrap_or(Variance::Invariant)
+            };
+            let reason = || {
+                SubsetReason::TypeArgument(
+                    param.name().clone(),
+                    variance,
+                    got_arg.clone(),
+                    want_arg.clone(),
+                )
             };
+            let result = self.explain(reason, |me| match variance {
+                Variance::Covariant => me.is_subset_eq(got_arg, want_arg),
+                Variance::Contravariant => me.is_subset_eq(want_arg, got_arg),
+                Variance::Invariant => me.is_equal(got_arg, want_arg),
+                Variance::Bivariant => true,
+            });
             if !result {
                 return false;
             }
@@ -4,10 +4,12 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
+use std::iter;
 use std::sync::Arc;
 
 use dupe::Clone_;
 use dupe::Copy_;
+use dupe::Dupe;
 use dupe::Dupe_;
 use ruff_python_ast::name::Name;
 use starlark_map::small_map::SmallMap;
@@ -18,6 +20,7 @@ use crate::alt::answers::LookupAnswer;
 use crate::alt::attr::Attribute;
 use crate::alt::class::variance_inference::VarianceMap;
 use crate::binding::binding::KeyVariance;
+use crate::module::module_info::TextRangeWithModuleInfo;
 use crate::types::callable::Required;
 use crate::types::class::Class;
 use crate::types::class::ClassType;
@@ -83,6 +86,19 @@ impl<'a, Ans: LookupAnswer> TypeOrder<'a, Ans> {
         }
     }
 
+    /// Where the field `name` of `cls` is defined, looking through its ancestors.
+    pub fn field_definition(self, cls: &Class, name: &Name) -> Option<TextRangeWithModuleInfo> {
+        let metadata = self.0.get_metadata_for_class(cls);
+        iter::once(cls)
+            .chain(metadata.ancestors(self.0.stdlib).map(|c| c.class_object()))
+            .find_map(|c| {
+                Some(TextRangeWithModuleInfo::new(
+                    c.module_info().dupe(),
+                    c.field_decl_range(name)?,
+                ))
+            })
+    }
+
     pub fn try_lookup_attr_from_class_type(
         self,
         cls: ClassType,
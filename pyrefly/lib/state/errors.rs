@@ -9,12 +9,13 @@ use std::sync::Arc;
 
 use dupe::Dupe;
 use pyrefly_util::arc_id::ArcId;
+use ruff_source_file::OneIndexed;
 use starlark_map::small_map::SmallMap;
 
 use crate::config::config::ConfigFile;
 use crate::error::collector::CollectedErrors;
 use crate::error::expectation::Expectation;
-use crate::module::ignore::Ignore;
+use crate::module::ignore::UnusedIgnore;
 use crate::module::module_path::ModulePath;
 use crate::state::load::Load;
 
@@ -40,14 +41,20 @@ impl Errors {
         errors
     }
 
-    pub fn collect_ignores(&self) -> SmallMap<&ModulePath, &Ignore> {
-        let mut ignore_collection: SmallMap<&ModulePath, &Ignore> = SmallMap::new();
-        for (load, _) in &self.loads {
-            let module_path = load.module_info.path();
-            let ignores = load.module_info.ignore();
-            ignore_collection.insert(module_path, ignores);
-        }
-        ignore_collection
+    /// The parts of the `# pyrefly: ignore` comments in each module that don't suppress any error.
+    pub fn collect_unused_ignores(
+        &self,
+    ) -> SmallMap<&ModulePath, SmallMap<OneIndexed, UnusedIgnore>> {
+        self.loads
+            .iter()
+            .map(|(load, config)| {
+                let error_config = config.get_error_config(load.module_info.path().as_path());
+                (
+                    load.module_info.path(),
+                    load.errors.unused_ignores(&error_config),
+                )
+            })
+            .collect()
     }
 
     pub fn check_against_expectations(&self) -> anyhow::Result<()> {
@@ -75,6 +75,14 @@ impl Load {
                 )],
             );
         }
+        for (range, msg) in module_info.inline_config().errors() {
+            errors.add(
+                *range,
+                ErrorKind::InvalidInlineConfig,
+                None,
+                vec1![msg.clone()],
+            );
+        }
         Self {
             errors,
             module_info,
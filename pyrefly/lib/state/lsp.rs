@@ -5,6 +5,7 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::iter;
 use std::sync::Arc;
 
 use dupe::Dupe;
@@ -25,6 +26,7 @@ use pyrefly_util::task_heap::Cancelled;
 use pyrefly_util::visit::Visit;
 use ruff_python_ast::Alias;
 use ruff_python_ast::AnyNodeRef;
+use ruff_python_ast::ExceptHandler;
 use ruff_python_ast::Expr;
 use ruff_python_ast::ExprAttribute;
 use ruff_python_ast::ExprCall;
@@ -47,6 +49,7 @@ use crate::binding::binding::Binding;
 use crate::binding::binding::Key;
 use crate::binding::bindings::Bindings;
 use crate::common::symbol_kind::SymbolKind;
+use crate::error::error::Error;
 use crate::error::kind::ErrorKind;
 use crate::export::definitions::DocString;
 use crate::export::exports::Export;
@@ -1096,10 +1099,10 @@ impl<'a> Transaction<'a> {
         &self,
         handle: &Handle,
         range: TextRange,
+        errors: &[Error],
     ) -> Option<Vec<(String, SourceRange, String)>> {
         let module_info = self.get_module_info(handle)?;
         let ast = self.get_ast(handle)?;
-        let errors = self.get_errors(vec![handle]).collect_errors().shown;
         let mut code_actions = Vec::new();
         for error in errors {
             match error.error_kind() {
@@ -1124,6 +1127,29 @@ impl<'a> Transaction<'a> {
         Some(code_actions)
     }
 
+    /// Produce the edits that remove every import reported as unused in `errors`, the errors
+    /// shown for the file, or `None` if there are no unused imports.
+    pub fn remove_unused_imports_edits(
+        &self,
+        handle: &Handle,
+        errors: &[Error],
+    ) -> Option<Vec<(SourceRange, String)>> {
+        let module_info = self.get_module_info(handle)?;
+        let ast = self.get_ast(handle)?;
+        let unused = errors
+            .iter()
+            .filter(|e| e.error_kind() == ErrorKind::UnusedImport)
+            .map(|e| module_info.to_text_range(e.source_range()))
+            .collect::<Vec<_>>();
+        if unused.is_empty() {
+            return None;
+        }
+        let mut edits = Vec::new();
+        remove_unused_imports_in_body(&ast.body, true, &unused, &module_info, &mut edits);
+        edits.sort_by_key(|(range, _)| range.start());
+        Some(edits.into_map(|(range, text)| (module_info.source_range(range), text)))
+    }
+
     pub fn find_local_references(&self, handle: &Handle, position: TextSize) -> Vec<TextRange> {
         if let Some((definition_kind, definition, _docstring)) =
             self.find_definition(handle, position)
@@ -1336,10 +1362,10 @@ impl<'a> Transaction<'a> {
                 context: IdentifierContext::ImportedName { module_name, .. },
             }) => {
                 // TODO: Handle relative import (via ModuleName::new_maybe_relative)
-                let handle = self.import_handle(handle, module_name, None).ok()?;
-                let exports = self.get_exports(&handle);
-                let completions = exports
-                    .keys()
+                let module_handle = self.import_handle(handle, module_name, None).ok()?;
+                let completions = self
+                    .get_importable_exports(handle, &module_handle)
+                    .into_iter()
                     .map(|name| CompletionItem {
                         label: name.to_string(),
                         // todo(kylei): completion kind for exports
@@ -1881,3 +1907,100 @@ impl<'a> CancellableTransaction<'a> {
         Ok(global_references)
     }
 }
+
+/// The blocks of statements nested directly inside a statement.
+fn nested_bodies(x: &Stmt) -> Vec<&[Stmt]> {
+    match x {
+        Stmt::FunctionDef(x) => vec![x.body.as_slice()],
+        Stmt::ClassDef(x) => vec![x.body.as_slice()],
+        Stmt::If(x) => iter::once(x.body.as_slice())
+            .chain(x.elif_else_clauses.iter().map(|x| x.body.as_slice()))
+            .collect(),
+        Stmt::For(x) => vec![x.body.as_slice(), x.orelse.as_slice()],
+        Stmt::While(x) => vec![x.body.as_slice(), x.orelse.as_slice()],
+        Stmt::With(x) => vec![x.body.as_slice()],
+        Stmt::Try(x) => iter::once(x.body.as_slice())
+            .chain(x.handlers.iter().map(|x| match x {
+                ExceptHandler::ExceptHandler(x) => x.body.as_slice(),
+            }))
+            .chain([x.orelse.as_slice(), x.finalbody.as_slice()])
+            .collect(),
+        Stmt::Match(x) => x.cases.iter().map(|x| x.body.as_slice()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Compute the edits that remove the unused aliases from the imports in a block of statements.
+/// An import with no aliases left is deleted, unless that would leave the block empty, in which
+/// case it is replaced with `pass`.
+fn remove_unused_imports_in_body(
+    body: &[Stmt],
+    top_level: bool,
+    unused: &[TextRange],
+    module_info: &ModuleInfo,
+    edits: &mut Vec<(TextRange, String)>,
+) {
+    let is_unused = |x: &Alias| unused.iter().any(|r| x.range.contains_range(*r));
+    let mut deleted = Vec::new();
+    for x in body {
+        let (names, prefix) = match x {
+            Stmt::Import(x) => (&x.names, "import ".to_owned()),
+            Stmt::ImportFrom(x) => (
+                &x.names,
+                format!(
+                    "from {}{} import ",
+                    ".".repeat(x.level as usize),
+                    x.module.as_ref().map_or("", |m| m.id.as_str())
+                ),
+            ),
+            _ => {
+                for body in nested_bodies(x) {
+                    remove_unused_imports_in_body(body, false, unused, module_info, edits);
+                }
+                continue;
+            }
+        };
+        let kept = names.iter().filter(|x| !is_unused(x)).collect::<Vec<_>>();
+        if kept.len() == names.len() {
+            continue;
+        } else if kept.is_empty() {
+            deleted.push(x.range());
+        } else {
+            let kept = kept.iter().map(|x| module_info.code_at(x.range)).join(", ");
+            edits.push((x.range(), format!("{prefix}{kept}")));
+        }
+    }
+    if let Some(last) = deleted.last()
+        && deleted.len() == body.len()
+        && !top_level
+    {
+        edits.push((*last, "pass".to_owned()));
+        deleted.pop();
+    }
+    let contents = module_info.contents();
+    for range in deleted {
+        // If the import is on a line by itself, delete the whole line, including the indentation
+        // and line break, so we don't leave a blank line behind.
+        let before = &contents[..range.start().to_usize()];
+        let indent = before.len() - before.trim_end_matches([' ', '\t']).len();
+        let rest = &contents[range.end().to_usize()..];
+        let line_break = if rest.starts_with("\r\n") {
+            2
+        } else if rest.starts_with('\n') {
+            1
+        } else {
+            0
+        };
+        let range = if line_break > 0
+            && (before.len() == indent || before[..before.len() - indent].ends_with('\n'))
+        {
+            TextRange::new(
+                range.start() - TextSize::from(indent as u32),
+                range.end() + TextSize::new(line_break),
+            )
+        } else {
+            range
+        };
+        edits.push((range, String::new()));
+    }
+}
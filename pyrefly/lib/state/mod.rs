@@ -19,3 +19,4 @@ pub mod semantic_tokens;
 pub mod state;
 pub mod steps;
 pub mod subscriber;
+pub mod trace;
@@ -6,11 +6,14 @@
  */
 
 use dupe::Dupe;
+use enum_iterator::Sequence;
 
 use crate::state::epoch::Epoch;
 
 /// How much information do we require about a module?
-#[derive(Debug, Clone, Dupe, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Debug, Clone, Dupe, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Sequence
+)]
 pub enum Require {
     /// We require nothing about the module.
     /// It's only purpose is to provide information about dependencies, namely Exports.
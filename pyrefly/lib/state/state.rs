@@ -25,6 +25,7 @@ use std::sync::MutexGuard;
 use std::sync::RwLockReadGuard;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 use std::time::Instant;
 
 use dupe::Dupe;
@@ -37,6 +38,8 @@ use pyrefly_util::events::CategorizedEvents;
 use pyrefly_util::lock::Mutex;
 use pyrefly_util::lock::RwLock;
 use pyrefly_util::locked_map::LockedMap;
+use pyrefly_util::memory::Bytes;
+use pyrefly_util::memory::MemoryUsage;
 use pyrefly_util::no_hash::BuildNoHash;
 use pyrefly_util::recurser::Recurser;
 use pyrefly_util::small_set1::SmallSet1;
@@ -90,6 +93,8 @@ use crate::module::module_info::ModuleInfo;
 use crate::module::module_name::ModuleName;
 use crate::module::module_path::ModulePath;
 use crate::module::module_path::ModulePathDetails;
+use crate::plugin::Plugins;
+use crate::report::binding_memory::bindings_size;
 use crate::state::dirty::Dirty;
 use crate::state::epoch::Epoch;
 use crate::state::epoch::Epochs;
@@ -107,6 +112,8 @@ use crate::state::require::RequireOverride;
 use crate::state::steps::Context;
 use crate::state::steps::Step;
 use crate::state::steps::Steps;
+use crate::state::subscriber::Invalidation;
+use crate::state::subscriber::Retained;
 use crate::state::subscriber::Subscriber;
 use crate::sys_info::SysInfo;
 use crate::types::class::Class;
@@ -124,6 +131,8 @@ struct ModuleData {
     /// The dependencies of this module.
     /// Most modules exist in exactly one place, but it can be possible to load the same module multiple times with different paths.
     deps: HashMap<ModuleName, SmallSet1<Handle>, BuildNoHash>,
+    /// The modules this module tried to import, but that couldn't be found.
+    missing_deps: SmallSet<ModuleName>,
     rdeps: HashSet<Handle>,
 }
 
@@ -133,6 +142,7 @@ struct ModuleDataMut {
     config: RwLock<ArcId<ConfigFile>>,
     state: UpgradeLock<Step, ModuleDataInner>,
     deps: RwLock<HashMap<ModuleName, SmallSet1<Handle>, BuildNoHash>>,
+    missing_deps: RwLock<SmallSet<ModuleName>>,
     /// The reverse dependencies of this module. This is used to invalidate on change.
     /// Note that if we are only running once, e.g. on the command line, this isn't valuable.
     /// But we create it anyway for simplicity, since it doesn't seem to add much overhead.
@@ -167,6 +177,7 @@ impl ModuleData {
             config: RwLock::new(self.config.dupe()),
             state: UpgradeLock::new(self.state.clone()),
             deps: RwLock::new(self.deps.clone()),
+            missing_deps: RwLock::new(self.missing_deps.clone()),
             rdeps: Mutex::new(self.rdeps.clone()),
         }
     }
@@ -179,6 +190,7 @@ impl ModuleDataMut {
             config: RwLock::new(config),
             state: UpgradeLock::new(ModuleDataInner::new(now)),
             deps: Default::default(),
+            missing_deps: Default::default(),
             rdeps: Default::default(),
         }
     }
@@ -191,9 +203,11 @@ impl ModuleDataMut {
             config,
             state,
             deps,
+            missing_deps,
             rdeps,
         } = self;
         let deps = mem::take(&mut *deps.write());
+        let missing_deps = mem::take(&mut *missing_deps.write());
         let rdeps = mem::take(&mut *rdeps.lock());
         let state = state.read().clone();
         ModuleData {
@@ -201,6 +215,7 @@ impl ModuleDataMut {
             config: config.read().dupe(),
             state,
             deps,
+            missing_deps,
             rdeps,
         }
     }
@@ -254,6 +269,50 @@ pub struct TransactionData<'a> {
     dirty: Mutex<SmallSet<ArcId<ModuleDataMut>>>,
     /// Thing to tell about each action.
     subscriber: Option<Box<dyn Subscriber>>,
+    /// Whether the subscriber wants to know when a computation is blocked, which costs a lock
+    /// on every cross-module lookup.
+    track_blocked: bool,
+    /// If we use more memory than this, evict what we can from modules that are solved.
+    memory_budget: Option<Bytes>,
+    /// Held by the thread that is evicting, so the others don't all try at once.
+    /// Records when we last measured memory, and whether we were over budget then.
+    evicting: Mutex<MemoryCheck>,
+    /// Modules that have finished solving but have not been evicted yet.
+    eviction_candidates: Mutex<Vec<ArcId<ModuleDataMut>>>,
+    /// What we evicted from each module because of the `memory_budget`.
+    evicted: Mutex<SmallMap<Handle, Evicted>>,
+}
+
+/// What was dropped from a module to stay within a memory budget.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Evicted {
+    pub ast: bool,
+    /// The number of bindings dropped, counting keys and values separately.
+    pub bindings: usize,
+    /// The shallow size of the bindings dropped, ignoring anything they point at.
+    pub binding_shallow_bytes: usize,
+}
+
+/// The last time we asked the OS how much memory we are using.
+#[derive(Debug, Default)]
+struct MemoryCheck {
+    last: Option<Instant>,
+    over_budget: bool,
+}
+
+impl MemoryCheck {
+    /// Measuring memory is expensive, so only do it this often.
+    const INTERVAL: Duration = Duration::from_millis(100);
+
+    fn over_budget(&mut self, budget: Bytes) -> bool {
+        if self.last.is_none_or(|x| x.elapsed() >= Self::INTERVAL) {
+            self.last = Some(Instant::now());
+            self.over_budget = MemoryUsage::now()
+                .physical
+                .is_some_and(|used| used > budget);
+        }
+        self.over_budget
+    }
 }
 
 impl<'a> TransactionData<'a> {
@@ -285,9 +344,27 @@ impl<'a> Transaction<'a> {
     }
 
     pub fn set_subscriber(&mut self, subscriber: Option<Box<dyn Subscriber>>) {
+        self.data.track_blocked = subscriber.as_ref().is_some_and(|x| x.tracks_blocked());
         self.data.subscriber = subscriber;
     }
 
+    /// Once we are using more than `budget` of memory, drop the AST, bindings and answers of
+    /// solved modules whose dependents are all solved, keeping only their exports and solutions,
+    /// even if their `Require` says to keep them.
+    pub fn set_memory_budget(&mut self, budget: Option<Bytes>) {
+        self.data.memory_budget = budget;
+    }
+
+    /// What we evicted from each module to stay within the memory budget.
+    pub fn evicted(&self) -> Vec<(Handle, Evicted)> {
+        self.data
+            .evicted
+            .lock()
+            .iter()
+            .map(|(handle, x)| (handle.dupe(), *x))
+            .collect()
+    }
+
     pub fn get_solutions(&self, handle: &Handle) -> Option<Arc<Solutions>> {
         self.with_module_inner(handle, |x| x.steps.solutions.dupe())
     }
@@ -359,16 +436,22 @@ impl<'a> Transaction<'a> {
     }
 
     pub fn search_exports_exact(&self, name: &str) -> Vec<Handle> {
-        self.search_exports_helper(|handle, exports| {
-            if let Some(export) = exports.get(&Name::new(name)) {
+        self.search_exports_helper(|handle, exports, lookup| {
+            let name = Name::new(name);
+            if let Some(export) = exports.exports(lookup).get(&name) {
                 match export {
                     ExportLocation::ThisModule(_) => vec![handle.dupe()],
-                    // Re-exported modules like `foo` in `from from_module import foo`
-                    // should likely be ignored in autoimport suggestions
-                    // because the original export in from_module will show it.
-                    // The current strategy will prevent intended re-exports from showing up in
-                    // result list, but it's better than showing thousands of likely bad results.
-                    ExportLocation::OtherModule(_) => Vec::new(),
+                    // Re-exported names like `foo` in `from from_module import foo`
+                    // are ignored in autoimport suggestions, because the original export in
+                    // from_module will show it. Only intended re-exports, like
+                    // `from from_module import foo as foo`, are suggested.
+                    ExportLocation::OtherModule(_) => {
+                        if exports.explicit_reexport(&name, lookup).is_some() {
+                            vec![handle.dupe()]
+                        } else {
+                            Vec::new()
+                        }
+                    }
                 }
             } else {
                 Vec::new()
@@ -377,17 +460,17 @@ impl<'a> Transaction<'a> {
     }
 
     pub fn search_exports_fuzzy(&self, pattern: &str) -> Vec<(Handle, String, Export)> {
-        self.search_exports_helper(|handle, exports| {
+        self.search_exports_helper(|handle, exports, lookup| {
             let matcher = SkimMatcherV2::default().smart_case();
             let mut results = Vec::new();
-            for (name, location) in exports.iter() {
-                let name = name.as_str();
-                if let Some(score) = matcher.fuzzy_match(name, pattern) {
-                    match location {
-                        ExportLocation::OtherModule(_) => {}
-                        ExportLocation::ThisModule(export) => {
-                            results.push((score, handle.dupe(), name.to_owned(), export.clone()));
-                        }
+            for (name, location) in exports.exports(lookup).iter() {
+                if let Some(score) = matcher.fuzzy_match(name.as_str(), pattern) {
+                    let export = match location {
+                        ExportLocation::OtherModule(_) => exports.explicit_reexport(name, lookup),
+                        ExportLocation::ThisModule(export) => Some(export.clone()),
+                    };
+                    if let Some(export) = export {
+                        results.push((score, handle.dupe(), name.to_string(), export));
                     }
                 }
             }
@@ -402,7 +485,7 @@ impl<'a> Transaction<'a> {
 
     fn search_exports_helper<V: Send + Sync>(
         &self,
-        searcher: impl Fn(&Handle, Arc<SmallMap<Name, ExportLocation>>) -> Vec<V> + Sync,
+        searcher: impl Fn(&Handle, &Exports, &dyn LookupExport) -> Vec<V> + Sync,
     ) -> Vec<V> {
         let all_results = Mutex::new(Vec::new());
         {
@@ -418,10 +501,9 @@ impl<'a> Transaction<'a> {
                 tasks.work_without_cancellation(|_, modules| {
                     let mut thread_local_results = Vec::new();
                     for (handle, module_data) in modules {
-                        let exports = self
-                            .lookup_export(module_data)
-                            .exports(&self.lookup(module_data.dupe()));
-                        thread_local_results.extend(searcher(handle, exports));
+                        let exports = self.lookup_export(module_data);
+                        let lookup = self.lookup(module_data.dupe());
+                        thread_local_results.extend(searcher(handle, &exports, &lookup));
                     }
                     if !thread_local_results.is_empty() {
                         all_results.lock().push(thread_local_results);
@@ -445,10 +527,9 @@ impl<'a> Transaction<'a> {
                     let mut thread_local_results = Vec::new();
                     for (handle, module_data) in modules {
                         let module_data = ArcId::new(module_data.clone_for_mutation());
-                        let exports = self
-                            .lookup_export(&module_data)
-                            .exports(&self.lookup(module_data));
-                        thread_local_results.extend(searcher(handle, exports));
+                        let exports = self.lookup_export(&module_data);
+                        let lookup = self.lookup(module_data);
+                        thread_local_results.extend(searcher(handle, &exports, &lookup));
                     }
                     if !thread_local_results.is_empty() {
                         all_results.lock().push(thread_local_results);
@@ -488,6 +569,29 @@ impl<'a> Transaction<'a> {
         transitive_rdeps
     }
 
+    /// The modules that `handle` imported while it was checked, as resolved by the loader,
+    /// in a non-deterministic order. Only complete if `handle` was checked at `Require::Errors` or above.
+    pub fn get_imports(&self, handle: &Handle) -> Vec<Handle> {
+        self.get_module(handle)
+            .deps
+            .read()
+            .values()
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    /// The modules that `handle` tried to import while it was checked, but that couldn't be found.
+    /// Includes the submodules that `from m import x` looks for, when `x` isn't one.
+    pub fn get_missing_imports(&self, handle: &Handle) -> Vec<ModuleName> {
+        self.get_module(handle)
+            .missing_deps
+            .read()
+            .iter()
+            .copied()
+            .collect()
+    }
+
     /// Return all handles for which there is data, in a non-deterministic order.
     pub fn handles(&self) -> Vec<Handle> {
         if self.data.updated_modules.is_empty() {
@@ -587,7 +691,9 @@ impl<'a> Transaction<'a> {
             w.dirty.clean();
         };
         // Rebuild stuff. Pass clear_ast to indicate we need to rebuild the AST, otherwise can reuse it (if present).
-        let rebuild = |mut w: UpgradeLockWriteGuard<Step, ModuleDataInner>, clear_ast: bool| {
+        let rebuild = |mut w: UpgradeLockWriteGuard<Step, ModuleDataInner>,
+                       clear_ast: bool,
+                       reason: Invalidation| {
             w.steps.last_step = if clear_ast || w.steps.ast.is_none() {
                 if w.steps.load.is_none() {
                     None
@@ -605,9 +711,11 @@ impl<'a> Transaction<'a> {
             // Do not clear solutions, since we can use that for equality
             w.epochs.computed = self.data.now;
             if let Some(subscriber) = &self.data.subscriber {
+                subscriber.invalidated(&module_data.handle, reason);
                 subscriber.start_work(module_data.handle.dupe());
             }
             let deps = mem::take(&mut *module_data.deps.write());
+            module_data.missing_deps.write().clear();
             finish(&mut w);
             if !deps.is_empty() {
                 // Downgrade to exclusive, so other people can read from us, or we lock up.
@@ -630,7 +738,7 @@ impl<'a> Transaction<'a> {
             // Could be optimised to do less work (e.g. if you had Retain::Error before don't need to reload)
             let mut write = exclusive.write();
             write.steps.load = None;
-            rebuild(write, true);
+            rebuild(write, true, Invalidation::Require);
             return;
         }
 
@@ -649,7 +757,7 @@ impl<'a> Transaction<'a> {
                     code,
                     self_error,
                 )));
-                rebuild(write, true);
+                rebuild(write, true, Invalidation::Load);
                 return;
             }
         }
@@ -663,7 +771,7 @@ impl<'a> Transaction<'a> {
                 errors: ErrorCollector::new(old_load.module_info.dupe(), old_load.errors.style()),
                 module_info: old_load.module_info.clone(),
             }));
-            rebuild(write, false);
+            rebuild(write, false, Invalidation::Deps);
             return;
         }
 
@@ -685,7 +793,7 @@ impl<'a> Transaction<'a> {
             }
             if is_dirty {
                 let write = exclusive.write();
-                rebuild(write, false);
+                rebuild(write, false, Invalidation::Find);
                 return;
             }
         }
@@ -693,6 +801,10 @@ impl<'a> Transaction<'a> {
         // The module was not dirty. Make sure our dependencies aren't dirty either.
         let mut write = exclusive.write();
         finish(&mut write);
+        drop(write);
+        if let Some(subscriber) = &self.data.subscriber {
+            subscriber.cache_hit(&module_data.handle);
+        }
     }
 
     fn demand(&self, module_data: &ArcId<ModuleDataMut>, step: Step) {
@@ -738,6 +850,9 @@ impl<'a> Transaction<'a> {
             }
 
             let stdlib = self.get_stdlib(&module_data.handle);
+            let start = Instant::now();
+            let config = module_data.config.read().dupe();
+            let path = module_data.handle.path().as_path();
             let set = compute(&Context {
                 require,
                 module: module_data.handle.module(),
@@ -745,18 +860,19 @@ impl<'a> Transaction<'a> {
                 sys_info: module_data.handle.sys_info(),
                 memory: &self.memory_lookup(),
                 uniques: &self.data.state.uniques,
+                plugins: &self.data.state.plugins,
                 stdlib: &stdlib,
                 lookup: &self.lookup(module_data.dupe()),
-                untyped_def_behavior: module_data
-                    .config
-                    .read()
-                    .untyped_def_behavior(module_data.handle.path().as_path()),
+                untyped_def_behavior: config.untyped_def_behavior(path),
+                solve_budget: config.solve_budget(path),
+                error_config: config.get_error_config(path),
             });
             {
                 let mut changed = false;
                 let mut to_drop = None;
                 let mut writer = exclusive.write();
                 let mut load_result = None;
+                let mut retained = None;
                 let old_solutions = if todo == Step::Solutions {
                     writer.steps.solutions.take()
                 } else {
@@ -780,8 +896,14 @@ impl<'a> Transaction<'a> {
                         to_drop = writer.steps.answers.take();
                     }
                     load_result = writer.steps.load.dupe();
+                    retained = Some(Retained {
+                        lines: writer.steps.line_count(),
+                        ast: writer.steps.ast.is_some(),
+                        answers: writer.steps.answers.is_some(),
+                    });
                 }
                 drop(writer);
+                let duration = start.elapsed();
                 // Release the lock before dropping
                 drop(to_drop);
                 if changed {
@@ -818,12 +940,19 @@ impl<'a> Transaction<'a> {
                     }
                     self.data.dirty.lock().extend(dirtied);
                 }
-                if let Some(load) = load_result
-                    && let Some(subscriber) = &self.data.subscriber
-                {
-                    subscriber.finish_work(module_data.handle.dupe(), load);
+                if let Some(subscriber) = &self.data.subscriber {
+                    subscriber.finish_step(&module_data.handle, todo, start, duration);
+                    if let Some(retained) = retained {
+                        subscriber.retained(&module_data.handle, require, retained);
+                    }
+                    if let Some(load) = load_result {
+                        subscriber.finish_work(module_data.handle.dupe(), load);
+                    }
                 }
             }
+            if todo == Step::Solutions {
+                self.evict_if_over_budget(module_data);
+            }
             if todo == step {
                 break; // Fast path - avoid asking again since we just did it.
             }
@@ -835,6 +964,73 @@ impl<'a> Transaction<'a> {
         }
     }
 
+    /// Has this module computed all its steps in this epoch.
+    fn is_solved(&self, module_data: &ModuleDataMut) -> bool {
+        let reader = module_data.state.read();
+        reader.epochs.checked == self.data.now && reader.steps.next_step().is_none()
+    }
+
+    /// Called when `module_data` is solved. If we are over the memory budget, drop the
+    /// AST and bindings of solved modules whose dependents are also solved.
+    fn evict_if_over_budget(&self, module_data: &ArcId<ModuleDataMut>) {
+        let Some(budget) = self.data.memory_budget else {
+            return;
+        };
+        self.data
+            .eviction_candidates
+            .lock()
+            .push(module_data.dupe());
+        let Some(mut evicting) = self.data.evicting.try_lock() else {
+            return;
+        };
+        if !evicting.over_budget(budget) {
+            return;
+        }
+        let candidates = mem::take(&mut *self.data.eviction_candidates.lock());
+        let mut retry = Vec::new();
+        for module_data in candidates {
+            if !self.is_solved(&module_data) {
+                // Invalidated since it was queued, it will be queued again once re-solved.
+                continue;
+            }
+            // Once solved, dependents only use our solutions, but be conservative and wait until
+            // they are solved too.
+            let rdeps = module_data.rdeps.lock().iter().cloned().collect::<Vec<_>>();
+            if !rdeps.iter().all(|rdep| {
+                self.data
+                    .updated_modules
+                    .get(rdep)
+                    .is_some_and(|x| self.is_solved(x))
+            }) {
+                retry.push(module_data);
+                continue;
+            }
+            let Some(exclusive) = module_data.state.exclusive(Step::last()) else {
+                retry.push(module_data);
+                continue;
+            };
+            let mut writer = exclusive.write();
+            let ast = writer.steps.ast.take();
+            let answers = writer.steps.answers.take();
+            drop(writer);
+            if ast.is_none() && answers.is_none() {
+                continue;
+            }
+            let mut evicted = Evicted {
+                ast: ast.is_some(),
+                ..Evicted::default()
+            };
+            if let Some(answers) = &answers {
+                (evicted.bindings, evicted.binding_shallow_bytes) = bindings_size(&answers.0);
+            }
+            self.data
+                .evicted
+                .lock()
+                .insert(module_data.handle.dupe(), evicted);
+        }
+        self.data.eviction_candidates.lock().extend(retry);
+    }
+
     /// Like `get_module` but if the data isn't yet in this transaction will not copy it over.
     /// Saves copying if it is just a query.
     fn with_module_inner<R>(
@@ -951,6 +1147,25 @@ impl<'a> Transaction<'a> {
         }
     }
 
+    /// Make sure `step` of `module_data` is available to the computation of `requester`,
+    /// telling the subscriber how long `requester` was blocked if it was not.
+    fn wait_for(&self, requester: &Handle, module_data: &ArcId<ModuleDataMut>, step: Step) {
+        if !self.data.track_blocked {
+            return;
+        }
+        let Some(subscriber) = &self.data.subscriber else {
+            return;
+        };
+        let reader = module_data.state.read();
+        if reader.epochs.checked == self.data.now && reader.steps.last_step >= Some(step) {
+            return;
+        }
+        drop(reader);
+        let start = Instant::now();
+        self.demand(module_data, step);
+        subscriber.blocked(requester, &module_data.handle, step, start, start.elapsed());
+    }
+
     fn lookup_export(&self, module_data: &ArcId<ModuleDataMut>) -> Exports {
         self.demand(module_data, Step::Exports);
         let lock = module_data.state.read();
@@ -989,6 +1204,7 @@ impl<'a> Transaction<'a> {
                     &load.errors,
                     &stdlib,
                     &self.data.state.uniques,
+                    &self.data.state.plugins,
                     key,
                     stack,
                 );
@@ -1037,10 +1253,10 @@ impl<'a> Transaction<'a> {
         }
     }
 
-    fn work(&self) -> Result<(), Cancelled> {
+    fn work(&self, step: Step) -> Result<(), Cancelled> {
         // ensure we have answers for everything, keep going until we don't discover any new modules
         self.data.todo.work(|_, x| {
-            self.demand(&x, Step::last());
+            self.demand(&x, step);
         })
     }
 
@@ -1048,6 +1264,7 @@ impl<'a> Transaction<'a> {
         &mut self,
         handles: &[(Handle, Require)],
         old_require: Option<RequireDefault>,
+        step: Step,
     ) -> Result<(), Cancelled> {
         self.data.now.next();
         let sys_infos = handles
@@ -1080,7 +1297,7 @@ impl<'a> Transaction<'a> {
 
         let cancelled = AtomicBool::new(false);
         self.data.state.threads.spawn_many(|| {
-            cancelled.fetch_or(self.work().is_err(), Ordering::Relaxed);
+            cancelled.fetch_or(self.work(step).is_err(), Ordering::Relaxed);
         });
         if cancelled.into_inner() {
             Err(Cancelled)
@@ -1148,6 +1365,7 @@ impl<'a> Transaction<'a> {
         &mut self,
         handles: &[(Handle, Require)],
         old_require: RequireDefault,
+        step: Step,
     ) -> Result<(), Cancelled> {
         // We first compute all the modules that are either new or have changed.
         // Then we repeatedly compute all the modules who depend on modules that changed.
@@ -1160,7 +1378,7 @@ impl<'a> Transaction<'a> {
             debug!("Running epoch {i}");
             // The first version we use the old require. We use this to trigger require changes,
             // but only once, as after we've done it once, the "old" value will no longer be accessible.
-            self.run_step(handles, if i == 1 { Some(old_require) } else { None })?;
+            self.run_step(handles, if i == 1 { Some(old_require) } else { None }, step)?;
             let changed = mem::take(&mut *self.data.changed.lock());
             if changed.is_empty() {
                 return Ok(());
@@ -1171,7 +1389,7 @@ impl<'a> Transaction<'a> {
                     // We are in a cycle of mutual dependencies, so give up.
                     // Just invalidate everything in the cycle and recompute it all.
                     self.invalidate_rdeps(&changed);
-                    return self.run_step(handles, None);
+                    return self.run_step(handles, None, step);
                 }
             }
         }
@@ -1179,7 +1397,13 @@ impl<'a> Transaction<'a> {
     }
 
     pub fn run(&mut self, handles: &[(Handle, Require)]) {
-        let _ = self.run_internal(handles, self.readable.require);
+        let _ = self.run_internal(handles, self.readable.require, Step::last());
+    }
+
+    /// Like `run`, but only compute `handles` up to `step`. For example, computing them up to
+    /// `Step::Answers` binds them, which finds their imports, without solving anything.
+    pub fn run_until(&mut self, handles: &[(Handle, Require)], step: Step) {
+        let _ = self.run_internal(handles, self.readable.require, step);
     }
 
     pub fn ad_hoc_solve<R: Sized, F: FnOnce(AnswersSolver<TransactionHandle>) -> R>(
@@ -1202,6 +1426,7 @@ impl<'a> Transaction<'a> {
             bindings,
             &lookup,
             &self.data.state.uniques,
+            &self.data.state.plugins,
             &recurser,
             &stdlib,
             &stack,
@@ -1366,6 +1591,8 @@ impl<'a> Transaction<'a> {
             let mut alt = Steps::default();
             let lock = m.state.read();
             let stdlib = self.get_stdlib(&m.handle);
+            let config = m.config.read().dupe();
+            let module_path = m.handle.path().as_path();
             let ctx = Context {
                 require: lock.require.get(self.data.require),
                 module: m.handle.module(),
@@ -1373,12 +1600,12 @@ impl<'a> Transaction<'a> {
                 sys_info: m.handle.sys_info(),
                 memory: &self.memory_lookup(),
                 uniques: &self.data.state.uniques,
+                plugins: &self.data.state.plugins,
                 stdlib: &stdlib,
                 lookup: &self.lookup(m.dupe()),
-                untyped_def_behavior: m
-                    .config
-                    .read()
-                    .untyped_def_behavior(m.handle.path().as_path()),
+                untyped_def_behavior: config.untyped_def_behavior(module_path),
+                solve_budget: config.solve_budget(module_path),
+                error_config: config.get_error_config(module_path),
             };
             let mut step = Step::Load; // Start at AST (Load.next)
             alt.load = lock.steps.load.dupe();
@@ -1425,6 +1652,32 @@ impl<'a> Transaction<'a> {
             .exports(&self.lookup(module_data))
     }
 
+    /// The names that `importer` can import from `handle` without an `implicit-reexport` error:
+    /// its exports, minus the names it only imports itself if it must re-export them explicitly.
+    pub fn get_importable_exports(&self, importer: &Handle, handle: &Handle) -> Vec<Name> {
+        let module_data = self.get_module(handle);
+        let exports = self.lookup_export(&module_data);
+        let lookup = self.lookup(module_data);
+        let setting = self
+            .get_module_info(importer)
+            .and_then(|x| x.inline_config().config().explicit_reexport);
+        let explicit = self
+            .lookup(self.get_module(importer))
+            .requires_explicit_reexport(handle.module(), setting);
+        exports
+            .exports(&lookup)
+            .keys()
+            .filter(|name| {
+                !explicit
+                    || !exports.is_implicit_reexport(name, &lookup)
+                    || self
+                        .import_handle(importer, handle.module().append(name), None)
+                        .is_ok()
+            })
+            .cloned()
+            .collect()
+    }
+
     pub fn get_module_docstring(&self, handle: &Handle) -> Option<DocString> {
         let module_data = self.get_module(handle);
         self.lookup_export(&module_data).docstring().cloned()
@@ -1448,9 +1701,16 @@ impl<'a> TransactionHandle<'a> {
             return Ok(self.transaction.get_module(res));
         }
 
-        let handle = self
+        let handle = match self
             .transaction
-            .import_handle(&self.module_data.handle, module, path)?;
+            .import_handle(&self.module_data.handle, module, path)
+        {
+            Ok(handle) => handle,
+            Err(e) => {
+                self.module_data.missing_deps.write().insert(module);
+                return Err(e);
+            }
+        };
         let res = self.transaction.get_module(&handle);
         let mut write = self.module_data.deps.write();
         let did_insert = match write.entry(module) {
@@ -1471,6 +1731,8 @@ impl<'a> TransactionHandle<'a> {
 impl<'a> LookupExport for TransactionHandle<'a> {
     fn get(&self, module: ModuleName) -> Result<Exports, FindError> {
         let module_data = self.get_module(module, None)?;
+        self.transaction
+            .wait_for(&self.module_data.handle, &module_data, Step::Exports);
         let exports = self.transaction.lookup_export(&module_data);
 
         // TODO: Design this better.
@@ -1492,6 +1754,24 @@ impl<'a> LookupExport for TransactionHandle<'a> {
         exports.exports(&transaction2);
         Ok(exports)
     }
+
+    fn is_untyped(&self, module: ModuleName) -> bool {
+        self.get_module(module, None).is_ok_and(|x| {
+            self.module_data
+                .config
+                .read()
+                .is_untyped_import(module, x.handle.path())
+        })
+    }
+
+    fn requires_explicit_reexport(&self, module: ModuleName, setting: Option<bool>) -> bool {
+        self.get_module(module, None).is_ok_and(|x| {
+            let config = self.module_data.config.read();
+            let setting = setting
+                .or_else(|| config.explicit_reexport(self.module_data.handle.path().as_path()));
+            config.requires_explicit_reexport(module, x.handle.path(), setting)
+        })
+    }
 }
 
 impl<'a> LookupAnswer for TransactionHandle<'a> {
@@ -1510,6 +1790,8 @@ impl<'a> LookupAnswer for TransactionHandle<'a> {
         // The unwrap is safe because we must have said there were no exports,
         // so no one can be trying to get at them
         let module_data = self.get_module(module, path).unwrap();
+        self.transaction
+            .wait_for(&self.module_data.handle, &module_data, Step::Answers);
         self.transaction.lookup_answer(module_data, k, stack)
     }
 }
@@ -1533,7 +1815,8 @@ pub struct CancellableTransaction<'a>(Transaction<'a>);
 
 impl CancellableTransaction<'_> {
     pub fn run(&mut self, handles: &[(Handle, Require)]) -> Result<(), Cancelled> {
-        self.0.run_internal(handles, self.0.readable.require)
+        self.0
+            .run_internal(handles, self.0.readable.require, Step::last())
     }
 
     pub fn get_cancellation_handle(&self) -> CancellationHandle {
@@ -1567,6 +1850,7 @@ pub struct State {
     threads: ThreadPool,
     uniques: UniqueFactory,
     config_finder: ConfigFinder,
+    plugins: Plugins,
     state: RwLock<StateInner>,
     committing_transaction_lock: Mutex<()>,
 }
@@ -1577,11 +1861,18 @@ impl State {
             threads: ThreadPool::new(),
             uniques: UniqueFactory::new(),
             config_finder,
+            plugins: Plugins::default(),
             state: RwLock::new(StateInner::new()),
             committing_transaction_lock: Mutex::new(()),
         }
     }
 
+    /// Use `plugins` for framework-specific behavior when solving.
+    pub fn with_plugins(mut self, plugins: Plugins) -> Self {
+        self.plugins = plugins;
+        self
+    }
+
     pub fn config_finder(&self) -> &ConfigFinder {
         &self.config_finder
     }
@@ -1602,6 +1893,7 @@ impl State {
         let readable = self.state.read();
         let now = readable.now;
         let stdlib = readable.stdlib.clone();
+        let track_blocked = subscriber.as_ref().is_some_and(|x| x.tracks_blocked());
         Transaction {
             readable,
             data: TransactionData {
@@ -1616,6 +1908,11 @@ impl State {
                 changed: Default::default(),
                 dirty: Default::default(),
                 subscriber,
+                track_blocked,
+                memory_budget: None,
+                evicting: Default::default(),
+                eviction_candidates: Default::default(),
+                evicted: Default::default(),
             },
         }
     }
@@ -1675,6 +1972,11 @@ impl State {
                             changed: _,
                             dirty: _,
                             subscriber: _,
+                            track_blocked: _,
+                            memory_budget: _,
+                            evicting: _,
+                            eviction_candidates: _,
+                            evicted: _,
                         },
                 },
             committing_transaction_guard,
@@ -6,6 +6,7 @@
  */
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use dupe::Dupe;
 use enum_iterator::Sequence;
@@ -19,11 +20,14 @@ use crate::alt::answers::LookupAnswer;
 use crate::alt::answers::Solutions;
 use crate::binding::bindings::Bindings;
 use crate::config::base::UntypedDefBehavior;
+use crate::config::error::ErrorConfig;
+use crate::config::error::OptInErrors;
 use crate::error::style::ErrorStyle;
 use crate::export::exports::Exports;
 use crate::export::exports::LookupExport;
 use crate::module::module_name::ModuleName;
 use crate::module::module_path::ModulePath;
+use crate::plugin::Plugins;
 use crate::solver::solver::Solver;
 use crate::state::load::Load;
 use crate::state::memory::MemoryFilesLookup;
@@ -38,9 +42,12 @@ pub struct Context<'a, Lookup> {
     pub sys_info: &'a SysInfo,
     pub memory: &'a MemoryFilesLookup<'a>,
     pub uniques: &'a UniqueFactory,
+    pub plugins: &'a Plugins,
     pub stdlib: &'a Stdlib,
     pub lookup: &'a Lookup,
     pub untyped_def_behavior: UntypedDefBehavior,
+    pub solve_budget: Option<Duration>,
+    pub error_config: ErrorConfig<'a>,
 }
 
 #[derive(Debug, Default, Dupe, Clone)]
@@ -71,7 +78,7 @@ impl Steps {
     }
 }
 
-#[derive(Debug, Clone, Copy, Dupe, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, Dupe, Eq, PartialEq, PartialOrd, Ord, Hash)]
 #[derive(Display, Sequence)]
 pub enum Step {
     Load,
@@ -166,7 +173,9 @@ impl Step {
     ) -> Arc<(Bindings, Arc<Answers>)> {
         let solver = Solver::new();
         let enable_index = ctx.require.keep_index();
+        let inline_config = load.module_info.inline_config().config();
         let enable_trace = ctx.require.keep_answers_trace();
+        let opt_in_errors = OptInErrors::new(&ctx.error_config, inline_config);
         let bindings = Bindings::new(
             Arc::unwrap_or_clone(ast),
             load.module_info.dupe(),
@@ -177,9 +186,22 @@ impl Step {
             &load.errors,
             ctx.uniques,
             enable_trace,
-            ctx.untyped_def_behavior,
+            inline_config
+                .untyped_def_behavior
+                .unwrap_or(ctx.untyped_def_behavior),
+            opt_in_errors.clone(),
+        );
+        let answers = Answers::new(
+            &bindings,
+            solver,
+            enable_index,
+            enable_trace,
+            inline_config
+                .solve_budget_ms
+                .map(Duration::from_millis)
+                .or(ctx.solve_budget),
+            opt_in_errors,
         );
-        let answers = Answers::new(&bindings, solver, enable_index, enable_trace);
         Arc::new((bindings, Arc::new(answers)))
     }
 
@@ -196,6 +218,7 @@ impl Step {
             &load.errors,
             ctx.stdlib,
             ctx.uniques,
+            ctx.plugins,
             ctx.require.compute_errors()
                 || ctx.require.keep_answers_trace()
                 || ctx.require.keep_answers(),
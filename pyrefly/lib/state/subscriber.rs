@@ -5,18 +5,56 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::fmt;
+use std::fmt::Display;
 use std::mem;
 use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
 
 use dupe::Dupe;
+use enum_iterator::Sequence;
 use indicatif::ProgressBar;
 use indicatif::ProgressStyle;
+use pyrefly_util::display::number_thousands;
 use pyrefly_util::lock::Mutex;
 use starlark_map::small_map::Entry;
 use starlark_map::small_map::SmallMap;
 
+use crate::module::module_name::ModuleName;
 use crate::state::handle::Handle;
 use crate::state::load::Load;
+use crate::state::require::Require;
+use crate::state::steps::Step;
+
+/// Why a module that was computed before has to be recomputed.
+#[derive(Debug, Clone, Copy, Dupe, PartialEq, Eq, Hash, Sequence)]
+#[derive(parse_display::Display)]
+pub enum Invalidation {
+    /// The module is now required at a higher `Require` level.
+    #[display("require")]
+    Require,
+    /// The contents of the module changed.
+    #[display("contents")]
+    Load,
+    /// The exports of one of its dependencies changed.
+    #[display("dependencies")]
+    Deps,
+    /// One of its imports now resolves to a different file.
+    #[display("imports")]
+    Find,
+}
+
+/// What we keep in memory for a module once we have computed its solutions.
+#[derive(Debug, Clone, Copy, Dupe, Default)]
+pub struct Retained {
+    pub lines: usize,
+    pub ast: bool,
+    pub answers: bool,
+}
 
 /// Trait to capture which handles are executed by `State`.
 /// Calls to `start_work` and `finish_work` will be paired.
@@ -32,6 +70,38 @@ pub trait Subscriber: Sync {
     /// While we have computed the solutions, we return the `Load` as that contains
     /// the `ErrorCollector` and `ModuleInfo` which are useful context for the completion.
     fn finish_work(&self, handle: Handle, result: Arc<Load>);
+
+    /// We have finished computing `step` for a `Handle`, which started at `start` and took `duration`.
+    fn finish_step(&self, _handle: &Handle, _step: Step, _start: Instant, _duration: Duration) {}
+
+    /// Whether to call `blocked`. Finding out costs a lock on every cross-module lookup, so only
+    /// subscribers that use it should opt in.
+    fn tracks_blocked(&self) -> bool {
+        false
+    }
+
+    /// The computation of `handle` was blocked for `duration` from `start`, waiting for `step`
+    /// of `waiting_on` to be available. The wait includes any time spent computing `waiting_on`
+    /// on this thread. Only called if `tracks_blocked` returns true.
+    fn blocked(
+        &self,
+        _handle: &Handle,
+        _waiting_on: &Handle,
+        _step: Step,
+        _start: Instant,
+        _duration: Duration,
+    ) {
+    }
+
+    /// We checked a `Handle` computed by a previous run, and can reuse it unchanged.
+    fn cache_hit(&self, _handle: &Handle) {}
+
+    /// We have to recompute a `Handle` computed by a previous run.
+    fn invalidated(&self, _handle: &Handle, _reason: Invalidation) {}
+
+    /// We have computed the solutions for a `Handle` required at `require`, and will keep
+    /// `retained` in memory.
+    fn retained(&self, _handle: &Handle, _require: Require, _retained: Retained) {}
 }
 
 /// A subscriber that validates all start/finish are paired and returns the final load states.
@@ -177,3 +247,274 @@ impl ProgressBarSubscriber {
         me
     }
 }
+
+/// A snapshot of how far along a run is, passed to the callback of a `TelemetrySubscriber`.
+#[derive(Debug, Clone)]
+pub struct ProgressReport {
+    /// Number of `start_work` calls.
+    pub started: usize,
+    /// Number of `finish_work` calls.
+    pub finished: usize,
+    /// The step most recently finished when progress was last reported, and the module it was
+    /// finished for.
+    pub current: Option<(ModuleName, Step)>,
+    pub elapsed: Duration,
+}
+
+impl Display for ProgressReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{:.1}s] {}/{} modules",
+            self.elapsed.as_secs_f32(),
+            number_thousands(self.finished),
+            number_thousands(self.started)
+        )?;
+        if let Some((module, step)) = &self.current {
+            write!(f, ", {step} {module}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Totals of everything a `TelemetrySubscriber` has seen.
+#[derive(Debug, Clone, Default)]
+pub struct Telemetry {
+    pub started: usize,
+    pub finished: usize,
+    /// How many times each step was computed, and how long it took in total.
+    pub steps: SmallMap<Step, (usize, Duration)>,
+    pub cache_hits: usize,
+    pub invalidations: SmallMap<Invalidation, usize>,
+    /// For each `Require` level, the number of modules and what they retain.
+    pub retained: SmallMap<Require, RetainedTotals>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RetainedTotals {
+    pub modules: usize,
+    pub lines: usize,
+    /// Modules whose AST we keep.
+    pub ast: usize,
+    /// Modules whose bindings and answers we keep.
+    pub answers: usize,
+}
+
+impl Display for Telemetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Modules: {} started, {} finished, {} reused",
+            number_thousands(self.started),
+            number_thousands(self.finished),
+            number_thousands(self.cache_hits)
+        )?;
+        let mut steps = self.steps.iter().collect::<Vec<_>>();
+        steps.sort_by_key(|(step, _)| **step);
+        for (step, (count, duration)) in steps {
+            writeln!(
+                f,
+                "Step {step}: {} modules, {:.3}s",
+                number_thousands(*count),
+                duration.as_secs_f32()
+            )?;
+        }
+        for (reason, count) in &self.invalidations {
+            writeln!(
+                f,
+                "Invalidated by {reason}: {} modules",
+                number_thousands(*count)
+            )?;
+        }
+        let mut retained = self.retained.iter().collect::<Vec<_>>();
+        retained.sort_by_key(|(require, _)| **require);
+        for (require, totals) in retained {
+            writeln!(
+                f,
+                "Retained at {require:?}: {} modules, {} lines, {} with AST, {} with answers",
+                number_thousands(totals.modules),
+                number_thousands(totals.lines),
+                number_thousands(totals.ast),
+                number_thousands(totals.answers)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct StepCounters {
+    count: AtomicUsize,
+    nanos: AtomicU64,
+}
+
+#[derive(Default)]
+struct RetainedCounters {
+    modules: AtomicUsize,
+    lines: AtomicUsize,
+    ast: AtomicUsize,
+    answers: AtomicUsize,
+}
+
+/// Events arrive on every worker thread, so the totals are atomics rather than a shared lock.
+struct TelemetryInner {
+    started: AtomicUsize,
+    finished: AtomicUsize,
+    cache_hits: AtomicUsize,
+    /// Indexed by `Step`.
+    steps: Vec<StepCounters>,
+    /// Indexed by `Invalidation`.
+    invalidations: Vec<AtomicUsize>,
+    /// Indexed by `Require`.
+    retained: Vec<RetainedCounters>,
+    /// Only locked when reporting, which is rate limited.
+    current: Mutex<Option<(ModuleName, Step)>>,
+    start: Instant,
+    /// When we last called `report`, in milliseconds since `start`.
+    last_report: AtomicU64,
+    /// The minimum time between two calls to `report`, in milliseconds.
+    interval: u64,
+    report: Box<dyn Fn(ProgressReport) + Send + Sync>,
+}
+
+fn counters<T: Sequence, C: Default>() -> Vec<C> {
+    enum_iterator::all::<T>().map(|_| C::default()).collect()
+}
+
+/// A subscriber that collects `Telemetry` about a run, and regularly reports its progress to a
+/// callback, e.g. to print it or to send it to an LSP client.
+#[derive(Clone, Dupe)]
+pub struct TelemetrySubscriber(Arc<TelemetryInner>);
+
+impl TelemetrySubscriber {
+    pub fn new(interval: Duration, report: Box<dyn Fn(ProgressReport) + Send + Sync>) -> Self {
+        Self(Arc::new(TelemetryInner {
+            started: AtomicUsize::new(0),
+            finished: AtomicUsize::new(0),
+            cache_hits: AtomicUsize::new(0),
+            steps: counters::<Step, _>(),
+            invalidations: counters::<Invalidation, _>(),
+            retained: counters::<Require, _>(),
+            current: Mutex::new(None),
+            start: Instant::now(),
+            last_report: AtomicU64::new(0),
+            interval: u64::try_from(interval.as_millis()).unwrap_or(u64::MAX),
+            report,
+        }))
+    }
+
+    pub fn telemetry(&self) -> Telemetry {
+        let load = |x: &AtomicUsize| x.load(Ordering::Relaxed);
+        let inner = &self.0;
+        Telemetry {
+            started: load(&inner.started),
+            finished: load(&inner.finished),
+            steps: enum_iterator::all::<Step>()
+                .zip(&inner.steps)
+                .filter(|(_, x)| load(&x.count) > 0)
+                .map(|(step, x)| {
+                    let nanos = Duration::from_nanos(x.nanos.load(Ordering::Relaxed));
+                    (step, (load(&x.count), nanos))
+                })
+                .collect(),
+            cache_hits: load(&inner.cache_hits),
+            invalidations: enum_iterator::all::<Invalidation>()
+                .zip(&inner.invalidations)
+                .map(|(reason, x)| (reason, load(x)))
+                .filter(|(_, count)| *count > 0)
+                .collect(),
+            retained: enum_iterator::all::<Require>()
+                .zip(&inner.retained)
+                .filter(|(_, x)| load(&x.modules) > 0)
+                .map(|(require, x)| {
+                    let totals = RetainedTotals {
+                        modules: load(&x.modules),
+                        lines: load(&x.lines),
+                        ast: load(&x.ast),
+                        answers: load(&x.answers),
+                    };
+                    (require, totals)
+                })
+                .collect(),
+        }
+    }
+
+    pub fn progress(&self) -> ProgressReport {
+        self.report_of(self.0.current.lock().clone())
+    }
+
+    fn report_of(&self, current: Option<(ModuleName, Step)>) -> ProgressReport {
+        ProgressReport {
+            started: self.0.started.load(Ordering::Relaxed),
+            finished: self.0.finished.load(Ordering::Relaxed),
+            current,
+            elapsed: self.0.start.elapsed(),
+        }
+    }
+
+    /// Call `report` if it is at least `interval` since we last did, on whichever thread notices
+    /// first.
+    fn maybe_report(&self, current: Option<(ModuleName, Step)>) {
+        let now = u64::try_from(self.0.start.elapsed().as_millis()).unwrap_or(u64::MAX);
+        let last = self.0.last_report.load(Ordering::Relaxed);
+        if now.saturating_sub(last) < self.0.interval
+            || self
+                .0
+                .last_report
+                .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+                .is_err()
+        {
+            return;
+        }
+        let report = {
+            let mut last_current = self.0.current.lock();
+            if current.is_some() {
+                *last_current = current;
+            }
+            self.report_of(last_current.clone())
+        };
+        (self.0.report)(report);
+    }
+
+    fn increment(&self, counter: &AtomicUsize, by: usize) {
+        counter.fetch_add(by, Ordering::Relaxed);
+    }
+}
+
+impl Subscriber for TelemetrySubscriber {
+    fn start_work(&self, _: Handle) {
+        self.increment(&self.0.started, 1);
+        self.maybe_report(None);
+    }
+
+    fn finish_work(&self, _: Handle, _: Arc<Load>) {
+        self.increment(&self.0.finished, 1);
+        self.maybe_report(None);
+    }
+
+    fn finish_step(&self, handle: &Handle, step: Step, _: Instant, duration: Duration) {
+        let counters = &self.0.steps[step as usize];
+        self.increment(&counters.count, 1);
+        counters.nanos.fetch_add(
+            u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+        self.maybe_report(Some((handle.module(), step)));
+    }
+
+    fn cache_hit(&self, _: &Handle) {
+        self.increment(&self.0.cache_hits, 1);
+    }
+
+    fn invalidated(&self, _: &Handle, reason: Invalidation) {
+        self.increment(&self.0.invalidations[reason as usize], 1);
+    }
+
+    fn retained(&self, _: &Handle, require: Require, retained: Retained) {
+        let counters = &self.0.retained[require as usize];
+        self.increment(&counters.modules, 1);
+        self.increment(&counters.lines, retained.lines);
+        self.increment(&counters.ast, retained.ast as usize);
+        self.increment(&counters.answers, retained.answers as usize);
+    }
+}
@@ -0,0 +1,248 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Record what each thread is doing during a run, and write it out in the
+//! [Chrome trace event format](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU),
+//! which can be viewed in Perfetto or `chrome://tracing`.
+
+use std::cell::Cell;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use dupe::Dupe;
+use pyrefly_util::fs_anyhow;
+use pyrefly_util::lock::Mutex;
+use serde::Serialize;
+
+use crate::state::handle::Handle;
+use crate::state::load::Load;
+use crate::state::require::Require;
+use crate::state::steps::Step;
+use crate::state::subscriber::Invalidation;
+use crate::state::subscriber::Retained;
+use crate::state::subscriber::Subscriber;
+
+/// The trace format only has a single process.
+const PID: u64 = 1;
+
+/// A small identifier for the current thread, as the trace format wants integers.
+fn thread_id() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    thread_local! {
+        static ID: Cell<u64> = const { Cell::new(0) };
+    }
+    ID.with(|id| {
+        if id.get() == 0 {
+            id.set(NEXT.fetch_add(1, Ordering::Relaxed));
+        }
+        id.get()
+    })
+}
+
+#[derive(Debug, Serialize)]
+struct TraceArgs {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    module: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    waiting_on: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct TraceEvent {
+    name: String,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    cat: &'static str,
+    /// The phase, `X` for a complete span and `M` for metadata.
+    ph: &'static str,
+    /// Microseconds since the start of the trace.
+    ts: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dur: Option<u64>,
+    pid: u64,
+    tid: u64,
+    args: TraceArgs,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TraceFile<'a> {
+    trace_events: &'a [TraceEvent],
+    display_time_unit: &'static str,
+}
+
+struct ChromeTraceInner {
+    origin: Instant,
+    events: Mutex<Vec<TraceEvent>>,
+    /// The threads we have already emitted a name for.
+    threads: Mutex<Vec<u64>>,
+}
+
+/// Collects a span for every step computed, and every time a module was blocked waiting
+/// on another, in the format used by Chrome traces.
+#[derive(Clone, Dupe)]
+pub struct ChromeTrace(Arc<ChromeTraceInner>);
+
+impl ChromeTrace {
+    pub fn new() -> Self {
+        Self(Arc::new(ChromeTraceInner {
+            origin: Instant::now(),
+            events: Mutex::new(Vec::new()),
+            threads: Mutex::new(Vec::new()),
+        }))
+    }
+
+    /// A subscriber which records into this trace, and passes all events on to `inner`.
+    pub fn subscriber(&self, inner: Box<dyn Subscriber>) -> Box<dyn Subscriber> {
+        Box::new(ChromeTraceSubscriber {
+            trace: self.dupe(),
+            inner,
+        })
+    }
+
+    fn micros(&self, x: Instant) -> u64 {
+        x.saturating_duration_since(self.0.origin).as_micros() as u64
+    }
+
+    fn span(
+        &self,
+        name: String,
+        cat: &'static str,
+        start: Instant,
+        dur: Duration,
+        args: TraceArgs,
+    ) {
+        let tid = thread_id();
+        let event = TraceEvent {
+            name,
+            cat,
+            ph: "X",
+            ts: self.micros(start),
+            dur: Some(dur.as_micros() as u64),
+            pid: PID,
+            tid,
+            args,
+        };
+        let mut threads = self.0.threads.lock();
+        let thread_name = if threads.contains(&tid) {
+            None
+        } else {
+            threads.push(tid);
+            Some(TraceEvent {
+                name: "thread_name".to_owned(),
+                cat: "",
+                ph: "M",
+                ts: 0,
+                dur: None,
+                pid: PID,
+                tid,
+                args: TraceArgs {
+                    name: Some(
+                        thread::current()
+                            .name()
+                            .map_or_else(|| format!("thread {tid}"), |x| x.to_owned()),
+                    ),
+                    module: None,
+                    path: None,
+                    waiting_on: None,
+                },
+            })
+        };
+        drop(threads);
+        let mut events = self.0.events.lock();
+        events.extend(thread_name);
+        events.push(event);
+    }
+
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        let events = self.0.events.lock();
+        let json = serde_json::to_vec(&TraceFile {
+            trace_events: &events,
+            display_time_unit: "ms",
+        })?;
+        fs_anyhow::write(path, &json)
+    }
+}
+
+struct ChromeTraceSubscriber {
+    trace: ChromeTrace,
+    inner: Box<dyn Subscriber>,
+}
+
+impl Subscriber for ChromeTraceSubscriber {
+    fn start_work(&self, handle: Handle) {
+        self.inner.start_work(handle)
+    }
+
+    fn finish_work(&self, handle: Handle, result: Arc<Load>) {
+        self.inner.finish_work(handle, result)
+    }
+
+    fn finish_step(&self, handle: &Handle, step: Step, start: Instant, duration: Duration) {
+        self.trace.span(
+            format!("{step} {}", handle.module()),
+            "step",
+            start,
+            duration,
+            TraceArgs {
+                name: None,
+                module: Some(handle.module().to_string()),
+                path: Some(handle.path().to_string()),
+                waiting_on: None,
+            },
+        );
+        self.inner.finish_step(handle, step, start, duration)
+    }
+
+    fn tracks_blocked(&self) -> bool {
+        true
+    }
+
+    fn blocked(
+        &self,
+        handle: &Handle,
+        waiting_on: &Handle,
+        step: Step,
+        start: Instant,
+        duration: Duration,
+    ) {
+        self.trace.span(
+            format!("wait {step} {}", waiting_on.module()),
+            "blocked",
+            start,
+            duration,
+            TraceArgs {
+                name: None,
+                module: Some(handle.module().to_string()),
+                path: None,
+                waiting_on: Some(waiting_on.module().to_string()),
+            },
+        );
+        self.inner
+            .blocked(handle, waiting_on, step, start, duration)
+    }
+
+    fn cache_hit(&self, handle: &Handle) {
+        self.inner.cache_hit(handle)
+    }
+
+    fn invalidated(&self, handle: &Handle, reason: Invalidation) {
+        self.inner.invalidated(handle, reason)
+    }
+
+    fn retained(&self, handle: &Handle, require: Require, retained: Retained) {
+        self.inner.retained(handle, require, retained)
+    }
+}
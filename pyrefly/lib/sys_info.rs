@@ -15,6 +15,7 @@ use dupe::Dupe;
 use itertools::Itertools;
 use parse_display::Display;
 use pyrefly_util::prelude::SliceExt;
+use pyrefly_util::visit::Visit;
 use pyrefly_util::with_hash::WithHash;
 use regex::Match;
 use regex::Regex;
@@ -227,6 +228,12 @@ impl SysInfo {
     }
 }
 
+impl Display for SysInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.version(), self.platform())
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 enum Value {
     Tuple(Vec<Value>),
@@ -381,6 +388,23 @@ impl SysInfo {
             .map(|x| (x.1, x.2))
             .take_while_inclusive(|x| x.0.is_some())
     }
+
+    /// Whether `self` and `other` take the same branches of every `if` in `x`, including nested
+    /// ones, so that binding `x` under either of them gives the same result.
+    pub fn prunes_like(&self, other: &SysInfo, x: &[Stmt]) -> bool {
+        fn f(a: &SysInfo, b: &SysInfo, x: &Stmt) -> bool {
+            if let Stmt::If(x) = x
+                && Ast::if_branches(x)
+                    .any(|(test, _)| a.evaluate_bool_opt(test) != b.evaluate_bool_opt(test))
+            {
+                return false;
+            }
+            let mut res = true;
+            x.recurse(&mut |x| res = res && f(a, b, x));
+            res
+        }
+        self == other || x.iter().all(|x| f(self, other, x))
+    }
 }
 
 #[cfg(test)]
@@ -457,6 +481,30 @@ mod tests {
         assert!(toml::from_str::<Output>("version = '5000000000'").is_err());
     }
 
+    #[test]
+    fn test_prunes_like() {
+        let (ast, _) = Ast::parse(
+            r#"
+import sys
+def f():
+    if sys.platform == "win32":
+        pass
+    elif sys.version_info >= (3, 12):
+        pass
+"#,
+        );
+        let sys_info = |version, platform| {
+            SysInfo::new(
+                PythonVersion::from_str(version).unwrap(),
+                PythonPlatform::new(platform),
+            )
+        };
+        let linux_9 = sys_info("3.9", "linux");
+        assert!(linux_9.prunes_like(&sys_info("3.11", "darwin"), &ast.body));
+        assert!(!linux_9.prunes_like(&sys_info("3.12", "linux"), &ast.body));
+        assert!(!linux_9.prunes_like(&sys_info("3.9", "win32"), &ast.body));
+    }
+
     #[test]
     fn test_tuple_lexicographical_compare() {
         fn assert_compare(op: CmpOp, x: &[i64], y: &[i64]) {
@@ -0,0 +1,268 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::test::util::TestEnv;
+use crate::testcase;
+
+fn env_attrs() -> TestEnv {
+    let mut env = TestEnv::new();
+    env.add_with_path(
+        "attr",
+        "attr/__init__.pyi",
+        r#"
+from typing import Any, Callable, TypeVar, overload
+_C = TypeVar("_C", bound=type)
+@overload
+def attrs(
+    maybe_cls: _C,
+    *,
+    auto_attribs: bool | None = None,
+    frozen: bool = False,
+    kw_only: bool = False,
+    init: bool = True,
+    order: bool = True,
+) -> _C: ...
+@overload
+def attrs(
+    maybe_cls: None = None,
+    *,
+    auto_attribs: bool | None = None,
+    frozen: bool = False,
+    kw_only: bool = False,
+    init: bool = True,
+    order: bool = True,
+) -> Callable[[_C], _C]: ...
+s = attrs
+@overload
+def define(maybe_cls: _C, *, frozen: bool = False, kw_only: bool = False) -> _C: ...
+@overload
+def define(maybe_cls: None = None, *, frozen: bool = False, kw_only: bool = False) -> Callable[[_C], _C]: ...
+@overload
+def frozen(maybe_cls: _C, *, kw_only: bool = False) -> _C: ...
+@overload
+def frozen(maybe_cls: None = None, *, kw_only: bool = False) -> Callable[[_C], _C]: ...
+def attrib(
+    default: Any = ...,
+    *,
+    init: bool = True,
+    kw_only: bool = False,
+    converter: Callable[..., Any] | None = None,
+    factory: Callable[[], Any] | None = None,
+    alias: str | None = None,
+) -> Any: ...
+ib = attrib
+field = attrib
+    "#,
+    );
+    env.add_with_path(
+        "attrs",
+        "attrs/__init__.pyi",
+        "from attr import define as define, field as field, frozen as frozen",
+    );
+    env
+}
+
+testcase!(
+    test_attr_s,
+    env_attrs(),
+    r#"
+import attr
+@attr.s
+class A:
+    x: int = attr.ib()
+    y = attr.ib(default=0)
+    z: str = "not a field"
+A(1)
+A(1, 2)
+A(x=1, y=2)
+A(1, 2, "")  # E: Expected 2 positional arguments
+A("")  # E: Argument `Literal['']` is not assignable to parameter `x` with type `int`
+A(z="")  # E: Missing argument `x`  # E: Unexpected keyword argument `z`
+    "#,
+);
+
+testcase!(
+    test_attr_s_auto_attribs,
+    env_attrs(),
+    r#"
+import attr
+@attr.s(auto_attribs=True)
+class A:
+    x: int
+    y: str = "y"
+A(1)
+A(1, "")
+A("")  # E: Argument `Literal['']` is not assignable to parameter `x` with type `int`
+    "#,
+);
+
+testcase!(
+    test_attr_s_order,
+    env_attrs(),
+    r#"
+import attr
+from typing import assert_type
+@attr.s(auto_attribs=True)
+class A:
+    x: int
+@attr.s(auto_attribs=True, order=False)
+class B:
+    x: int
+def f(a: A, b: B):
+    assert_type(a < a, bool)
+    b < b  # E: `<` is not supported between `B` and `B`
+    "#,
+);
+
+testcase!(
+    test_define_detects_auto_attribs,
+    env_attrs(),
+    r#"
+from attrs import define, field
+@define
+class A:
+    x: int
+    y: str = field(default="")
+A(1)
+A(1, "")
+A()  # E: Missing argument `x`
+
+@define
+class B:
+    x = field()
+    y: int = 0  # Not a field, because there is an unannotated `field()`.
+B(1)
+B(1, 2)  # E: Expected 1 positional argument
+    "#,
+);
+
+testcase!(
+    test_attrs_frozen,
+    env_attrs(),
+    r#"
+from attrs import frozen
+@frozen
+class A:
+    x: int
+def f(a: A):
+    a.x = 0  # E: Cannot assign to read-only attribute `x`
+    "#,
+);
+
+testcase!(
+    test_attrs_private_name_and_alias,
+    env_attrs(),
+    r#"
+from attrs import define, field
+@define
+class A:
+    _x: int
+    y: int = field(alias="why")
+A(x=1, why=2)
+A(_x=1, why=2)  # E: Missing argument `x`  # E: Unexpected keyword argument `_x`
+    "#,
+);
+
+testcase!(
+    test_attrs_converter,
+    env_attrs(),
+    r#"
+from attrs import define, field
+from typing import assert_type
+def to_int(x: str) -> int:
+    return int(x)
+@define
+class A:
+    x: int = field(converter=to_int)
+a = A("1")
+assert_type(a.x, int)
+A(1)  # E: Argument `Literal[1]` is not assignable to parameter `x` with type `str`
+    "#,
+);
+
+testcase!(
+    test_attrs_init_false_and_kw_only,
+    env_attrs(),
+    r#"
+from attrs import define, field
+@define
+class A:
+    x: int
+    y: int = field(init=False)
+    z: int = field(kw_only=True)
+A(1, z=2)
+A(1, 2)  # E: Missing argument `z`  # E: Expected 1 positional argument
+    "#,
+);
+
+testcase!(
+    test_attrs_inheritance,
+    env_attrs(),
+    r#"
+from attrs import define
+@define
+class A:
+    x: int
+@define
+class B(A):
+    y: str
+B(1, "")
+B("", 1)  # E: Argument `Literal['']` is not assignable to parameter `x` with type `int`  # E: Argument `Literal[1]` is not assignable to parameter `y` with type `str`
+    "#,
+);
+
+testcase!(
+    test_attrs_inherited_field_uses_defining_auto_attribs,
+    env_attrs(),
+    r#"
+from attrs import define, field
+@define
+class A:
+    x: int
+@define
+class B(A):
+    y = field()
+B(1, 2)
+B(y=2)  # E: Missing argument `x`
+    "#,
+);
+
+testcase!(
+    test_attrs_converter_class,
+    env_attrs(),
+    r#"
+from attrs import define, field
+class Celsius:
+    def __init__(self, degrees: float) -> None: ...
+@define
+class A:
+    t: Celsius = field(converter=Celsius)
+A(1.0)
+A("hot")  # E: Argument `Literal['hot']` is not assignable to parameter `t` with type `float`
+    "#,
+);
+
+testcase!(
+    test_attrs_converter_overload,
+    env_attrs(),
+    r#"
+from attrs import define, field
+from typing import overload
+@overload
+def conv(x: int) -> str: ...
+@overload
+def conv(x: bytes) -> str: ...
+def conv(x: int | bytes) -> str:
+    return str(x)
+@define
+class A:
+    s: str = field(converter=conv)
+A(1)
+A(b"")
+A("")  # E: Argument `Literal['']` is not assignable to parameter `s`
+    "#,
+);
@@ -782,3 +782,18 @@ isinstance(1, "not a class object")  # E: Expected class object
 issubclass(str, "not a class object")  # E: Expected class object
     "#,
 );
+
+testcase!(
+    test_callable_explain_mismatch,
+    r#"
+from typing import Callable, Protocol
+class P(Protocol):
+    def __call__(self, *, x: int, y: str) -> int: ...
+def f(*, x: int) -> int: ...
+def g(*, x: int, y: bytes) -> int: ...
+def h(*, x: int, y: str) -> str: ...
+a: P = f  # E: Keyword parameter `y` is missing
+b: P = g  # E: Parameter `y` has type `bytes`, which does not accept `str`
+c: P = h  # E: Return type `str` is not assignable to `int`
+"#,
+);
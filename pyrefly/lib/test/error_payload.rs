@@ -0,0 +1,94 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use serde_json::Value;
+use serde_json::json;
+
+use crate::error::legacy::LegacyErrors;
+use crate::test::util::mk_state;
+
+fn errors_json_with(code: &str, include_suppressed: bool) -> Vec<Value> {
+    let (handle, state) = mk_state(code);
+    let errors = state.transaction().get_errors([&handle]).collect_errors();
+    let json =
+        serde_json::to_value(LegacyErrors::from_collected(&errors, include_suppressed)).unwrap();
+    json["errors"].as_array().unwrap().clone()
+}
+
+fn errors_json(code: &str) -> Vec<Value> {
+    errors_json_with(code, false)
+}
+
+#[test]
+fn test_payload_type_check() {
+    let errors = errors_json(
+        r#"
+class A: ...
+def f(x: int) -> None: ...
+f(A())
+"#,
+    );
+    assert_eq!(errors.len(), 1);
+    let error = &errors[0];
+    assert_eq!(error["type_check"], json!("CallArgument"));
+    assert_eq!(error["suppressed"], json!(false));
+    assert_eq!(
+        error["actual"],
+        json!({"rendered": "A", "qualified": "main.A"})
+    );
+    assert_eq!(
+        error["expected"],
+        json!({"rendered": "int", "qualified": "builtins.int"})
+    );
+    assert!(error.get("context").is_none());
+}
+
+#[test]
+fn test_payload_context() {
+    let errors = errors_json(
+        r#"
+class A: ...
+A() + 1
+"#,
+    );
+    assert_eq!(errors.len(), 1);
+    let error = &errors[0];
+    assert_eq!(error["context"], json!("BinaryOp"));
+    assert_eq!(error["types"][0]["qualified"], json!("main.A"));
+    assert_eq!(error["types"][1]["rendered"], json!("Literal[1]"));
+}
+
+#[test]
+fn test_payload_message_only() {
+    let errors = errors_json(
+        r#"
+import does_not_exist
+"#,
+    );
+    assert_eq!(errors.len(), 1);
+    let error = &errors[0];
+    assert_eq!(error["name"], json!("import-error"));
+    assert!(error.get("type_check").is_none());
+    assert!(error.get("types").is_none());
+}
+
+#[test]
+fn test_payload_suppressed() {
+    let code = r#"
+import does_not_exist
+import also_does_not_exist  # type: ignore
+"#;
+    let errors = errors_json(code);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0]["suppressed"], json!(false));
+
+    let errors = errors_json_with(code, true);
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0]["suppressed"], json!(false));
+    assert_eq!(errors[1]["suppressed"], json!(true));
+    assert_eq!(errors[1]["line"], json!(3));
+}
@@ -0,0 +1,177 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::testcase;
+
+testcase!(
+    test_total_ordering,
+    r#"
+from functools import total_ordering
+from typing import assert_type
+@total_ordering
+class A:
+    def __init__(self, x: int) -> None:
+        self.x = x
+    def __lt__(self, other: "A") -> bool:
+        return self.x < other.x
+def f(a: A, b: A):
+    assert_type(a < b, bool)
+    assert_type(a <= b, bool)
+    assert_type(a > b, bool)
+    assert_type(a >= b, bool)
+    a >= 1  # E: `>=` is not supported between `A` and `Literal[1]`
+    "#,
+);
+
+testcase!(
+    test_total_ordering_uses_root_other_type,
+    r#"
+from functools import total_ordering
+from typing import assert_type
+@total_ordering
+class A:
+    def __ge__(self, other: object) -> bool:
+        return True
+def f(a: A):
+    assert_type(a < 1, bool)
+    assert_type(a <= "", bool)
+    "#,
+);
+
+testcase!(
+    test_total_ordering_inherited_root,
+    r#"
+from functools import total_ordering
+from typing import assert_type
+class Base:
+    def __gt__(self, other: "Base") -> bool:
+        return True
+@total_ordering
+class A(Base):
+    pass
+def f(a: A, b: A):
+    assert_type(a < b, bool)
+    assert_type(a > b, bool)
+    "#,
+);
+
+testcase!(
+    test_total_ordering_no_root,
+    r#"
+from functools import total_ordering
+@total_ordering
+class A:  # E: Class `A` must define at least one of `__lt__`, `__le__`, `__gt__`, or `__ge__` to use `functools.total_ordering`
+    pass
+    "#,
+);
+
+testcase!(
+    test_total_ordering_dataclass,
+    r#"
+from dataclasses import dataclass
+from functools import total_ordering
+from typing import assert_type
+@total_ordering
+@dataclass
+class A:
+    x: int
+    def __lt__(self, other: "A") -> bool:
+        return self.x < other.x
+def f(a: A, b: A):
+    assert_type(a >= b, bool)
+A(x=1)
+    "#,
+);
+
+testcase!(
+    test_cached_property,
+    r#"
+from functools import cached_property
+from typing import assert_type
+class A:
+    @cached_property
+    def x(self) -> int:
+        return 1
+assert_type(A().x, int)
+    "#,
+);
+
+testcase!(
+    test_singledispatch_register,
+    r#"
+from functools import singledispatch
+from typing import assert_type
+@singledispatch
+def f(x: object) -> str:
+    return ""
+@f.register
+def _int(x: int) -> str:
+    return ""
+@f.register(bytes)
+def _bytes(x: bytes, y: int = 0) -> str:
+    return ""
+assert_type(f(1.0), str)
+assert_type(_bytes(b"", 1), str)
+_int("")  # E: Argument `Literal['']` is not assignable to parameter `x` with type `int`
+    "#,
+);
+
+testcase!(
+    test_singledispatch_register_unannotated,
+    r#"
+from functools import singledispatch
+@singledispatch
+def f(x: object) -> None: ...
+@f.register
+def _(x) -> None: ...  # E: Cannot register `_` with `register`, the argument it dispatches on must be annotated
+    "#,
+);
+
+testcase!(
+    test_singledispatch_register_class_mismatch,
+    r#"
+from functools import singledispatch
+@singledispatch
+def f(x: object) -> None: ...
+@f.register(str)  # E: `_` is registered for `str`, but its argument `x` has type `int`
+def _(x: int) -> None: ...
+@f.register(bool)
+def _(x: int) -> None: ...
+@f.register(int)
+def _(x) -> None: ...
+    "#,
+);
+
+testcase!(
+    test_singledispatch_register_bad_return,
+    r#"
+from functools import singledispatch
+@singledispatch
+def f(x: object) -> str:
+    return ""
+@f.register
+def _(x: int) -> int:  # E: `_` is registered with a `singledispatch` function returning `str`, but returns `int`
+    return 0
+@f.register(bytes)
+def _(x: bytes) -> bytes:  # E: `_` is registered with a `singledispatch` function returning `str`, but returns `bytes`
+    return x
+    "#,
+);
+
+testcase!(
+    test_singledispatchmethod_register,
+    r#"
+from functools import singledispatchmethod
+class A:
+    @singledispatchmethod
+    def f(self, x: object) -> None: ...
+    @f.register
+    def _(self, x: int) -> None: ...
+    @f.register
+    def _g(self, x) -> None: ...  # E: Cannot register `_g` with `register`, the argument it dispatches on must be annotated
+    "#,
+);
@@ -869,3 +869,11 @@ def g[T2](x: type[T2]) -> T2:
     return x()
     "#,
 );
+
+testcase!(
+    test_explain_invariant_type_argument,
+    r#"
+def f(x: list[int]) -> None:
+    y: list[float] = x  # E: Type argument `int` is not compatible with `float` for invariant type parameter `_T`
+"#,
+);
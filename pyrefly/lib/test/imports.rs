@@ -130,11 +130,64 @@ testcase!(
     env_imports_dot(),
     r#"
 from typing import assert_type
-from foo.bar.baz import x
+from foo.bar.baz import x  # E: Module `foo.bar.baz` does not explicitly re-export `x`
 assert_type(x, int)
 "#,
 );
 
+fn env_stub_reexports() -> TestEnv {
+    let mut t = TestEnv::new();
+    t.add_with_path(
+        "foo",
+        "foo/__init__.pyi",
+        r#"
+import os
+import sys as sys
+from typing import Any, List as List
+from . import sub
+"#,
+    );
+    t.add_with_path("foo.sub", "foo/sub.pyi", "x: int");
+    t.add_with_path(
+        "bar",
+        "bar.pyi",
+        r#"
+from typing import Any, List
+__all__ = ["Any"]
+"#,
+    );
+    t
+}
+
+testcase!(
+    test_stub_explicit_reexport,
+    env_stub_reexports(),
+    r#"
+from foo import os  # E: Module `foo` does not explicitly re-export `os`
+from foo import Any  # E: Module `foo` does not explicitly re-export `Any`
+from foo import sys, List, sub
+from bar import Any
+from bar import List  # E: Module `bar` does not explicitly re-export `List`
+"#,
+);
+
+testcase!(
+    test_stub_explicit_reexport_disabled,
+    env_stub_reexports(),
+    r#"
+# pyrefly: explicit-reexport = false
+from foo import os, Any
+"#,
+);
+
+testcase!(
+    test_source_implicit_reexport,
+    TestEnv::one("foo", "from typing import Any"),
+    r#"
+from foo import Any
+"#,
+);
+
 testcase!(
     test_access_nonexistent_module,
     env_imports_dot(),
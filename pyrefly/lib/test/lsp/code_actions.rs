@@ -14,6 +14,7 @@ use crate::state::handle::Handle;
 use crate::state::state::State;
 use crate::state::state::Transaction;
 use crate::test::util::get_batched_lsp_operations_report_allow_error;
+use crate::test::util::get_batched_lsp_operations_report_no_cursor;
 
 fn apply_patch(
     transaction: &Transaction<'_>,
@@ -36,8 +37,9 @@ fn apply_patch(
 fn get_test_report(state: &State, handle: &Handle, position: TextSize) -> String {
     let mut report = "Code Actions Results:\n".to_owned();
     let transaction = state.transaction();
+    let errors = transaction.get_errors(vec![handle]).collect_errors().shown;
     for (title, range, patch) in transaction
-        .local_quickfix_code_actions(handle, TextRange::new(position, position))
+        .local_quickfix_code_actions(handle, TextRange::new(position, position), &errors)
         .unwrap_or_default()
     {
         let (before, after) = apply_patch(&transaction, handle, range, patch);
@@ -103,6 +105,51 @@ my_export
     );
 }
 
+#[test]
+fn explicit_reexport_test() {
+    let report = get_batched_lsp_operations_report_allow_error(
+        &[
+            ("a", "my_export = 3\n"),
+            ("b", "from .a import my_export as my_export\n"),
+            ("c", "my_export\n# ^"),
+        ],
+        get_test_report,
+    );
+    // The explicit re-export in b is suggested alongside the original definition.
+    assert_eq!(
+        r#"
+# a.py
+
+# b.py
+
+# c.py
+1 | my_export
+      ^
+Code Actions Results:
+# Title: Insert import: `from a import my_export`
+
+## Before:
+my_export
+# ^
+## After:
+from a import my_export
+my_export
+# ^
+# Title: Insert import: `from b import my_export`
+
+## Before:
+my_export
+# ^
+## After:
+from b import my_export
+my_export
+# ^
+"#
+        .trim(),
+        report.trim()
+    );
+}
+
 #[test]
 fn insertion_test_comments() {
     let report = get_batched_lsp_operations_report_allow_error(
@@ -210,3 +257,56 @@ my_export
         report.trim()
     );
 }
+
+fn get_remove_unused_imports_report(state: &State, handle: &Handle) -> String {
+    let transaction = state.transaction();
+    let module_info = transaction.get_module_info(handle).unwrap();
+    let mut after = module_info.contents().as_str().to_owned();
+    let errors = transaction.get_errors(vec![handle]).collect_errors().shown;
+    let mut edits = transaction
+        .remove_unused_imports_edits(handle, &errors)
+        .unwrap_or_default();
+    // Apply the edits from the end, so the earlier ranges stay valid.
+    edits.reverse();
+    for (range, patch) in edits {
+        let range = module_info.to_text_range(&range);
+        after.replace_range(range.start().to_usize()..range.end().to_usize(), &patch);
+    }
+    after
+}
+
+#[test]
+fn remove_unused_imports_test() {
+    let report = get_batched_lsp_operations_report_no_cursor(
+        &[(
+            "main",
+            r#"# pyrefly: errors = {unused-import = true}
+import os, sys
+from typing import List, Dict as D
+import json
+from typing import Any as Any
+def f() -> None:
+    import re
+print(sys.argv)
+x: List[int] = []
+"#,
+        )],
+        get_remove_unused_imports_report,
+    );
+    assert_eq!(
+        r#"
+# main.py
+
+# pyrefly: errors = {unused-import = true}
+import sys
+from typing import List
+from typing import Any as Any
+def f() -> None:
+    pass
+print(sys.argv)
+x: List[int] = []
+"#
+        .trim(),
+        report.trim()
+    );
+}
@@ -11,6 +11,7 @@ use ruff_text_size::TextSize;
 
 use crate::state::handle::Handle;
 use crate::state::state::State;
+use crate::test::util::TestEnv;
 use crate::test::util::get_batched_lsp_operations_report_allow_error;
 
 fn get_test_report(state: &State, handle: &Handle, position: TextSize) -> String {
@@ -270,3 +271,29 @@ Completion Results:
         report.trim(),
     );
 }
+
+#[test]
+fn from_import_stub_reexport() {
+    let mut env = TestEnv::new();
+    env.add_with_path(
+        "foo",
+        "foo.pyi",
+        r#"
+import os
+from typing import Any, List as List
+import_me = 1
+"#,
+    );
+    env.add("main", "from foo import x");
+    let (state, handle) = env.to_state();
+    let position = TextSize::new("from foo import x".len() as u32);
+    assert_eq!(
+        r#"
+Completion Results:
+- (Variable) List
+- (Variable) import_me
+"#
+        .trim(),
+        get_test_report(&state, &handle("main"), position),
+    );
+}
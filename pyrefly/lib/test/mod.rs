@@ -10,6 +10,7 @@
 mod assign;
 mod attribute_narrow;
 mod attributes;
+mod attrs;
 mod callable;
 mod calls;
 mod class_keywords;
@@ -25,7 +26,9 @@ mod decorators;
 mod delayed_inference;
 mod descriptors;
 mod enums;
+mod error_payload;
 mod flow;
+mod functools;
 mod generic_basic;
 mod generic_restrictions;
 mod imports;
@@ -41,11 +44,15 @@ mod overload;
 mod paramspec;
 mod pattern_match;
 mod perf;
+mod plugin;
 mod protocol;
+mod pydantic;
 mod returns;
 mod scope;
 mod simple;
+mod slots;
 mod state;
+mod strict;
 mod subscript_narrow;
 mod suppression;
 mod tuple;
@@ -53,7 +60,9 @@ mod type_alias;
 mod type_var_tuple;
 mod typed_dict;
 mod typing_self;
+mod unreachable;
 mod untyped_def_behaviors;
+mod unused;
 pub mod util;
 mod var_resolution;
 mod variance_inference;
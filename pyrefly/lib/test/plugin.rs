@@ -0,0 +1,138 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use ruff_python_ast::name::Name;
+use starlark_map::small_map::SmallMap;
+
+use crate::library::library::library::library::Callable;
+use crate::library::library::library::library::Class;
+use crate::library::library::library::library::ClassSynthesizedField;
+use crate::library::library::library::library::ClassSynthesizedFields;
+use crate::library::library::library::library::FuncMetadata;
+use crate::library::library::library::library::Function;
+use crate::library::library::library::library::Param;
+use crate::library::library::library::library::ParamList;
+use crate::library::library::library::library::Plugin;
+use crate::library::library::library::library::PluginContext;
+use crate::library::library::library::library::Plugins;
+use crate::library::library::library::library::Required;
+use crate::library::library::library::library::Type;
+use crate::test::util::TestEnv;
+use crate::testcase;
+
+// Plugins live in other crates, so only use what the `library` module exports.
+
+/// Gives subclasses of `orm.Model` an `__init__` that takes each annotated attribute as a keyword.
+struct ModelPlugin;
+
+impl Plugin for ModelPlugin {
+    fn class_synthesized_fields(
+        &self,
+        ctx: &dyn PluginContext,
+        cls: &Class,
+    ) -> Option<ClassSynthesizedFields> {
+        let mut params = vec![Param::Pos(
+            Name::new_static("self"),
+            ctx.instantiate(cls),
+            Required::Required,
+        )];
+        for name in cls.fields() {
+            if cls.is_field_annotated(name)
+                && let Some(ty) = ctx.class_field_type(cls, name)
+            {
+                params.push(Param::KwOnly(name.clone(), ty, Required::Required));
+            }
+        }
+        let init = Type::Function(Box::new(Function {
+            signature: Callable::list(ParamList::new(params), Type::None),
+            metadata: FuncMetadata::def(
+                cls.module_name(),
+                cls.name().clone(),
+                Name::new_static("__init__"),
+            ),
+        }));
+        let mut fields = SmallMap::new();
+        fields.insert(
+            Name::new_static("__init__"),
+            ClassSynthesizedField::new(init),
+        );
+        Some(ClassSynthesizedFields::new(fields))
+    }
+}
+
+/// Makes `orm.column(T)` return an instance of `T`.
+struct ColumnPlugin;
+
+impl Plugin for ColumnPlugin {
+    fn call_return_type(
+        &self,
+        ctx: &dyn PluginContext,
+        args: &[Type],
+        _keywords: &[(Name, Type)],
+        _ret: &Type,
+    ) -> Option<Type> {
+        match args.first()? {
+            Type::ClassDef(cls) => Some(ctx.instantiate(cls)),
+            _ => None,
+        }
+    }
+}
+
+fn env_orm() -> TestEnv {
+    let mut plugins = Plugins::new();
+    plugins.register("orm.Model", Arc::new(ModelPlugin));
+    plugins.register("orm.column", Arc::new(ColumnPlugin));
+    let mut env = TestEnv::new_with_plugins(plugins);
+    env.add(
+        "orm",
+        r#"
+class Model: ...
+def column(ty: type) -> object: ...
+"#,
+    );
+    env
+}
+
+testcase!(
+    test_plugin_class_synthesis,
+    env_orm(),
+    r#"
+from orm import Model
+class User(Model):
+    name: str
+    age: int
+class Admin(User):
+    level: int
+User(name="", age=1)
+User(name=1, age=1)  # E: Argument `Literal[1]` is not assignable to parameter `name` with type `str`
+User("", 1)  # E: Expected 0 positional arguments
+Admin(level=1)
+    "#,
+);
+
+testcase!(
+    test_plugin_call_return,
+    env_orm(),
+    r#"
+from orm import column
+from typing import assert_type
+assert_type(column(int), int)
+assert_type(column(str), str)
+    "#,
+);
+
+testcase!(
+    test_plugin_not_registered,
+    r#"
+class Model: ...
+class User(Model):
+    name: str
+User(name="")  # E: Unexpected keyword argument `name`
+    "#,
+);
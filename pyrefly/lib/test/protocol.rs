@@ -5,6 +5,7 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use crate::test::util::mk_state;
 use crate::testcase;
 
 testcase!(
@@ -562,3 +563,64 @@ class AnotherClass: # E: @runtime_checkable can only be applied to Protocol clas
     x: int = 5
 "#,
 );
+
+testcase!(
+    test_protocol_explain_missing_member,
+    r#"
+from typing import Protocol
+class P(Protocol):
+    x: int
+    def f(self) -> None: ...
+class C:
+    x: int = 0
+def g(p: P) -> None: ...
+g(C())  # E: Argument `C` is not assignable to parameter `p` with type `P` in function `g`\n  Protocol member `f` is missing
+"#,
+);
+
+testcase!(
+    test_protocol_explain_incompatible_member,
+    r#"
+from typing import Protocol
+class P(Protocol):
+    def f(self, x: int) -> None: ...
+class C:
+    def f(self, x: str) -> None: ...
+def g(p: P) -> None: ...
+g(C())  # E: Protocol member `f` has an incompatible type\n    Parameter `x` has type `str`, which does not accept `int`
+"#,
+);
+
+#[test]
+fn test_protocol_explain_location() {
+    let (handle, state) = mk_state(
+        r#"
+from typing import Protocol
+class P(Protocol):
+    def f(self, x: int) -> None: ...
+class C:
+    def f(self, x: str) -> None: ...
+def g(p: P) -> None: ...
+g(C())
+"#,
+    );
+    let errors = state.transaction().get_errors([&handle]).collect_errors();
+    let [error] = errors.shown.as_slice() else {
+        panic!("Expected one error, got {:?}", errors.shown);
+    };
+    // The member and the parameter that caused it both point at the protocol member.
+    assert_eq!(error.because().len(), 2);
+    for reason in error.because() {
+        let location = reason.location.as_ref().unwrap();
+        assert_eq!(location.module_info.code_at(location.range), "f");
+        assert_eq!(
+            location
+                .module_info
+                .source_range(location.range)
+                .start
+                .line
+                .get(),
+            4
+        );
+    }
+}
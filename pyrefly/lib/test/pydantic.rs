@@ -0,0 +1,247 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::test::util::TestEnv;
+use crate::testcase;
+
+fn env_pydantic() -> TestEnv {
+    let mut env = TestEnv::new();
+    env.add_with_path(
+        "pydantic",
+        "pydantic/__init__.pyi",
+        r#"
+from pydantic.config import ConfigDict as ConfigDict
+from pydantic.fields import Field as Field
+from pydantic.functional_validators import field_validator as field_validator
+from pydantic.functional_validators import model_validator as model_validator
+from pydantic.main import BaseModel as BaseModel
+from pydantic.root_model import RootModel as RootModel
+"#,
+    );
+    env.add_with_path(
+        "pydantic.config",
+        "pydantic/config.pyi",
+        r#"
+from typing import Literal, TypedDict
+class ConfigDict(TypedDict, total=False):
+    frozen: bool
+    populate_by_name: bool
+    extra: Literal["allow", "ignore", "forbid"]
+"#,
+    );
+    env.add_with_path(
+        "pydantic.fields",
+        "pydantic/fields.pyi",
+        r#"
+from typing import Any, Callable
+def Field(
+    default: Any = ...,
+    *,
+    default_factory: Callable[[], Any] | None = None,
+    alias: str | None = None,
+) -> Any: ...
+"#,
+    );
+    env.add_with_path(
+        "pydantic.functional_validators",
+        "pydantic/functional_validators.pyi",
+        r#"
+from typing import Any, Callable
+def field_validator(
+    field: str, /, *fields: str, mode: str = "after", check_fields: bool | None = None
+) -> Callable[[Any], Any]: ...
+def model_validator(*, mode: str) -> Callable[[Any], Any]: ...
+"#,
+    );
+    env.add_with_path(
+        "pydantic.main",
+        "pydantic/main.pyi",
+        r#"
+from typing import Any, ClassVar
+from pydantic.config import ConfigDict
+class BaseModel:
+    model_config: ClassVar[ConfigDict]
+    def __init__(self, /, **data: Any) -> None: ...
+    def __init_subclass__(cls, **kwargs: Any) -> None: ...
+"#,
+    );
+    env.add_with_path(
+        "pydantic.root_model",
+        "pydantic/root_model.pyi",
+        r#"
+from typing import Generic, TypeVar
+from pydantic.main import BaseModel
+RootModelRootType = TypeVar("RootModelRootType")
+class RootModel(BaseModel, Generic[RootModelRootType]):
+    root: RootModelRootType
+"#,
+    );
+    env
+}
+
+testcase!(
+    test_pydantic_init,
+    env_pydantic(),
+    r#"
+from pydantic import BaseModel, Field, field_validator
+class User(BaseModel):
+    id: int
+    name: str = "Jane"
+    tags: list[str] = Field(default_factory=list)
+    nickname: str = Field(...)
+    _secret: str = "private attributes are not fields"
+
+    @field_validator("name")
+    @classmethod
+    def check_name(cls, v: str) -> str:
+        return v
+User(id=1, nickname="")
+User(id=1, name="", tags=[], nickname="")
+User(id="", nickname="")  # E: Argument `Literal['']` is not assignable to parameter `id` with type `int`
+User(nickname="")  # E: Missing argument `id`
+User(1, "")  # E: Missing argument `id`  # E: Missing argument `nickname`  # E: Expected 0 positional arguments
+User(id=1, nickname="", _secret="")  # E: Unexpected keyword argument `_secret`
+    "#,
+);
+
+testcase!(
+    test_pydantic_alias,
+    env_pydantic(),
+    r#"
+from pydantic import BaseModel, ConfigDict, Field
+class A(BaseModel):
+    x: int = Field(alias="X")
+A(X=1)
+A(x=1)  # E: Missing argument `X`  # E: Unexpected keyword argument `x`
+
+class B(BaseModel):
+    model_config = ConfigDict(populate_by_name=True)
+    x: int = Field(alias="X")
+B(x=1)
+B(X=1)
+    "#,
+);
+
+testcase!(
+    test_pydantic_frozen,
+    env_pydantic(),
+    r#"
+from pydantic import BaseModel, ConfigDict
+class A(BaseModel, frozen=True):
+    x: int
+class B(BaseModel):
+    model_config = ConfigDict(frozen=True)
+    x: int
+class C(B):
+    y: int
+class D(BaseModel):
+    x: int
+def f(a: A, b: B, c: C, d: D):
+    a.x = 0  # E: Cannot assign to read-only attribute `x`
+    b.x = 0  # E: Cannot assign to read-only attribute `x`
+    c.y = 0  # E: Cannot assign to read-only attribute `y`
+    d.x = 0
+    "#,
+);
+
+testcase!(
+    test_pydantic_extra_allow,
+    env_pydantic(),
+    r#"
+from pydantic import BaseModel
+class A(BaseModel):
+    model_config = {"extra": "allow"}
+    x: int
+class B(BaseModel):
+    model_config = {"extra": "forbid"}
+    x: int
+A(x=1, y=2)
+B(x=1, y=2)  # E: Unexpected keyword argument `y`
+    "#,
+);
+
+testcase!(
+    test_pydantic_inheritance,
+    env_pydantic(),
+    r#"
+from pydantic import BaseModel
+class A(BaseModel):
+    x: int
+class B(A):
+    y: str
+B(x=1, y="")
+B(y="")  # E: Missing argument `x`
+    "#,
+);
+
+testcase!(
+    test_pydantic_root_model,
+    env_pydantic(),
+    r#"
+from pydantic import RootModel
+from typing import assert_type
+class Pets(RootModel[list[str]]):
+    pass
+p = Pets(["dog"])
+assert_type(p.root, list[str])
+Pets(1)  # E: Argument `Literal[1]` is not assignable to parameter `root` with type `list[str]`
+    "#,
+);
+
+testcase!(
+    test_pydantic_field_validator_fields,
+    env_pydantic(),
+    r#"
+from pydantic import BaseModel, field_validator
+class A(BaseModel):
+    x: int
+class B(A):
+    y: int
+
+    @field_validator("x", "y")
+    def check(cls, v: int) -> int:
+        return v
+
+    @field_validator("*")
+    def check_all(cls, v: int) -> int:
+        return v
+
+    @field_validator("z")  # E: Validator refers to `z`, which is not a field of `B`
+    def check_z(cls, v: int) -> int:
+        return v
+
+    @field_validator("z", check_fields=False)
+    def check_z_unchecked(cls, v: int) -> int:
+        return v
+    "#,
+);
+
+testcase!(
+    test_pydantic_validator_receiver,
+    env_pydantic(),
+    r#"
+from pydantic import BaseModel, field_validator, model_validator
+from typing import Any, Self
+class A(BaseModel):
+    x: int
+
+    @field_validator("x")
+    def check_x(cls, v: int) -> int:
+        cls(x=v)
+        return v
+
+    @model_validator(mode="before")
+    def check_before(cls, data: Any) -> Any:
+        cls(x=0)
+        return data
+
+    @model_validator(mode="after")
+    def check_after(self) -> Self:
+        self.x + 1
+        return self
+    "#,
+);
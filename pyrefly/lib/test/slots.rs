@@ -0,0 +1,116 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::testcase;
+
+testcase!(
+    test_slots_assignment,
+    r#"
+class A:
+    __slots__ = ("x",)
+    def __init__(self) -> None:
+        self.x = 1
+        self.y = 2  # E: Attribute `y` is not declared in `__slots__` of class `A`
+    "#,
+);
+
+testcase!(
+    test_slots_forms,
+    r#"
+class A:
+    __slots__ = "x"
+    def __init__(self) -> None:
+        self.x = 1
+        self.y = 2  # E: Attribute `y` is not declared in `__slots__` of class `A`
+class B:
+    __slots__ = ["x"]
+    def __init__(self) -> None:
+        self.x = 1
+        self.y = 2  # E: Attribute `y` is not declared in `__slots__` of class `B`
+class C:
+    __slots__ = {"x": "documentation for x"}
+    def __init__(self) -> None:
+        self.x = 1
+        self.y = 2  # E: Attribute `y` is not declared in `__slots__` of class `C`
+    "#,
+);
+
+testcase!(
+    test_slots_annotation_only,
+    r#"
+from typing import ClassVar
+class A:
+    __slots__ = ("x",)
+    x: int
+    y: str  # E: Attribute `y` is not declared in `__slots__` of class `A`
+    z: ClassVar[int]
+    "#,
+);
+
+testcase!(
+    test_slots_dict,
+    r#"
+class A:
+    __slots__ = ("x", "__dict__")
+    def __init__(self) -> None:
+        self.x = 1
+        self.y = 2
+    "#,
+);
+
+testcase!(
+    test_slots_inheritance,
+    r#"
+class A:
+    __slots__ = ("x",)
+class B(A):
+    __slots__ = ("y",)
+    def __init__(self) -> None:
+        self.x = 1
+        self.y = 2
+        self.z = 3  # E: Attribute `z` is not declared in `__slots__` of class `B`
+class NoSlots:
+    pass
+class C(NoSlots):
+    __slots__ = ()
+    def __init__(self) -> None:
+        self.z = 3
+    "#,
+);
+
+testcase!(
+    test_slots_conflict_with_class_variable,
+    r#"
+class A:
+    __slots__ = ("x", "y", "f")  # E: `x` in `__slots__` conflicts with class variable  # E: `f` in `__slots__` conflicts with class variable
+    x = 1
+    y: int
+    def f(self) -> None: ...
+    "#,
+);
+
+testcase!(
+    test_slots_declared_field,
+    r#"
+class A:
+    __slots__ = ("x",)
+def f(a: A):
+    a.x = 1
+    a.y = 1  # E: Object of class `A` has no attribute `y`
+    "#,
+);
+
+testcase!(
+    test_slots_not_literal,
+    r#"
+names = ("x",)
+class A:
+    __slots__ = names
+    def __init__(self) -> None:
+        self.y = 2
+    "#,
+);
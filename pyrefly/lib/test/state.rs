@@ -10,22 +10,29 @@
 use std::mem;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use dupe::Dupe;
 use pyrefly_util::arc_id::ArcId;
 use pyrefly_util::lock::Mutex;
+use pyrefly_util::memory::Bytes;
 use pyrefly_util::prelude::SliceExt;
 use starlark_map::small_map::SmallMap;
 
 use crate::config::config::ConfigFile;
 use crate::config::finder::ConfigFinder;
 use crate::error::error::print_errors;
+use crate::error::kind::ErrorKind;
 use crate::module::module_name::ModuleName;
 use crate::module::module_path::ModulePath;
+use crate::report::binding_memory::binding_memory;
 use crate::state::handle::Handle;
 use crate::state::require::Require;
 use crate::state::state::State;
+use crate::state::steps::Step;
+use crate::state::subscriber::TelemetrySubscriber;
 use crate::state::subscriber::TestSubscriber;
+use crate::state::trace::ChromeTrace;
 use crate::sys_info::PythonPlatform;
 use crate::sys_info::PythonVersion;
 use crate::sys_info::SysInfo;
@@ -272,6 +279,150 @@ fn test_incremental_cyclic() {
     i.check(&["foo"], &["foo", "foo", "bar"]);
 }
 
+#[test]
+fn test_telemetry() {
+    let mut i = Incremental::new();
+    let run = |i: &mut Incremental, file: &str, contents: &str| {
+        let subscriber = TelemetrySubscriber::new(Duration::MAX, Box::new(|_| ()));
+        let mut transaction = i
+            .state
+            .new_committable_transaction(Require::Exports, Some(Box::new(subscriber.dupe())));
+        transaction.as_mut().set_memory(vec![(
+            PathBuf::from(file),
+            Some(Arc::new(contents.to_owned())),
+        )]);
+        let handle = i.handle("main");
+        i.state
+            .run_with_committing_transaction(transaction, &[(handle, Require::Everything)]);
+        subscriber.telemetry()
+    };
+
+    let first = run(&mut i, "main", "import foo; x = foo.x");
+    assert_eq!(first.started, first.finished);
+    assert!(first.finished >= 2);
+    assert!(first.steps.contains_key(&Step::Solutions));
+    assert_eq!(first.retained[&Require::Everything].modules, 1);
+
+    let second = run(&mut i, "foo", "x = 1");
+    assert!(second.invalidations.values().sum::<usize>() > 0);
+    assert!(second.cache_hits > 0);
+}
+
+#[test]
+fn test_chrome_trace() {
+    let mut i = Incremental::new();
+    let trace = ChromeTrace::new();
+    let mut transaction = i.state.new_committable_transaction(
+        Require::Exports,
+        Some(trace.subscriber(Box::new(TestSubscriber::new()))),
+    );
+    for (file, contents) in [("main", "import foo; x = foo.x"), ("foo", "x = 1")] {
+        transaction.as_mut().set_memory(vec![(
+            PathBuf::from(file),
+            Some(Arc::new(contents.to_owned())),
+        )]);
+    }
+    let handle = i.handle("main");
+    i.state
+        .run_with_committing_transaction(transaction, &[(handle, Require::Everything)]);
+
+    let temp = tempfile::tempdir().unwrap();
+    let path = temp.path().join("trace.json");
+    trace.write(&path).unwrap();
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let events = json["traceEvents"].as_array().unwrap();
+    let has = |name: &str| events.iter().any(|x| x["name"] == name);
+    assert!(has("thread_name"));
+    assert!(has("Solutions main"));
+    assert!(events.iter().any(|x| x["cat"] == "blocked"
+        && x["args"]["module"] == "main"
+        && x["args"]["waiting_on"] == "foo"));
+}
+
+#[test]
+fn test_memory_budget_evicts() {
+    let i = Incremental::new();
+    let mut transaction = i.state.new_transaction(Require::Everything, None);
+    transaction.set_memory(vec![
+        (
+            PathBuf::from("main"),
+            Some(Arc::new("import foo; x: str = foo.x # E:".to_owned())),
+        ),
+        (PathBuf::from("foo"), Some(Arc::new("x = 1".to_owned()))),
+    ]);
+    // Everything uses more than no memory, so we evict whatever we can.
+    transaction.set_memory_budget(Some(Bytes::new(0)));
+    let main = i.handle("main");
+    let foo = i.handle("foo");
+    transaction.run(&[(main.dupe(), Require::Everything)]);
+
+    let evicted = transaction.evicted();
+    assert!(
+        evicted
+            .iter()
+            .any(|(h, x)| h == &foo && x.ast && x.bindings > 0)
+    );
+    assert!(transaction.get_bindings(&foo).is_none());
+    assert!(transaction.get_solutions(&foo).is_some());
+    transaction
+        .get_errors([&main])
+        .check_against_expectations()
+        .unwrap();
+    assert!(binding_memory(&transaction).contains("foo,Evicted,Bindings,"));
+}
+
+#[test]
+fn test_solve_budget() {
+    let mut t = TestEnv::new_with_solve_budget(Duration::ZERO);
+    t.add("foo", "x: str = 1\ny: int = x\n");
+    let (state, handle) = t.to_state();
+    let errors = state
+        .transaction()
+        .get_errors([&handle("foo")])
+        .collect_errors()
+        .shown;
+    // The budget runs out immediately, so none of the bad assignments are reported.
+    assert_eq!(errors.map(|e| e.error_kind()), vec![ErrorKind::TooComplex]);
+    assert!(errors[0].msg().contains("exceeded its solve budget of 0ms"));
+}
+
+#[test]
+fn test_solve_budget_class() {
+    let mut t = TestEnv::new_with_solve_budget(Duration::ZERO);
+    t.add("foo", "x: int = 1\nclass C[T]: pass\n");
+    let (state, handle) = t.to_state();
+    let errors = state
+        .transaction()
+        .get_errors([&handle("foo")])
+        .collect_errors()
+        .shown;
+    assert_eq!(
+        errors.map(|e| e.error_kind()),
+        vec![ErrorKind::TooComplex, ErrorKind::TooComplex]
+    );
+    assert!(errors.iter().any(|e| {
+        e.msg()
+            .contains("Class `C` was not solved within the solve budget")
+    }));
+}
+
+#[test]
+fn test_solve_budget_generous() {
+    let mut t = TestEnv::new_with_solve_budget(Duration::from_secs(600));
+    t.add("foo", "x: str = 1\ny: int = x\n");
+    let (state, handle) = t.to_state();
+    let errors = state
+        .transaction()
+        .get_errors([&handle("foo")])
+        .collect_errors()
+        .shown;
+    assert_eq!(
+        errors.map(|e| e.error_kind()),
+        vec![ErrorKind::BadAssignment, ErrorKind::BadAssignment]
+    );
+}
+
 /// Check that the interface is consistent as we change things.
 fn test_interface_consistent(code: &str) {
     let mut i = Incremental::new();
@@ -0,0 +1,151 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::config::base::UntypedDefBehavior;
+use crate::test::util::TestEnv;
+use crate::testcase;
+
+testcase!(
+    test_strict_off_by_default,
+    r#"
+def f(x, *args, **kwargs):
+    return x
+"#,
+);
+
+testcase!(
+    test_strict_off_ignore_is_not_unused,
+    r#"
+def _f(x) -> None:  # pyrefly: ignore[implicit-any]
+    pass
+"#,
+);
+
+testcase!(
+    test_strict_implicit_any_param,
+    r#"
+# pyrefly: strict = true
+def _f(x: int, y, *args, z=1, **kwargs) -> None:  # E: Parameter `y` is unannotated # E: Parameter `args` is unannotated # E: Parameter `z` is unannotated # E: Parameter `kwargs` is unannotated
+    pass
+"#,
+);
+
+testcase!(
+    test_strict_receiver,
+    r#"
+# pyrefly: strict = true
+class C:
+    def f(self) -> None: ...
+    @classmethod
+    def g(cls) -> None: ...
+    @staticmethod
+    def h(x) -> None: ...  # E: Parameter `x` is unannotated
+    def __new__(cls) -> "C": ...
+"#,
+);
+
+testcase!(
+    test_strict_missing_return_annotation,
+    r#"
+# pyrefly: strict = true
+def public():  # E: Public function `public` is missing a return annotation
+    def nested():
+        return 1
+    return nested()
+def _private():
+    return 1
+class C:
+    def method(self):  # E: Public function `method` is missing a return annotation
+        return 1
+    def __eq__(self, other: object):
+        return True
+"#,
+);
+
+testcase!(
+    test_strict_implicit_any_return,
+    TestEnv::new_with_untyped_def_behavior(UntypedDefBehavior::CheckAndInferReturnAny),
+    r#"
+# pyrefly: strict = true
+def _f():  # E: Return type of `_f` is implicitly `Any`
+    return 1
+def public():  # E: Public function `public` is missing a return annotation
+    return 1
+"#,
+);
+
+testcase!(
+    test_strict_disable_one_check,
+    r#"
+# pyrefly: strict = true
+# pyrefly: errors = {missing-return-annotation = false}
+def public(x):  # E: Parameter `x` is unannotated
+    return x
+"#,
+);
+
+testcase!(
+    test_strict_enable_one_check,
+    r#"
+# pyrefly: errors = {implicit-any = true}
+def public(x):  # E: Parameter `x` is unannotated
+    return x
+"#,
+);
+
+testcase!(
+    test_strict_unnecessary_cast,
+    r#"
+# pyrefly: strict = true
+from typing import Any, cast
+def f(x: int, y: int | str, z: Any) -> None:
+    cast(int, x)  # E: Unnecessary `typing.cast`, the value already has type `int`
+    cast(int, y)
+    cast(int, z)
+    cast(Any, x)
+"#,
+);
+
+testcase!(
+    test_strict_unnecessary_isinstance,
+    r#"
+# pyrefly: strict = true
+from typing import Any, final
+@final
+class A: ...
+class B: ...
+def f(x: int, y: int | str, a: A, b: B, z: Any) -> None:
+    isinstance(x, int)  # E: Unnecessary `isinstance()` call, it is always `True` for `int`
+    isinstance(x, (int, str))  # E: always `True`
+    isinstance(y, int)
+    isinstance(a, B)  # E: Unnecessary `isinstance()` call, it is always `False` for `A`
+    isinstance(b, A)  # E: always `False`
+    isinstance(x, B)  # B could be subclassed together with int
+    isinstance(z, int)
+    if isinstance(y, str):
+        pass
+    elif isinstance(y, int):  # E: always `True`
+        pass
+def g(x: type[int], y: type[B]) -> None:
+    issubclass(x, int)  # E: Unnecessary `issubclass()` call, it is always `True` for `int`
+    issubclass(y, int)
+"#,
+);
+
+testcase!(
+    test_strict_unnecessary_comparison,
+    r#"
+# pyrefly: strict = true
+from typing import Literal
+def f(x: Literal["a", "b"], y: Literal[1], z: str) -> None:
+    x == "c"  # E: `==` between `Literal['a', 'b']` and `Literal['c']` is always `False`
+    x != "c"  # E: is always `True`
+    x == "a"
+    y == True
+    x == z
+"#,
+);
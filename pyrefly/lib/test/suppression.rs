@@ -62,3 +62,52 @@ testcase!(
 3 + "3" # E:
 "#,
 );
+
+testcase!(
+    test_pyrefly_suppression_code,
+    r#"
+def foo() -> str:
+  # pyrefly: ignore[bad-return]
+  return 1
+"#,
+);
+
+testcase!(
+    test_pyrefly_suppression_several_codes,
+    r#"
+def foo() -> str:
+  return 1  # pyrefly: ignore[bad-assignment, bad-return]  # E: There is no `bad-assignment` error here to ignore
+"#,
+);
+
+testcase!(
+    test_pyrefly_suppression_wrong_code,
+    r#"
+def foo() -> str:
+  return 1  # pyrefly: ignore[bad-assignment]  # E: not assignable # E: There is no `bad-assignment` error here to ignore
+"#,
+);
+
+testcase!(
+    test_pyrefly_suppression_unknown_code,
+    r#"
+x: int = 1  # pyrefly: ignore[not-a-kind]  # E: `not-a-kind` is not an error kind, so it doesn't ignore anything
+"#,
+);
+
+testcase!(
+    test_inline_config_errors,
+    r#"
+# pyrefly: errors = {bad-return = false}
+def foo() -> str:
+  return 1
+x: str = 1  # E: not assignable
+"#,
+);
+
+testcase!(
+    test_inline_config_invalid,
+    r#"
+# pyrefly: untyped-def-behavior = sometimes  # E: Invalid value for `untyped-def-behavior`
+"#,
+);
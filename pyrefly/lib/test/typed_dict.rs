@@ -827,3 +827,27 @@ class TD3(TypedDict, bar="test", baz=False):  # E: TypedDict does not support ke
     x: int
 "#,
 );
+
+testcase!(
+    test_typed_dict_explain_mismatch,
+    r#"
+from typing import TypedDict, NotRequired, ReadOnly
+class A(TypedDict):
+    x: int
+class B(TypedDict):
+    x: int
+    y: str
+class C(TypedDict):
+    x: ReadOnly[int]
+class D(TypedDict):
+    x: NotRequired[int]
+class E(TypedDict):
+    x: bool
+def f(a: A, c: C, d: D, e: E):
+    b: B = a  # E: TypedDict key `y` is missing
+    a2: A = c  # E: TypedDict key `x` is read-only, but is expected to be mutable
+    a3: A = d  # E: TypedDict key `x` is non-required, but is expected to be required
+    a4: A = e  # E: TypedDict key `x` has type `bool`, which is not compatible with `int`
+    c2: C = e
+"#,
+);
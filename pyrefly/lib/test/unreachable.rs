@@ -0,0 +1,128 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::testcase;
+
+testcase!(
+    test_unreachable_off_by_default,
+    r#"
+def f() -> int:
+    return 1
+    print("hello")
+"#,
+);
+
+testcase!(
+    test_unreachable_after_return,
+    r#"
+# pyrefly: errors = {unreachable = true}
+def f(x: bool) -> int:
+    if x:
+        return 1
+        print("hello")  # E: This code is unreachable
+    else:
+        raise ValueError()
+    x = False  # E: This code is unreachable
+    print("hello")
+"#,
+);
+
+testcase!(
+    test_unreachable_loop,
+    r#"
+# pyrefly: errors = {unreachable = true}
+def f(xs: list[int]) -> None:
+    for x in xs:
+        if x == 1:
+            break
+            print("hello")  # E: This code is unreachable
+        continue
+        print("hello")  # E: This code is unreachable
+    print("done")
+"#,
+);
+
+testcase!(
+    test_unreachable_no_return,
+    r#"
+# pyrefly: errors = {unreachable = true}
+import sys
+from typing import NoReturn
+def fail() -> NoReturn: ...
+def f() -> None:
+    fail()
+    print("hello")  # E: This code is unreachable
+def g() -> None:
+    sys.exit(1)
+    print("hello")  # E: This code is unreachable
+def h() -> None:
+    print("hello")
+    print("hello")
+"#,
+);
+
+testcase!(
+    test_unreachable_narrowed_to_never,
+    r#"
+# pyrefly: errors = {unreachable = true}
+def f(x: int, y: int | None) -> int:
+    if x is None:
+        return 0  # E: This code is unreachable
+    if y is None:
+        return 0
+    return x
+"#,
+);
+
+testcase!(
+    test_unreachable_static_conditions,
+    r#"
+# pyrefly: errors = {unreachable = true}
+import sys
+from typing import TYPE_CHECKING
+if TYPE_CHECKING:
+    x = 1
+else:
+    x = 2
+def f() -> int:
+    if sys.version_info >= (3, 0):
+        return 1
+    return 2
+"#,
+);
+
+testcase!(
+    test_unreachable_exhaustiveness_check,
+    r#"
+# pyrefly: errors = {unreachable = true}
+import typing
+from typing import assert_never
+def f(x: int | str) -> int:
+    if isinstance(x, int):
+        return 1
+    elif isinstance(x, str):
+        return 2
+    else:
+        assert_never(x)
+def g(x: int | str) -> int:
+    if isinstance(x, int):
+        return 1
+    elif isinstance(x, str):
+        return 2
+    else:
+        typing.assert_never(x)
+def h(x: int) -> int:
+    if not isinstance(x, int):
+        raise TypeError()
+    return x
+def j(x: int) -> int:
+    if isinstance(x, int):
+        return 1
+    else:
+        return 2  # E: This code is unreachable
+"#,
+);
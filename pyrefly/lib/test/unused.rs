@@ -0,0 +1,94 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::testcase;
+
+testcase!(
+    test_unused_off_by_default,
+    r#"
+import os
+def _f() -> None:
+    x = 1
+"#,
+);
+
+testcase!(
+    test_unused_import,
+    r#"
+# pyrefly: errors = {unused-import = true}
+from __future__ import annotations
+import os  # E: Import `os` is unused
+import sys
+import json as json
+import re as _re
+from typing import Any, List, Sequence  # E: Import `List` is unused
+x: Any = sys.argv
+def f() -> "Sequence[int]":
+    import re  # E: Import `re` is unused
+    return []
+"#,
+);
+
+testcase!(
+    test_unused_import_dunder_all,
+    r#"
+# pyrefly: errors = {unused-import = true}
+from os import path
+from typing import Any  # E: Import `Any` is unused
+__all__ = ["path"]
+"#,
+);
+
+testcase!(
+    test_unused_variable,
+    r#"
+# pyrefly: errors = {unused-variable = true}
+y = 1
+def f(a: int, b: int) -> int:
+    x = 1  # E: Variable `x` is unused
+    y = 2
+    _z = 3
+    for i in range(a):
+        pass
+    for j, _k in enumerate([a]):
+        pass
+    def g() -> int:
+        return y
+    return g()
+"#,
+);
+
+testcase!(
+    test_unused_variable_captured,
+    r#"
+# pyrefly: errors = {unused-variable = true}
+count = 0
+def f() -> None:
+    n = 0
+    def inc() -> None:
+        nonlocal n
+        n = 1
+    inc()
+def g() -> None:
+    global count
+    count = 1
+"#,
+);
+
+testcase!(
+    test_unused_function,
+    r#"
+# pyrefly: errors = {unused-function = true}
+def _unused() -> None:  # E: Private function `_unused` is never used
+    pass
+def _used() -> None:
+    pass
+def public() -> None:
+    _used()
+def __getattr__(name: str) -> int: ...
+"#,
+);
@@ -39,6 +39,7 @@ use crate::error::error::print_errors;
 use crate::module::module_name::ModuleName;
 use crate::module::module_path::ModulePath;
 use crate::module::module_path::ModulePathDetails;
+use crate::plugin::Plugins;
 use crate::state::handle::Handle;
 use crate::state::require::Require;
 use crate::state::state::State;
@@ -96,6 +97,8 @@ pub struct TestEnv {
     modules: SmallMap<ModuleName, (ModulePath, Option<Arc<String>>)>,
     version: PythonVersion,
     untyped_def_behavior: UntypedDefBehavior,
+    plugins: Plugins,
+    solve_budget: Option<Duration>,
 }
 
 impl TestEnv {
@@ -117,6 +120,18 @@ impl TestEnv {
         res
     }
 
+    pub fn new_with_solve_budget(solve_budget: Duration) -> Self {
+        let mut res = Self::new();
+        res.solve_budget = Some(solve_budget);
+        res
+    }
+
+    pub fn new_with_plugins(plugins: Plugins) -> Self {
+        let mut res = Self::new();
+        res.plugins = plugins;
+        res
+    }
+
     pub fn add_with_path(&mut self, name: &str, path: &str, code: &str) {
         assert!(
             path.ends_with(".py") || path.ends_with(".pyi") || path.ends_with(".rs"),
@@ -178,6 +193,7 @@ impl TestEnv {
         config.python_environment.python_platform = Some(PythonPlatform::linux());
         config.python_environment.site_package_path = Some(Vec::new());
         config.root.untyped_def_behavior = Some(self.untyped_def_behavior);
+        config.root.solve_budget_ms = self.solve_budget.map(|x| x.as_millis() as u64);
         for (name, (path, _)) in self.modules.iter() {
             config.custom_module_paths.insert(*name, path.clone());
         }
@@ -200,7 +216,7 @@ impl TestEnv {
             .rev()
             .map(|(x, (path, _))| Handle::new(*x, path.clone(), config.dupe()))
             .collect::<Vec<_>>();
-        let state = State::new(self.config_finder());
+        let state = State::new(self.config_finder()).with_plugins(self.plugins.clone());
         let subscriber = TestSubscriber::new();
         let mut transaction =
             state.new_committable_transaction(Require::Exports, Some(Box::new(subscriber.dupe())));
@@ -240,6 +240,13 @@ pub enum FunctionKind {
     IsSubclass,
     Dataclass(Box<BoolKeywords>),
     DataclassField,
+    /// An `attrs` class decorator, such as `attr.s` or `attrs.define`. The keywords start out with
+    /// the defaults that differ between the decorators.
+    AttrsClass(Box<BoolKeywords>),
+    /// An `attrs` field specifier: `attr.ib` or `attrs.field`.
+    AttrsField,
+    /// The Pydantic field specifier, `pydantic.Field`.
+    PydanticField,
     ClassMethod,
     Overload,
     Override,
@@ -248,6 +255,7 @@ pub enum FunctionKind {
     RevealType,
     Final,
     RuntimeCheckable,
+    TotalOrdering,
     PropertySetter(Box<FuncId>),
     Def(Box<FuncId>),
     AbstractMethod,
@@ -295,6 +303,11 @@ impl BoolKeywords {
     pub fn set(&mut self, name: Name, value: bool) {
         self.0.insert(name, value);
     }
+
+    /// Get the value of a keyword, if it was set explicitly.
+    pub fn get(&self, name: &Name) -> Option<bool> {
+        self.0.get(name).copied()
+    }
 }
 
 /// Namespace for keyword names and defaults.
@@ -313,6 +326,31 @@ impl DataclassKeywords {
     pub const UNSAFE_HASH: (Name, bool) = (Name::new_static("unsafe_hash"), false);
 }
 
+/// Namespace for keyword names that only apply to `attrs` classes.
+pub struct AttrsKeywords;
+
+impl AttrsKeywords {
+    /// Whether annotated attributes are fields even without an `attr.ib()` value. When not set,
+    /// `attrs` guesses based on how the fields are declared.
+    pub const AUTO_ATTRIBS: Name = Name::new_static("auto_attribs");
+    pub const CONVERTER: Name = Name::new_static("converter");
+    pub const FACTORY: Name = Name::new_static("factory");
+    pub const ALIAS: Name = Name::new_static("alias");
+}
+
+/// Namespace for Pydantic model configuration and field keyword names.
+pub struct PydanticKeywords;
+
+impl PydanticKeywords {
+    pub const ALIAS: Name = Name::new_static("alias");
+    pub const DEFAULT_FACTORY: Name = Name::new_static("default_factory");
+    /// Whether fields with an alias can also be populated by their name.
+    pub const POPULATE_BY_NAME: Name = Name::new_static("populate_by_name");
+    /// In the model config this is a string; we record whether it is `"allow"`, in which case
+    /// `__init__` accepts arbitrary keyword arguments.
+    pub const EXTRA: Name = Name::new_static("extra");
+}
+
 impl Callable {
     pub fn fmt_with_type<'a, D: Display + 'a>(
         &'a self,
@@ -521,6 +559,21 @@ impl FunctionKind {
             ("builtins", None, "classmethod") => Self::ClassMethod,
             ("dataclasses", None, "dataclass") => Self::Dataclass(Box::new(BoolKeywords::new())),
             ("dataclasses", None, "field") => Self::DataclassField,
+            ("attr", None, "attrs") => {
+                // `attr.s` predates annotations, and orders instances by default.
+                let mut kws = BoolKeywords::new();
+                kws.set(AttrsKeywords::AUTO_ATTRIBS, false);
+                kws.set(DataclassKeywords::ORDER.0, true);
+                Self::AttrsClass(Box::new(kws))
+            }
+            ("attr", None, "define") => Self::AttrsClass(Box::new(BoolKeywords::new())),
+            ("attr", None, "frozen") => {
+                let mut kws = BoolKeywords::new();
+                kws.set(DataclassKeywords::FROZEN.0, true);
+                Self::AttrsClass(Box::new(kws))
+            }
+            ("attr", None, "attrib" | "field") => Self::AttrsField,
+            ("pydantic.fields", None, "Field") => Self::PydanticField,
             ("typing", None, "overload") => Self::Overload,
             ("typing", None, "override") => Self::Override,
             ("typing", None, "cast") => Self::Cast,
@@ -530,6 +583,7 @@ impl FunctionKind {
             ("typing", None, "runtime_checkable") => Self::RuntimeCheckable,
             ("typing_extensions", None, "runtime_checkable") => Self::RuntimeCheckable,
             ("abc", None, "abstractmethod") => Self::AbstractMethod,
+            ("functools", None, "total_ordering") => Self::TotalOrdering,
             _ => Self::Def(Box::new(FuncId {
                 module,
                 cls: cls.cloned(),
@@ -565,6 +619,21 @@ impl FunctionKind {
                 cls: None,
                 func: Name::new_static("field"),
             },
+            Self::AttrsClass(_) => FuncId {
+                module: ModuleName::attr(),
+                cls: None,
+                func: Name::new_static("attrs"),
+            },
+            Self::AttrsField => FuncId {
+                module: ModuleName::attr(),
+                cls: None,
+                func: Name::new_static("attrib"),
+            },
+            Self::PydanticField => FuncId {
+                module: ModuleName::pydantic_fields(),
+                cls: None,
+                func: Name::new_static("Field"),
+            },
             Self::Final => FuncId {
                 module: ModuleName::typing(),
                 cls: None,
@@ -600,6 +669,11 @@ impl FunctionKind {
                 cls: None,
                 func: Name::new_static("runtime_checkable"),
             },
+            Self::TotalOrdering => FuncId {
+                module: ModuleName::functools(),
+                cls: None,
+                func: Name::new_static("total_ordering"),
+            },
             Self::CallbackProtocol(cls) => FuncId {
                 module: cls.qname().module_name(),
                 cls: Some(cls.name().clone()),
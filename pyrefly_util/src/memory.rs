@@ -8,6 +8,7 @@
 use std::cmp;
 use std::fmt;
 use std::fmt::Display;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::thread::sleep;
 use std::thread::spawn;
@@ -30,6 +31,38 @@ impl Display for Bytes {
     }
 }
 
+impl Bytes {
+    pub fn new(x: u64) -> Self {
+        Self(x)
+    }
+}
+
+/// Parse a number of bytes, optionally followed by a `K`, `M`, `G` or `T` suffix
+/// (as powers of 1024, with an optional trailing `B` or `iB`), e.g. `512MB` or `8G`.
+impl FromStr for Bytes {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let s = s.trim();
+        let upper = s.to_ascii_uppercase();
+        let digits = upper.trim_end_matches("IB").trim_end_matches('B');
+        let (digits, shift) = match digits.char_indices().last() {
+            Some((i, 'K')) => (&digits[..i], 10),
+            Some((i, 'M')) => (&digits[..i], 20),
+            Some((i, 'G')) => (&digits[..i], 30),
+            Some((i, 'T')) => (&digits[..i], 40),
+            _ => (digits, 0),
+        };
+        let n = digits
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| anyhow::anyhow!("Invalid size `{s}`, expected e.g. `512MB` or `8GB`"))?;
+        n.checked_mul(1 << shift)
+            .map(Self)
+            .ok_or_else(|| anyhow::anyhow!("Size `{s}` is too large"))
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct MemoryUsage {
     pub physical: Option<Bytes>,
@@ -150,3 +183,19 @@ impl MemoryUsageTrace {
         self.state.lock().1.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes_from_str() {
+        assert_eq!("100".parse::<Bytes>().unwrap(), Bytes(100));
+        assert_eq!("1K".parse::<Bytes>().unwrap(), Bytes(1024));
+        assert_eq!("512MB".parse::<Bytes>().unwrap(), Bytes(512 << 20));
+        assert_eq!("8GiB".parse::<Bytes>().unwrap(), Bytes(8 << 30));
+        assert_eq!("2 gb".parse::<Bytes>().unwrap(), Bytes(2 << 30));
+        assert!("lots".parse::<Bytes>().is_err());
+        assert!("".parse::<Bytes>().is_err());
+    }
+}